@@ -1,9 +1,13 @@
 use futures_lite::io::{AsyncRead, AsyncReadExt};
 
+use std::collections::HashSet;
+
 use crate::base::read::counting::Counting;
 use crate::base::read::io::CombinedCentralDirectoryRecord;
+use crate::base::read::options::{lossy_name, DuplicateNames};
 use crate::base::read::{detect_filename, get_combined_sizes, get_zip64_extra_field, io};
 use crate::error::{Result, ZipError};
+use crate::file::index::NameMatching;
 use crate::spec::consts::{CDH_SIGNATURE, EOCDR_SIGNATURE, NON_ZIP64_MAX_SIZE, ZIP64_EOCDR_SIGNATURE};
 use crate::spec::header::{
     CentralDirectoryRecord, EndOfCentralDirectoryHeader, Zip64EndOfCentralDirectoryLocator,
@@ -86,6 +90,10 @@ pub struct CentralDirectoryReader<R> {
     reader: R,
     initial: bool,
     offset: u64,
+    duplicate_names: DuplicateNames,
+    duplicate_name_matching: NameMatching,
+    /// The names of the entries returned so far (as compared), if duplicates are being checked for.
+    names: HashSet<Vec<u8>>,
 }
 
 impl<R> CentralDirectoryReader<Counting<R>>
@@ -94,7 +102,29 @@ where
 {
     /// Constructs a new ZIP reader from a non-seekable source.
     pub fn new(reader: R, offset: u64) -> Self {
-        Self { reader: Counting::new(reader), offset, initial: true }
+        Self {
+            reader: Counting::new(reader),
+            offset,
+            initial: true,
+            duplicate_names: DuplicateNames::Allow,
+            duplicate_name_matching: NameMatching::Exact,
+            names: HashSet::new(),
+        }
+    }
+
+    /// Sets how entries which share a name are handled.
+    ///
+    /// As entries are returned as they're read, [`DuplicateNames::KeepLast`] isn't supported, and causes an error to
+    /// be returned once a duplicate is encountered.
+    pub fn with_duplicate_names(mut self, duplicate_names: DuplicateNames) -> Self {
+        self.duplicate_names = duplicate_names;
+        self
+    }
+
+    /// Sets how names are compared when checking for duplicates, which defaults to [`NameMatching::Exact`].
+    pub fn with_duplicate_name_matching(mut self, matching: NameMatching) -> Self {
+        self.duplicate_name_matching = matching;
+        self
     }
 
     /// Reads the next [`CentralDirectoryEntry`] from the underlying source, advancing the
@@ -103,6 +133,26 @@ where
     /// Returns `Ok(EndOfCentralDirectoryRecord)` if the end of the central directory record has
     /// been reached.
     pub async fn next(&mut self) -> Result<Entry> {
+        loop {
+            let entry = self.next_record().await?;
+            if let Entry::CentralDirectoryEntry(entry) = &entry {
+                if self.duplicate_names != DuplicateNames::Allow
+                    && !self.names.insert(self.duplicate_name_matching.key(entry.filename.as_bytes()))
+                {
+                    match self.duplicate_names {
+                        DuplicateNames::KeepFirst => continue,
+                        DuplicateNames::KeepLast => {
+                            return Err(ZipError::FeatureNotSupported("keeping the last of duplicated entries"))
+                        }
+                        _ => return Err(ZipError::DuplicateEntryName(lossy_name(&entry.filename))),
+                    }
+                }
+            }
+            return Ok(entry);
+        }
+    }
+
+    async fn next_record(&mut self) -> Result<Entry> {
         // Skip the first `CDH_SIGNATURE`. The `CentralDirectoryReader` is assumed to pick up from
         // where the streaming `ZipFileReader` left off, which means that the first record's
         // signature has already been read.
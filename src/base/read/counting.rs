@@ -23,6 +23,11 @@ impl<R> Counting<R> {
         self.bytes
     }
 
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
     /// Consumes the [`Counting`] reader and returns the inner reader.
     pub fn into_inner(self) -> R {
         self.inner
@@ -0,0 +1,115 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Integrity testing of a ZIP file's entries, akin to `zip -T`.
+
+use crate::base::read::io::entry::{WithEntry, ZipEntryReader};
+use crate::error::{Result, ZipError};
+
+use std::convert::Infallible;
+use std::future::Future;
+
+use futures_lite::io::{AsyncBufRead, AsyncReadExt};
+
+/// The size of the buffer into which entries are decompressed (and then discarded) whilst testing.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// An entry which failed an integrity test.
+#[derive(Debug)]
+pub struct EntryFailure {
+    index: usize,
+    error: ZipError,
+}
+
+impl EntryFailure {
+    /// Returns the index of the entry which failed.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the error which the entry failed with.
+    pub fn error(&self) -> &ZipError {
+        &self.error
+    }
+}
+
+/// The result of testing the integrity of every entry within a ZIP file, as returned by each ZIP reader's `test_all()`
+/// method.
+///
+/// Each entry is decompressed, and its CRC32 value and sizes are checked against those recorded in the central
+/// directory. Rather than stopping at the first failure, each entry which fails is recorded in the report.
+#[derive(Debug, Default)]
+pub struct TestReport {
+    tested: usize,
+    failures: Vec<EntryFailure>,
+}
+
+impl TestReport {
+    /// Returns whether every entry passed.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the number of entries tested.
+    pub fn tested(&self) -> usize {
+        self.tested
+    }
+
+    /// Returns the entries which failed, in ascending order of index.
+    pub fn failures(&self) -> &[EntryFailure] {
+        &self.failures
+    }
+
+    /// Records the result of testing the entry at the provided index.
+    pub(crate) fn record(&mut self, index: usize, result: Result<()>) {
+        self.tested += 1;
+        if let Err(error) = result {
+            let position = self.failures.partition_point(|failure| failure.index < index);
+            self.failures.insert(position, EntryFailure { index, error });
+        }
+    }
+}
+
+/// Reads an entry to its end, checking its CRC32 value and both of its sizes against those recorded in the central
+/// directory.
+pub(crate) async fn test_entry<R>(mut reader: ZipEntryReader<'_, R, WithEntry<'_>>) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut produced = 0;
+    loop {
+        match reader.read(&mut buffer).await? {
+            0 => break,
+            read => produced += read as u64,
+        }
+    }
+
+    let expected = reader.entry().uncompressed_size();
+    if produced != expected {
+        return Err(ZipError::UncompressedSizeMismatch { expected, actual: produced });
+    }
+    if !reader.hash_matches() {
+        return Err(ZipError::CRC32CheckError);
+    }
+    if !reader.compressed_data_exhausted() {
+        return Err(ZipError::CompressedSizeMismatch(reader.entry().compressed_size()));
+    }
+
+    Ok(())
+}
+
+/// Tests the entries at indices `0..entries` via `test`, with up to `concurrency` tests in progress at once.
+pub(crate) async fn test_all<F, Fut>(entries: usize, concurrency: usize, test: F) -> TestReport
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut report = TestReport::default();
+    let Ok(()) = crate::utils::for_each_concurrent(entries, concurrency, test, |index, result| {
+        report.record(index, result);
+        Ok::<_, Infallible>(())
+    })
+    .await;
+    report
+}
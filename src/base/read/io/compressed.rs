@@ -78,6 +78,25 @@ where
         }
     }
 
+    /// Returns a mutable reference to the inner reader.
+    pub(crate) fn inner_mut(&mut self) -> &mut R {
+        match self {
+            CompressedReader::Stored(inner) => inner,
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => inner.get_mut(),
+            #[cfg(feature = "deflate64")]
+            CompressedReader::Deflate64(inner) => inner.get_mut(),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => inner.get_mut(),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => inner.get_mut(),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => inner.get_mut(),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => inner.get_mut(),
+        }
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         match self {
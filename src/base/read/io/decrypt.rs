@@ -0,0 +1,423 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::get_winzip_aes_extra_field;
+#[cfg(feature = "aes")]
+use crate::crypto::aes::{AesCipher, AUTHENTICATION_CODE_LENGTH, PASSWORD_VERIFIER_LENGTH};
+use crate::crypto::zip_crypto::{ZipCryptoKeys, ENCRYPTION_HEADER_LENGTH};
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+#[cfg(feature = "aes")]
+use crate::spec::header::WinZipAesExtraField;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt};
+use futures_lite::ready;
+use pin_project::pin_project;
+
+/// The size of the buffer used to hold decrypted data.
+const DECRYPT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// The cipher state needed to decrypt an entry's data, derived from its encryption header.
+pub(crate) enum Decryption {
+    ZipCrypto(ZipCryptoKeys),
+    #[cfg(feature = "aes")]
+    Aes {
+        cipher: Box<AesCipher>,
+        header_length: u64,
+    },
+}
+
+impl Decryption {
+    /// Returns the number of bytes which precede the entry's data and are accounted for in its compressed size.
+    pub(crate) fn header_length(&self) -> u64 {
+        match self {
+            Decryption::ZipCrypto(_) => ENCRYPTION_HEADER_LENGTH as u64,
+            #[cfg(feature = "aes")]
+            Decryption::Aes { header_length, .. } => *header_length,
+        }
+    }
+
+    /// Returns the number of bytes which follow the entry's data and are accounted for in its compressed size.
+    pub(crate) fn trailer_length(&self) -> usize {
+        match self {
+            Decryption::ZipCrypto(_) => 0,
+            #[cfg(feature = "aes")]
+            Decryption::Aes { .. } => AUTHENTICATION_CODE_LENGTH,
+        }
+    }
+}
+
+/// Reads and verifies the encryption header of an entry, if the entry is encrypted.
+///
+/// The reader is expected to be positioned at the start of the entry's data, and is left positioned at the start of
+/// the encrypted payload.
+pub(crate) async fn read_encryption_header<R>(
+    mut reader: R,
+    entry: &ZipEntry,
+    password: Option<&[u8]>,
+) -> Result<Option<Decryption>>
+where
+    R: AsyncRead + Unpin,
+{
+    if !entry.encrypted {
+        return Ok(None);
+    }
+
+    #[cfg(feature = "aes")]
+    if let Some(aes_field) = get_winzip_aes_extra_field(&entry.extra_fields) {
+        return read_aes_header(reader, entry, aes_field, password).await.map(Some);
+    }
+    #[cfg(not(feature = "aes"))]
+    if get_winzip_aes_extra_field(&entry.extra_fields).is_some() {
+        return Err(ZipError::FeatureNotSupported("WinZip AES encryption"));
+    }
+
+    let password = password.ok_or(ZipError::PasswordRequired)?;
+    if !entry.data_descriptor && entry.compressed_size < ENCRYPTION_HEADER_LENGTH as u64 {
+        return Err(ZipError::InvalidEntryDataRange);
+    }
+
+    let mut header = [0; ENCRYPTION_HEADER_LENGTH];
+    reader.read_exact(&mut header).await?;
+
+    let check =
+        if entry.data_descriptor { (entry.last_modification_date.time >> 8) as u8 } else { (entry.crc32 >> 24) as u8 };
+
+    let mut keys = ZipCryptoKeys::new(password);
+    if !keys.verify_header(header, check) {
+        return Err(ZipError::IncorrectPassword);
+    }
+
+    Ok(Some(Decryption::ZipCrypto(keys)))
+}
+
+/// Reads the salt and password verifier of a WinZip AES-encrypted entry, and derives its cipher state.
+#[cfg(feature = "aes")]
+async fn read_aes_header<R>(
+    mut reader: R,
+    entry: &ZipEntry,
+    aes_field: &WinZipAesExtraField,
+    password: Option<&[u8]>,
+) -> Result<Decryption>
+where
+    R: AsyncRead + Unpin,
+{
+    let password = password.ok_or(ZipError::PasswordRequired)?;
+    let salt_length = aes_field.strength.salt_length();
+    let header_length = salt_length + PASSWORD_VERIFIER_LENGTH;
+    if !entry.data_descriptor && entry.compressed_size < (header_length + AUTHENTICATION_CODE_LENGTH) as u64 {
+        return Err(ZipError::InvalidEntryDataRange);
+    }
+
+    let mut header = vec![0; header_length];
+    reader.read_exact(&mut header).await?;
+
+    let (cipher, verifier) = AesCipher::new(password, aes_field.strength, &header[..salt_length]);
+    if verifier[..] != header[salt_length..] {
+        return Err(ZipError::IncorrectPassword);
+    }
+
+    Ok(Decryption::Aes { cipher: Box::new(cipher), header_length: header_length as u64 })
+}
+
+/// A wrapping reader which decrypts entry data before it reaches decompression.
+#[pin_project(project = DecryptingReaderProj)]
+pub(crate) enum DecryptingReader<R> {
+    Plain(#[pin] R),
+    ZipCrypto(#[pin] ZipCryptoReader<R>),
+    #[cfg(feature = "aes")]
+    Aes(#[pin] AesReader<R>),
+}
+
+impl<R> DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
+    ///
+    /// `size` is the number of bytes the inner reader holds, if known, which is needed to locate the authentication
+    /// code trailing AES-encrypted data.
+    #[cfg_attr(not(feature = "aes"), allow(unused_variables))]
+    pub(crate) fn new(reader: R, decryption: Option<Decryption>, size: Option<u64>) -> Self {
+        match decryption {
+            None => DecryptingReader::Plain(reader),
+            Some(Decryption::ZipCrypto(keys)) => DecryptingReader::ZipCrypto(ZipCryptoReader::new(reader, keys)),
+            #[cfg(feature = "aes")]
+            Some(Decryption::Aes { cipher, .. }) => DecryptingReader::Aes(AesReader::new(
+                reader,
+                cipher,
+                size.map(|size| size.saturating_sub(AUTHENTICATION_CODE_LENGTH as u64)),
+            )),
+        }
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub(crate) fn get_mut(&mut self) -> &mut R {
+        match self {
+            DecryptingReader::Plain(inner) => inner,
+            DecryptingReader::ZipCrypto(inner) => &mut inner.inner,
+            #[cfg(feature = "aes")]
+            DecryptingReader::Aes(inner) => &mut inner.inner,
+        }
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub(crate) fn into_inner(self) -> R {
+        match self {
+            DecryptingReader::Plain(inner) => inner,
+            DecryptingReader::ZipCrypto(inner) => inner.inner,
+            #[cfg(feature = "aes")]
+            DecryptingReader::Aes(inner) => inner.inner,
+        }
+    }
+
+    /// Completes any verification which can only happen once the entry's data has been fully read.
+    ///
+    /// For AES-encrypted entries, this reads the trailing authentication code and checks it against the data.
+    #[cfg_attr(not(feature = "aes"), allow(unused_variables))]
+    pub(crate) fn poll_finish(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self {
+            DecryptingReader::Plain(_) | DecryptingReader::ZipCrypto(_) => Poll::Ready(Ok(())),
+            #[cfg(feature = "aes")]
+            DecryptingReader::Aes(inner) => inner.poll_finish(cx),
+        }
+    }
+}
+
+impl<R> AsyncBufRead for DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        match self.project() {
+            DecryptingReaderProj::Plain(inner) => inner.poll_fill_buf(cx),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.poll_fill_buf(cx),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.poll_fill_buf(cx),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        match self.project() {
+            DecryptingReaderProj::Plain(inner) => inner.consume(amt),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.consume(amt),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.consume(amt),
+        }
+    }
+}
+
+impl<R> AsyncRead for DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        match self.project() {
+            DecryptingReaderProj::Plain(inner) => inner.poll_read(c, b),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.poll_read(c, b),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.poll_read(c, b),
+        }
+    }
+}
+
+/// A reader which decrypts ZipCrypto data from an inner [`AsyncBufRead`] implementer.
+///
+/// Decrypted bytes are consumed from the inner reader in lockstep with this reader, so no data beyond what the
+/// decompressor consumes is taken from the source (which matters for entries followed by a data descriptor).
+pub(crate) struct ZipCryptoReader<R> {
+    inner: R,
+    keys: ZipCryptoKeys,
+    buffer: Box<[u8]>,
+    position: usize,
+    filled: usize,
+}
+
+impl<R> ZipCryptoReader<R> {
+    fn new(inner: R, keys: ZipCryptoKeys) -> Self {
+        Self { inner, keys, buffer: vec![0; DECRYPT_BUFFER_SIZE].into_boxed_slice(), position: 0, filled: 0 }
+    }
+}
+
+impl<R> AsyncBufRead for ZipCryptoReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.position >= this.filled {
+            let available = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+            let length = available.len().min(this.buffer.len());
+
+            this.buffer[..length].copy_from_slice(&available[..length]);
+            this.keys.decrypt(&mut this.buffer[..length]);
+
+            this.position = 0;
+            this.filled = length;
+        }
+
+        Poll::Ready(Ok(&this.buffer[this.position..this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(this.filled - this.position);
+
+        this.position += amt;
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+impl<R> AsyncRead for ZipCryptoReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
+
+/// A reader which decrypts and authenticates WinZip AES data from an inner [`AsyncBufRead`] implementer.
+///
+/// As with [`ZipCryptoReader`], bytes are consumed from the inner reader in lockstep with this reader. Consumed bytes
+/// are fed into the authentication code, which is checked against the trailing one via [`AesReader::poll_finish()`].
+#[cfg(feature = "aes")]
+pub(crate) struct AesReader<R> {
+    inner: R,
+    cipher: Box<AesCipher>,
+    encrypted: Box<[u8]>,
+    buffer: Box<[u8]>,
+    position: usize,
+    filled: usize,
+    /// The number of encrypted bytes yet to be consumed, if known ahead of time.
+    remaining: Option<u64>,
+    code: [u8; AUTHENTICATION_CODE_LENGTH],
+    code_filled: usize,
+    verified: bool,
+}
+
+#[cfg(feature = "aes")]
+impl<R> AesReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn new(inner: R, cipher: Box<AesCipher>, remaining: Option<u64>) -> Self {
+        Self {
+            inner,
+            cipher,
+            encrypted: vec![0; DECRYPT_BUFFER_SIZE].into_boxed_slice(),
+            buffer: vec![0; DECRYPT_BUFFER_SIZE].into_boxed_slice(),
+            position: 0,
+            filled: 0,
+            remaining,
+            code: [0; AUTHENTICATION_CODE_LENGTH],
+            code_filled: 0,
+            verified: false,
+        }
+    }
+
+    fn poll_finish(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if self.verified {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Any data which wasn't read still needs to be authenticated.
+        while self.remaining.is_some_and(|remaining| remaining > 0) {
+            let length = ready!(Pin::new(&mut *self).poll_fill_buf(cx))?.len();
+            if length == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+            Pin::new(&mut *self).consume(length);
+        }
+
+        // The inner reader is positioned directly after the consumed data, regardless of what's been buffered here.
+        while self.code_filled < AUTHENTICATION_CODE_LENGTH {
+            let available = ready!(Pin::new(&mut self.inner).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+
+            let length = available.len().min(AUTHENTICATION_CODE_LENGTH - self.code_filled);
+            self.code[self.code_filled..self.code_filled + length].copy_from_slice(&available[..length]);
+            self.code_filled += length;
+            Pin::new(&mut self.inner).consume(length);
+        }
+
+        if !self.cipher.verify(&self.code) {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                ZipError::AuthenticationCodeMismatch,
+            )));
+        }
+
+        self.verified = true;
+        self.remaining = Some(0);
+        self.position = self.filled;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "aes")]
+impl<R> AsyncBufRead for AesReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.position >= this.filled && this.remaining != Some(0) {
+            let available = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+            let mut length = available.len().min(this.buffer.len());
+            if let Some(remaining) = this.remaining {
+                length = length.min(usize::try_from(remaining).unwrap_or(usize::MAX));
+            }
+
+            this.encrypted[..length].copy_from_slice(&available[..length]);
+            this.buffer[..length].copy_from_slice(&available[..length]);
+            this.cipher.apply_keystream(&mut this.buffer[..length]);
+
+            this.position = 0;
+            this.filled = length;
+        }
+
+        Poll::Ready(Ok(&this.buffer[this.position..this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(this.filled - this.position);
+
+        this.cipher.authenticate(&this.encrypted[this.position..this.position + amt]);
+        this.position += amt;
+        if let Some(remaining) = this.remaining.as_mut() {
+            *remaining -= amt as u64;
+        }
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+#[cfg(feature = "aes")]
+impl<R> AsyncRead for AesReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Locating the end of Stored entries which are followed by a data descriptor.
+//!
+//! As Stored data isn't self-terminating, the only way to find its end whilst streaming is to scan for the data
+//! descriptor signature. A candidate descriptor is only accepted if its sizes (and, for unencrypted entries, its CRC32
+//! value) agree with the data which precedes it, so a signature which happens to appear within the data is skipped.
+
+use crate::error::ZipError;
+use crate::spec::consts::{DATA_DESCRIPTOR_SIGNATURE, SIGNATURE_LENGTH};
+use crate::spec::data_descriptor::CombinedDataDescriptor;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use crc32fast::Hasher;
+use futures_lite::io::{AsyncBufRead, AsyncRead};
+
+/// The maximum number of bytes pulled from the inner reader at once whilst scanning.
+const SCAN_BUFFER_SIZE: usize = 8 * 1024;
+
+/// The parameters needed to recognise an entry's data descriptor.
+pub(crate) struct DescriptorScan {
+    /// Whether the descriptor holds 8-byte (ZIP64) sizes.
+    pub(crate) zip64: bool,
+    /// The number of bytes which preceded the scanned data but are accounted for in the compressed size.
+    pub(crate) header_length: u64,
+    /// The number of bytes at the end of the scanned data which aren't part of the entry's data (eg. the AES
+    /// authentication code), and are only made available via [`DescriptorScanner::release_trailer()`].
+    pub(crate) trailer_length: usize,
+    /// Whether the data's CRC32 value should be checked against the descriptor's.
+    pub(crate) check_crc: bool,
+}
+
+impl DescriptorScan {
+    fn descriptor_length(&self) -> usize {
+        SIGNATURE_LENGTH + if self.zip64 { 20 } else { 12 }
+    }
+}
+
+struct ScanState {
+    scan: DescriptorScan,
+    window: Vec<u8>,
+    start: usize,
+    /// The offset (from `start`) at which to resume searching for a signature, past any rejected candidates.
+    search_from: usize,
+    emitted: u64,
+    hasher: Hasher,
+    descriptor: Option<CombinedDataDescriptor>,
+    trailer_released: bool,
+}
+
+/// A reader which passes through an entry's data, optionally ending it at its data descriptor.
+///
+/// Whilst scanning, no bytes beyond the end of the data descriptor are consumed from the inner reader.
+pub(crate) struct DescriptorScanner<R> {
+    inner: R,
+    state: Option<Box<ScanState>>,
+}
+
+impl<R> DescriptorScanner<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a reader which passes through all data from the inner reader.
+    pub(crate) fn passthrough(inner: R) -> Self {
+        Self { inner, state: None }
+    }
+
+    /// Constructs a reader which ends at the data descriptor described by `scan`.
+    pub(crate) fn new(inner: R, scan: DescriptorScan) -> Self {
+        let state = ScanState {
+            scan,
+            window: Vec::new(),
+            start: 0,
+            search_from: 0,
+            emitted: 0,
+            hasher: Hasher::new(),
+            descriptor: None,
+            trailer_released: false,
+        };
+        Self { inner, state: Some(Box::new(state)) }
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub(crate) fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Takes the data descriptor consumed whilst scanning, if one was found.
+    pub(crate) fn take_descriptor(&mut self) -> Option<CombinedDataDescriptor> {
+        self.state.as_mut().and_then(|state| state.descriptor.take())
+    }
+
+    /// Makes the trailer which preceded the data descriptor available to read, once the data has been exhausted.
+    pub(crate) fn release_trailer(&mut self) {
+        if let Some(state) = self.state.as_mut() {
+            state.trailer_released = state.descriptor.is_some();
+        }
+    }
+}
+
+impl ScanState {
+    fn pending(&self) -> &[u8] {
+        &self.window[self.start..]
+    }
+
+    /// Returns the offset of the first (possibly partial) signature within the pending bytes.
+    fn find_candidate(&self) -> Option<usize> {
+        find_signature(&self.pending()[self.search_from..]).map(|offset| offset + self.search_from)
+    }
+
+    /// Checks whether the complete candidate descriptor at `offset` agrees with the data preceding it.
+    fn accept(&mut self, offset: usize) -> bool {
+        let trailer_length = self.scan.trailer_length;
+        if offset != trailer_length {
+            return false;
+        }
+
+        let length = self.scan.descriptor_length();
+        let descriptor = &self.pending()[offset..offset + length];
+        let crc = u32::from_le_bytes(descriptor[4..8].try_into().unwrap());
+        let (compressed_size, uncompressed_size) = if self.scan.zip64 {
+            (
+                u64::from_le_bytes(descriptor[8..16].try_into().unwrap()),
+                u64::from_le_bytes(descriptor[16..24].try_into().unwrap()),
+            )
+        } else {
+            (
+                u32::from_le_bytes(descriptor[8..12].try_into().unwrap()) as u64,
+                u32::from_le_bytes(descriptor[12..16].try_into().unwrap()) as u64,
+            )
+        };
+
+        if uncompressed_size != self.emitted
+            || compressed_size != self.scan.header_length + self.emitted + trailer_length as u64
+            || (self.scan.check_crc && crc != self.hasher.clone().finalize())
+        {
+            return false;
+        }
+
+        self.descriptor = Some(CombinedDataDescriptor { crc, compressed_size, uncompressed_size });
+        self.window.truncate(self.start + trailer_length);
+        true
+    }
+}
+
+/// Returns the offset of the first data descriptor signature in `data`, or of a partial signature at its end.
+fn find_signature(data: &[u8]) -> Option<usize> {
+    let signature = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes();
+
+    if let Some(offset) = data.windows(SIGNATURE_LENGTH).position(|window| window == signature) {
+        return Some(offset);
+    }
+
+    let tail = data.len().saturating_sub(SIGNATURE_LENGTH - 1);
+    (tail..data.len()).find(|&offset| signature.starts_with(&data[offset..]))
+}
+
+impl<R> AsyncBufRead for DescriptorScanner<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let Some(state) = this.state.as_mut() else {
+            return Pin::new(&mut this.inner).poll_fill_buf(cx);
+        };
+
+        loop {
+            if state.descriptor.is_some() {
+                let pending = if state.trailer_released { state.pending() } else { &[] };
+                return Poll::Ready(Ok(pending));
+            }
+
+            let candidate = state.find_candidate();
+            let limit = candidate.unwrap_or(state.pending().len());
+            let safe = limit.saturating_sub(state.scan.trailer_length);
+            if safe > 0 {
+                return Poll::Ready(Ok(&state.pending()[..safe]));
+            }
+
+            let length = state.scan.descriptor_length();
+            if let Some(offset) = candidate.filter(|offset| state.pending().len() >= offset + length) {
+                if !state.accept(offset) {
+                    state.search_from = offset + 1;
+                }
+                continue;
+            }
+
+            // Pull in more data, without consuming beyond the end of the first possible descriptor.
+            let available = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                let error = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, ZipError::DataDescriptorNotFound);
+                return Poll::Ready(Err(error));
+            }
+
+            let wanted = match candidate {
+                Some(offset) => offset + length - state.pending().len(),
+                None => find_signature(available).map_or(SCAN_BUFFER_SIZE, |offset| offset + length),
+            };
+            let pulled = available.len().min(wanted).min(SCAN_BUFFER_SIZE);
+
+            state.window.drain(..state.start);
+            state.start = 0;
+            state.window.extend_from_slice(&available[..pulled]);
+            Pin::new(&mut this.inner).consume(pulled);
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let Some(state) = this.state.as_mut() else {
+            return Pin::new(&mut this.inner).consume(amt);
+        };
+
+        let amt = amt.min(state.pending().len());
+        if state.descriptor.is_none() {
+            state.hasher.update(&state.window[state.start..state.start + amt]);
+            state.emitted += amt as u64;
+            state.search_from = state.search_from.saturating_sub(amt);
+        }
+        state.start += amt;
+    }
+}
+
+impl<R> AsyncRead for DescriptorScanner<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::io::{AsyncReadExt, BufReader};
+
+    fn descriptor(data: &[u8]) -> Vec<u8> {
+        let mut descriptor = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes().to_vec();
+        descriptor.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+        descriptor.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        descriptor.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        descriptor
+    }
+
+    #[tokio::test]
+    async fn skips_signatures_within_data() {
+        // Embed a signature (followed by a descriptor for other data) within the data itself.
+        let mut data = b"leading bytes".to_vec();
+        data.extend_from_slice(&descriptor(b"other data"));
+        data.extend_from_slice(b"trailing bytes PK\x07");
+
+        let mut archive = data.clone();
+        archive.extend_from_slice(&descriptor(&data));
+        archive.extend_from_slice(b"next header");
+
+        // A tiny buffer ensures the signature straddles multiple fills of the inner reader.
+        let scan = DescriptorScan { zip64: false, header_length: 0, trailer_length: 0, check_crc: true };
+        let mut reader = DescriptorScanner::new(BufReader::with_capacity(3, archive.as_slice()), scan);
+
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(buffer, data);
+
+        let found = reader.take_descriptor().unwrap();
+        assert_eq!(found.uncompressed_size, data.len() as u64);
+
+        let mut remaining = Vec::new();
+        reader.into_inner().read_to_end(&mut remaining).await.unwrap();
+        assert_eq!(remaining, b"next header");
+    }
+}
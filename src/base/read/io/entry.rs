@@ -2,13 +2,20 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::read::counting::Counting;
-use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
+use crate::base::read::get_winzip_aes_extra_field;
+use crate::base::read::io::decrypt::{DecryptingReader, Decryption};
+use crate::base::read::io::descriptor::{DescriptorScan, DescriptorScanner};
+use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader, poll_result_ok};
+use crate::base::read::limits::{EntryLimits, SharedLimits};
 use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
+use crate::spec::data_descriptor::CombinedDataDescriptor;
+use crate::spec::header::AesVendorVersion;
 use crate::spec::Compression;
 
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
 
 use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, Take};
 use pin_project::pin_project;
@@ -19,12 +26,16 @@ pub struct WithEntry<'a>(OwnedEntry<'a>);
 /// A type which encodes that [`ZipEntryReader`] has no associated entry data.
 pub struct WithoutEntry;
 
+/// The reader over an entry's (possibly encrypted) compressed data, as consumed by decompression.
+type DataReader<'a, R> = Counting<DecryptingReader<DescriptorScanner<Take<OwnedReader<'a, R>>>>>;
+
 /// A ZIP entry reader which may implement decompression.
 #[pin_project]
 pub struct ZipEntryReader<'a, R, E> {
     #[pin]
-    reader: HashedReader<CompressedReader<Counting<Take<OwnedReader<'a, R>>>>>,
+    reader: HashedReader<CompressedReader<DataReader<'a, R>>>,
     entry: E,
+    limits: EntryLimits,
 }
 
 impl<'a, R> ZipEntryReader<'a, R, WithoutEntry>
@@ -32,27 +43,90 @@ where
     R: AsyncBufRead + Unpin,
 {
     /// Constructs a new entry reader from its required parameters (incl. an owned R).
-    pub(crate) fn new_with_owned(reader: R, compression: Compression, size: u64) -> Self {
-        let reader =
-            HashedReader::new(CompressedReader::new(Counting::new(OwnedReader::Owned(reader).take(size)), compression));
-        Self { reader, entry: WithoutEntry }
+    ///
+    /// For encrypted entries, the encryption header is expected to have already been consumed from the reader, but
+    /// still be accounted for within `size`.
+    pub(crate) fn new_with_owned(
+        reader: R,
+        compression: Compression,
+        size: u64,
+        decryption: Option<Decryption>,
+    ) -> Self {
+        Self::new_with_reader(OwnedReader::Owned(reader), compression, size, decryption)
+    }
+
+    /// Constructs a new entry reader (incl. an owned R) whose data ends at the data descriptor which follows it.
+    ///
+    /// This is needed for entries whose data isn't self-terminating (ie. Stored entries) when the compressed size
+    /// isn't known upfront. The data descriptor is consumed, and can be retrieved via
+    /// [`ZipEntryReader::take_data_descriptor()`].
+    pub(crate) fn new_with_owned_until_descriptor(
+        reader: R,
+        compression: Compression,
+        decryption: Option<Decryption>,
+        zip64: bool,
+    ) -> Self {
+        let scan = DescriptorScan {
+            zip64,
+            header_length: decryption.as_ref().map_or(0, Decryption::header_length),
+            trailer_length: decryption.as_ref().map_or(0, Decryption::trailer_length),
+            check_crc: decryption.is_none(),
+        };
+        let reader = DescriptorScanner::new(OwnedReader::Owned(reader).take(u64::MAX), scan);
+        Self::new_with_data_reader(reader, compression, decryption, None)
     }
 
     /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
-    pub(crate) fn new_with_borrow(reader: &'a mut R, compression: Compression, size: u64) -> Self {
+    ///
+    /// For encrypted entries, the encryption header is expected to have already been consumed from the reader, but
+    /// still be accounted for within `size`.
+    pub(crate) fn new_with_borrow(
+        reader: &'a mut R,
+        compression: Compression,
+        size: u64,
+        decryption: Option<Decryption>,
+    ) -> Self {
+        Self::new_with_reader(OwnedReader::Borrow(reader), compression, size, decryption)
+    }
+
+    fn new_with_reader(
+        reader: OwnedReader<'a, R>,
+        compression: Compression,
+        size: u64,
+        decryption: Option<Decryption>,
+    ) -> Self {
+        // Readers without a known data length (ie. streamed entries with data descriptors) pass an unbounded size.
+        let known_size = size != u64::MAX;
+        let size = size.saturating_sub(decryption.as_ref().map_or(0, Decryption::header_length));
+        let reader = DescriptorScanner::passthrough(reader.take(size));
+        Self::new_with_data_reader(reader, compression, decryption, known_size.then_some(size))
+    }
+
+    fn new_with_data_reader(
+        reader: DescriptorScanner<Take<OwnedReader<'a, R>>>,
+        compression: Compression,
+        decryption: Option<Decryption>,
+        size: Option<u64>,
+    ) -> Self {
         let reader = HashedReader::new(CompressedReader::new(
-            Counting::new(OwnedReader::Borrow(reader).take(size)),
+            Counting::new(DecryptingReader::new(reader, decryption, size)),
             compression,
         ));
-        Self { reader, entry: WithoutEntry }
+        Self { reader, entry: WithoutEntry, limits: EntryLimits::default() }
+    }
+
+    /// Applies the provided limits, and rejects data beyond the entry's uncompressed size (if known).
+    pub(crate) fn with_limits(mut self, limits: Option<Arc<SharedLimits>>, uncompressed_size: Option<u64>) -> Self {
+        self.limits = EntryLimits::new(limits, uncompressed_size);
+        self
     }
 
     pub(crate) fn into_with_entry(self, entry: &'a ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Borrow(entry)) }
+        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Borrow(entry)), limits: self.limits }
     }
 
     pub(crate) fn into_with_entry_owned(self, entry: ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Owned(entry)) }
+        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Owned(entry)), limits: self.limits }
     }
 }
 
@@ -61,7 +135,22 @@ where
     R: AsyncBufRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
-        self.project().reader.poll_read(c, b)
+        let this = self.project();
+        let reader = this.reader.get_mut();
+        let read = poll_result_ok!(ready!(Pin::new(&mut *reader).poll_read(c, b)));
+
+        if let Err(err) = this.limits.check(read, reader.inner().inner().bytes_read()) {
+            return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err)));
+        }
+
+        // Once the entry's data has been exhausted, run any checks which depend on having seen all of it.
+        if read == 0 && !b.is_empty() {
+            let decrypting = reader.inner_mut().inner_mut().get_mut();
+            decrypting.get_mut().release_trailer();
+            poll_result_ok!(ready!(decrypting.poll_finish(c)));
+        }
+
+        Poll::Ready(Ok(read))
     }
 }
 
@@ -81,9 +170,24 @@ where
         self.reader.inner().inner().bytes_read()
     }
 
+    /// Takes the data descriptor consumed whilst reading the entry's data, if any.
+    ///
+    /// This is only populated for readers constructed via [`ZipEntryReader::new_with_owned_until_descriptor()`], once
+    /// EOF has been reached.
+    pub(crate) fn take_data_descriptor(&mut self) -> Option<CombinedDataDescriptor> {
+        self.reader.inner_mut().inner_mut().get_mut().get_mut().take_descriptor()
+    }
+
+    /// Returns whether all of the entry's compressed data has been consumed.
+    ///
+    /// This is only meaningful for readers constructed with a known compressed size, once EOF has been reached.
+    pub(crate) fn compressed_data_exhausted(&mut self) -> bool {
+        self.reader.inner_mut().inner_mut().get_mut().get_mut().get_mut().limit() == 0
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
-        self.reader.into_inner().into_inner().into_inner().into_inner().owned_into_inner()
+        self.reader.into_inner().into_inner().into_inner().into_inner().into_inner().into_inner().owned_into_inner()
     }
 }
 
@@ -102,7 +206,7 @@ where
     pub async fn read_to_end_checked(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
         let read = self.read_to_end(buf).await?;
 
-        if self.compute_hash() == self.entry.0.entry().crc32() {
+        if self.hash_matches() {
             Ok(read)
         } else {
             Err(ZipError::CRC32CheckError)
@@ -115,12 +219,25 @@ where
     pub async fn read_to_string_checked(&mut self, buf: &mut String) -> Result<usize> {
         let read = self.read_to_string(buf).await?;
 
-        if self.compute_hash() == self.entry.0.entry().crc32() {
+        if self.hash_matches() {
             Ok(read)
         } else {
             Err(ZipError::CRC32CheckError)
         }
     }
+
+    /// Computes the CRC32 hash and compares it against the entry's.
+    ///
+    /// AE-2 entries omit their CRC32 value in favour of the authentication code (which is checked whilst reading), so
+    /// there's nothing to compare against.
+    pub(crate) fn hash_matches(&mut self) -> bool {
+        let hash = self.compute_hash();
+        let entry = self.entry.0.entry();
+
+        hash == entry.crc32()
+            || get_winzip_aes_extra_field(&entry.extra_fields)
+                .is_some_and(|field| field.vendor_version == AesVendorVersion::Ae2)
+    }
 }
 
 enum OwnedEntry<'a> {
@@ -41,6 +41,11 @@ where
         &self.reader
     }
 
+    /// Returns a mutable reference to the inner reader.
+    pub(crate) fn inner_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         self.reader
@@ -55,7 +55,14 @@ where
     reader.seek(SeekFrom::Start(position)).await?;
 
     loop {
-        let read = reader.read(&mut buffer).await?;
+        // Fill the buffer, as a single read may return fewer bytes (eg. at the boundary between split segments).
+        let mut read = 0;
+        while read < BUFFER_SIZE {
+            match reader.read(&mut buffer[read..]).await? {
+                0 => break,
+                n => read += n,
+            }
+        }
 
         if let Some(match_index) = reverse_search_buffer(&buffer[..read], signature) {
             return Ok(position + (match_index + 1) as u64);
@@ -3,10 +3,14 @@
 
 pub(crate) mod combined_record;
 pub(crate) mod compressed;
+pub(crate) mod decrypt;
+pub(crate) mod descriptor;
 pub(crate) mod entry;
 pub(crate) mod hashed;
 pub(crate) mod locator;
 pub(crate) mod owned;
+pub(crate) mod raw;
+pub(crate) mod stored;
 
 pub use combined_record::CombinedCentralDirectoryRecord;
 
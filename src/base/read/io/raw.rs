@@ -0,0 +1,200 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Reading an entry's raw data whilst streaming, including that of entries followed by a data descriptor.
+//!
+//! The compressed size of an entry followed by a data descriptor isn't known upfront, so the end of its data is found
+//! by scanning for the descriptor (for Stored entries), or by decompressing the data (for any other entry), with the
+//! decompressed data being discarded and the raw bytes consumed by the decoder being passed through instead.
+
+use crate::base::read::io::compressed::CompressedReader;
+use crate::base::read::io::descriptor::{DescriptorScan, DescriptorScanner};
+use crate::spec::data_descriptor::CombinedDataDescriptor;
+use crate::spec::Compression;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, Take};
+
+/// The maximum number of bytes copied from the inner reader's buffer, or decompressed and discarded, at once.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// A reader over an entry's raw data (ie. as it's stored, neither decompressed nor decrypted).
+pub struct RawEntryReader<R> {
+    inner: Raw<R>,
+    /// The number of raw bytes consumed so far.
+    read: u64,
+}
+
+enum Raw<R> {
+    /// The compressed size is known upfront.
+    Sized(Take<R>),
+    /// Stored data, which ends at its data descriptor.
+    Scanned(DescriptorScanner<R>),
+    /// Compressed data, which ends where its decoder stops consuming it.
+    Decoded(Box<Decoding<R>>),
+}
+
+struct Decoding<R> {
+    decoder: CompressedReader<Recording<R>>,
+    finished: bool,
+}
+
+/// A reader which records the bytes consumed from the inner reader, without consuming any more than that.
+struct Recording<R> {
+    inner: R,
+    /// A copy of the start of the inner reader's buffer, from which `position` bytes have been consumed.
+    peeked: Vec<u8>,
+    position: usize,
+    /// The bytes consumed since the decoder was last polled, of which `emitted` have been passed through.
+    recorded: Vec<u8>,
+    emitted: usize,
+}
+
+impl<R> RawEntryReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a reader over exactly `size` bytes.
+    pub(crate) fn sized(reader: R, size: u64) -> Self {
+        Self { inner: Raw::Sized(reader.take(size)), read: 0 }
+    }
+
+    /// Constructs a reader over data which ends at its data descriptor, which is consumed when scanning Stored data.
+    ///
+    /// The data must be unencrypted, so that Stored data can be checked against the descriptor's CRC32 value whilst
+    /// scanning, and any other data can be decompressed.
+    pub(crate) fn until_descriptor(reader: R, compression: Compression, zip64: bool) -> Self {
+        let inner = if compression == Compression::Stored {
+            let scan = DescriptorScan { zip64, header_length: 0, trailer_length: 0, check_crc: true };
+            Raw::Scanned(DescriptorScanner::new(reader, scan))
+        } else {
+            let recording =
+                Recording { inner: reader, peeked: Vec::new(), position: 0, recorded: Vec::new(), emitted: 0 };
+            Raw::Decoded(Box::new(Decoding { decoder: CompressedReader::new(recording, compression), finished: false }))
+        };
+        Self { inner, read: 0 }
+    }
+
+    /// Returns the number of raw bytes read so far.
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns whether all of the data has been read, provided that a read has returned EOF.
+    pub(crate) fn exhausted(&self) -> bool {
+        match &self.inner {
+            Raw::Sized(reader) => reader.limit() == 0,
+            Raw::Scanned(_) | Raw::Decoded(_) => true,
+        }
+    }
+
+    /// Consumes this reader, returning the inner reader alongside the data descriptor consumed whilst scanning (if
+    /// any).
+    pub(crate) fn into_inner(self) -> (R, Option<CombinedDataDescriptor>) {
+        match self.inner {
+            Raw::Sized(reader) => (reader.into_inner(), None),
+            Raw::Scanned(mut reader) => {
+                let descriptor = reader.take_descriptor();
+                (reader.into_inner(), descriptor)
+            }
+            Raw::Decoded(decoding) => (decoding.decoder.into_inner().inner, None),
+        }
+    }
+}
+
+impl<R> AsyncBufRead for RawEntryReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        match &mut self.get_mut().inner {
+            Raw::Sized(reader) => Pin::new(reader).poll_fill_buf(cx),
+            Raw::Scanned(reader) => Pin::new(reader).poll_fill_buf(cx),
+            Raw::Decoded(decoding) => {
+                loop {
+                    let recording = decoding.decoder.inner_mut();
+                    if recording.emitted < recording.recorded.len() || decoding.finished {
+                        break;
+                    }
+
+                    recording.recorded.clear();
+                    recording.emitted = 0;
+                    let read = ready!(Pin::new(&mut decoding.decoder).poll_read(cx, &mut [0; BUFFER_SIZE]))?;
+                    decoding.finished = read == 0;
+                }
+
+                let recording = decoding.decoder.inner_mut();
+                Poll::Ready(Ok(&recording.recorded[recording.emitted..]))
+            }
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.read += amt as u64;
+        match &mut this.inner {
+            Raw::Sized(reader) => Pin::new(reader).consume(amt),
+            Raw::Scanned(reader) => Pin::new(reader).consume(amt),
+            Raw::Decoded(decoding) => {
+                let recording = decoding.decoder.inner_mut();
+                recording.emitted = recording.recorded.len().min(recording.emitted + amt);
+            }
+        }
+    }
+}
+
+impl<R> AsyncRead for RawEntryReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
+
+impl<R> AsyncBufRead for Recording<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.position == this.peeked.len() {
+            let available = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+            this.peeked.clear();
+            this.peeked.extend_from_slice(&available[..available.len().min(BUFFER_SIZE)]);
+            this.position = 0;
+        }
+        Poll::Ready(Ok(&this.peeked[this.position..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(this.peeked.len() - this.position);
+        this.recorded.extend_from_slice(&this.peeked[this.position..this.position + amt]);
+        this.position += amt;
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+impl<R> AsyncRead for Recording<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Error, ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek};
+
+/// A seekable reader over the data of a Stored (and unencrypted) entry.
+///
+/// Positions are relative to the start of the entry's data, and reads end at the entry's compressed size, so the
+/// reader can itself be used as the source of another reader (eg. for a ZIP file nested within an entry).
+///
+/// Unlike [`ZipEntryReader`](crate::base::read::ZipEntryReader), the data's CRC32 value isn't computed, as it may not
+/// be read in order (or in full).
+pub struct StoredEntryReader<R> {
+    reader: R,
+    start: u64,
+    length: u64,
+    position: u64,
+}
+
+impl<R> StoredEntryReader<R> {
+    /// Constructs a new reader over the `length` bytes of data which start at `start`, where `reader` is expected to
+    /// already be positioned at `start`.
+    pub(crate) fn new(reader: R, start: u64, length: u64) -> Self {
+        Self { reader, start, length, position: 0 }
+    }
+
+    /// Returns the offset of the entry's data within the inner reader.
+    pub fn data_offset(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the length of the entry's data.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns whether the entry has no data.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn remaining(&self) -> u64 {
+        self.length.saturating_sub(self.position)
+    }
+}
+
+impl<R> AsyncRead for StoredEntryReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let remaining = this.remaining();
+        if remaining == 0 || b.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let limit = b.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let read = ready!(Pin::new(&mut this.reader).poll_read(c, &mut b[..limit]))?;
+        if read == 0 {
+            return Poll::Ready(Err(ErrorKind::UnexpectedEof.into()));
+        }
+
+        this.position += read as u64;
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl<R> AsyncBufRead for StoredEntryReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let remaining = this.remaining();
+        if remaining == 0 {
+            return Poll::Ready(Ok(&[]));
+        }
+
+        let buffer = ready!(Pin::new(&mut this.reader).poll_fill_buf(c))?;
+        if buffer.is_empty() {
+            return Poll::Ready(Err(ErrorKind::UnexpectedEof.into()));
+        }
+
+        let limit = buffer.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        Poll::Ready(Ok(&buffer[..limit]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = amt.min(usize::try_from(this.remaining()).unwrap_or(usize::MAX));
+        Pin::new(&mut this.reader).consume(amt);
+        this.position += amt as u64;
+    }
+}
+
+impl<R> AsyncSeek for StoredEntryReader<R>
+where
+    R: AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+        let (position, absolute) = position
+            .and_then(|position| Some((position, this.start.checked_add(position)?)))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))?;
+
+        ready!(Pin::new(&mut this.reader).poll_seek(c, SeekFrom::Start(absolute)))?;
+        this.position = position;
+        Poll::Ready(Ok(position))
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Limits on the data produced whilst reading entries, which guard against decompression bombs.
+
+use crate::error::{Result, ZipError};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The number of bytes an entry must produce before its compression ratio is checked.
+///
+/// Small entries can legitimately have very high ratios (eg. a run of zeros), so they're exempt.
+const RATIO_CHECK_THRESHOLD: u64 = 1024 * 1024;
+
+/// Limits on the uncompressed data produced whilst reading entries.
+///
+/// Each limit is disabled by default, and exceeding one causes the entry reader to fail with a dedicated
+/// [`ZipError`] variant. Independently of these limits, entry readers always fail with
+/// [`ZipError::UncompressedSizeExceeded`] if an entry produces more data than its declared uncompressed size (when
+/// that size is known).
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::base::read::{mem::ZipFileReader, ReadLimits};
+/// # use async_zip::error::Result;
+/// #
+/// async fn run(data: Vec<u8>) -> Result<()> {
+///     let limits = ReadLimits::new()
+///         .max_entry_size(64 * 1024 * 1024)
+///         .max_total_size(1024 * 1024 * 1024)
+///         .max_compression_ratio(100);
+///     let reader = ZipFileReader::new(data).await?.with_limits(limits);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadLimits {
+    max_entry_size: Option<u64>,
+    max_total_size: Option<u64>,
+    max_compression_ratio: Option<u64>,
+}
+
+impl ReadLimits {
+    /// Constructs a set of limits with none enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of uncompressed bytes which may be read from a single entry.
+    pub fn max_entry_size(mut self, bytes: u64) -> Self {
+        self.max_entry_size = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of uncompressed bytes which may be read from all entries of an archive combined.
+    ///
+    /// The total is shared between all entry readers constructed by a ZIP reader (and its clones).
+    pub fn max_total_size(mut self, bytes: u64) -> Self {
+        self.max_total_size = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum ratio of uncompressed to compressed bytes which an entry may have.
+    ///
+    /// The ratio is only checked once an entry has produced more than 1 MiB of data.
+    pub fn max_compression_ratio(mut self, ratio: u64) -> Self {
+        self.max_compression_ratio = Some(ratio);
+        self
+    }
+}
+
+/// A set of limits alongside the total number of bytes read under them so far.
+pub(crate) struct SharedLimits {
+    limits: ReadLimits,
+    total: AtomicU64,
+}
+
+impl SharedLimits {
+    pub(crate) fn new(limits: ReadLimits) -> Arc<Self> {
+        Arc::new(Self { limits, total: AtomicU64::new(0) })
+    }
+}
+
+/// The limits applied to a single entry reader.
+#[derive(Default)]
+pub(crate) struct EntryLimits {
+    shared: Option<Arc<SharedLimits>>,
+    uncompressed_size: Option<u64>,
+    produced: u64,
+}
+
+impl EntryLimits {
+    pub(crate) fn new(shared: Option<Arc<SharedLimits>>, uncompressed_size: Option<u64>) -> Self {
+        Self { shared, uncompressed_size, produced: 0 }
+    }
+
+    /// Accounts for `read` newly produced bytes, given the number of compressed bytes consumed so far.
+    pub(crate) fn check(&mut self, read: usize, compressed: u64) -> Result<()> {
+        self.produced += read as u64;
+
+        if let Some(size) = self.uncompressed_size.filter(|size| self.produced > *size) {
+            return Err(ZipError::UncompressedSizeExceeded(size));
+        }
+
+        let Some(shared) = self.shared.as_ref() else {
+            return Ok(());
+        };
+        let limits = &shared.limits;
+
+        if let Some(limit) = limits.max_entry_size.filter(|limit| self.produced > *limit) {
+            return Err(ZipError::EntrySizeLimitExceeded(limit));
+        }
+
+        if let Some(limit) = limits.max_compression_ratio {
+            if self.produced > RATIO_CHECK_THRESHOLD && self.produced > compressed.max(1).saturating_mul(limit) {
+                return Err(ZipError::CompressionRatioLimitExceeded(limit));
+            }
+        }
+
+        let total = shared.total.fetch_add(read as u64, Ordering::Relaxed) + read as u64;
+        if let Some(limit) = limits.max_total_size.filter(|limit| total > *limit) {
+            return Err(ZipError::TotalSizeLimitExceeded(limit));
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-//! A concurrent ZIP reader which acts over an owned vector of bytes.
+//! A concurrent ZIP reader which acts over an owned buffer of bytes.
 //!
 //! Concurrency is achieved as a result of:
-//! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership.
+//! - Wrapping the provided buffer of bytes within an [`Arc`] to allow shared ownership.
 //! - Wrapping this [`Arc`] around a [`Cursor`] when reading (as the [`Arc`] can deref and coerce into a `&[u8]`).
 //!
+//! The buffer is a `Vec<u8>` by default, but may be any type which can be viewed as a `&[u8]` (eg. an `Arc<[u8]>`, or
+//! with the `bytes` feature, a `Bytes` whose slices can be handed out without copying via
+//! `ZipFileReader::entry_bytes_shared()`).
+//!
 //! ### Usage
 //! Unlike the [`seek`] module, we no longer hold a mutable reference to any inner reader which in turn, allows the
 //! construction of concurrent [`ZipEntryReader`]s. Though, note that each individual [`ZipEntryReader`] cannot be sent
@@ -67,42 +71,82 @@
 //! }
 //! ```
 
-#[cfg(doc)]
 use crate::base::read::seek;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
 
+use crate::base::read::integrity::{self, TestReport};
+use crate::base::read::io::decrypt::read_encryption_header;
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::stored::StoredEntryReader;
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::base::read::nested::{self, NestedOptions, NestedSource};
+use crate::base::read::options::ReadOptions;
+use crate::base::read::verify::VerificationReport;
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::spec::Compression;
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
-use futures_lite::io::Cursor;
+use futures_lite::io::{AsyncReadExt, Cursor, Take};
 
 use super::io::entry::{WithEntry, WithoutEntry};
 
-struct Inner {
-    data: Vec<u8>,
+struct Inner<D> {
+    data: D,
     file: ZipFile,
 }
 
-// A concurrent ZIP reader which acts over an owned vector of bytes.
-#[derive(Clone)]
-pub struct ZipFileReader {
-    inner: Arc<Inner>,
+// A concurrent ZIP reader which acts over an owned buffer of bytes.
+pub struct ZipFileReader<D = Vec<u8>> {
+    inner: Arc<Inner<D>>,
+    password: Option<Arc<[u8]>>,
+    limits: Option<Arc<SharedLimits>>,
+}
+
+// Derived implementations would needlessly require the buffer to be `Clone`.
+impl<D> Clone for ZipFileReader<D> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), password: self.password.clone(), limits: self.limits.clone() }
+    }
 }
 
-impl ZipFileReader {
-    /// Constructs a new ZIP reader from an owned vector of bytes.
-    pub async fn new(data: Vec<u8>) -> Result<ZipFileReader> {
-        let file = crate::base::read::file(Cursor::new(&data)).await?;
+impl<D> ZipFileReader<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Constructs a new ZIP reader from an owned buffer of bytes.
+    pub async fn new(data: D) -> Result<ZipFileReader<D>> {
+        Self::new_with_options(data, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from an owned buffer of bytes, parsing its central directory with the provided
+    /// options.
+    pub async fn new_with_options(data: D, options: ReadOptions) -> Result<ZipFileReader<D>> {
+        let file = crate::base::read::file(Cursor::new(data.as_ref()), &options).await?;
         Ok(ZipFileReader::from_raw_parts(data, file))
     }
 
-    /// Constructs a ZIP reader from an owned vector of bytes and ZIP file information derived from those bytes.
+    /// Constructs a ZIP reader from an owned buffer of bytes and ZIP file information derived from those bytes.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from those bytes may lead to inaccurate parsing.
-    pub fn from_raw_parts(data: Vec<u8>, file: ZipFile) -> ZipFileReader {
-        ZipFileReader { inner: Arc::new(Inner { data, file }) }
+    pub fn from_raw_parts(data: D, file: ZipFile) -> ZipFileReader<D> {
+        ZipFileReader { inner: Arc::new(Inner { data, file }), password: None, limits: None }
+    }
+
+    /// Sets the password used to decrypt encrypted entries when no per-entry password is provided.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> ZipFileReader<D> {
+        self.password = Some(Arc::from(password.as_ref()));
+        self
+    }
+
+    /// Sets the limits applied to the data read from entries.
+    pub fn with_limits(mut self, limits: ReadLimits) -> ZipFileReader<D> {
+        self.limits = Some(SharedLimits::new(limits));
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -112,36 +156,199 @@ impl ZipFileReader {
 
     /// Returns the raw bytes provided to the reader during construction.
     pub fn data(&self) -> &[u8] {
-        &self.inner.data
+        self.inner.data.as_ref()
+    }
+
+    /// Returns the data of the Stored (and unencrypted) entry at the provided index, borrowed directly from the buffer.
+    ///
+    /// The entry's local file header is validated as when reading the entry, but its CRC32 value isn't checked.
+    pub async fn stored_data(&self, index: usize) -> Result<&[u8]> {
+        stored_data(self.data(), self.file(), index).await?.ok_or(ZipError::EntryNotStored)
+    }
+
+    /// Returns the data of the entry at the provided index.
+    ///
+    /// The data of Stored (and unencrypted) entries is borrowed directly from the buffer, whilst that of any other
+    /// entry is decompressed (and decrypted) into a new buffer. As borrowed data isn't read, it isn't counted towards
+    /// the limits set via [`ZipFileReader::with_limits()`].
+    pub async fn entry_bytes(&self, index: usize) -> Result<Cow<'_, [u8]>> {
+        self.entry_bytes_with_check(index, false).await
+    }
+
+    /// Returns the data of the entry at the provided index as per [`ZipFileReader::entry_bytes()`], and verifies its
+    /// CRC32 value.
+    pub async fn entry_bytes_checked(&self, index: usize) -> Result<Cow<'_, [u8]>> {
+        self.entry_bytes_with_check(index, true).await
+    }
+
+    async fn entry_bytes_with_check(&self, index: usize, checked: bool) -> Result<Cow<'_, [u8]>> {
+        if let Some(data) = stored_data(self.data(), self.file(), index).await? {
+            if checked && crc32fast::hash(data) != self.file().entries()[index].crc32() {
+                return Err(ZipError::CRC32CheckError);
+            }
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let mut reader = self.reader_with_entry(index).await?;
+        let mut buffer = Vec::new();
+        if checked {
+            reader.read_to_end_checked(&mut buffer).await?;
+        } else {
+            reader.read_to_end(&mut buffer).await?;
+        }
+        Ok(Cow::Owned(buffer))
+    }
+
+    /// Tests the integrity of every entry, as described by [`TestReport`].
+    pub async fn test_all(&self) -> TestReport {
+        self.test_all_concurrently(1).await
+    }
+
+    /// Tests the integrity of every entry as per [`ZipFileReader::test_all()`], with up to `concurrency` entries
+    /// being tested at once.
+    pub async fn test_all_concurrently(&self, concurrency: usize) -> TestReport {
+        integrity::test_all(self.file().entries().len(), concurrency, |index| async move {
+            integrity::test_entry(self.reader_with_entry(index).await?).await
+        })
+        .await
+    }
+
+    /// Audits every entry's local file header against its central directory record, as described by
+    /// [`VerificationReport`].
+    pub async fn verify(&self) -> Result<VerificationReport> {
+        crate::base::read::verify::verify(Cursor::new(self.data()), self.file()).await
     }
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>> {
-        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut cursor = Cursor::new(&self.inner.data[..]);
-
-        stored_entry.seek_to_data_offset(&mut cursor).await?;
+        let (reader, _) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader)
+    }
 
-        Ok(ZipEntryReader::new_with_owned(
-            cursor,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_without_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>> {
+        let (reader, _) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader)
     }
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a new entry reader for the entry with the provided name, if one exists.
+    ///
+    /// See [`ZipFile::entry_index()`] for how names are matched.
+    pub async fn reader_with_entry_by_name(
+        &self,
+        name: impl AsRef<[u8]>,
+    ) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
+        let index = self.file().entry_index(name).ok_or(ZipError::EntryNameNotFound)?;
+        self.reader_with_entry(index).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_with_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a reader over the raw data of the entry at the provided index, if the index is valid.
+    ///
+    /// Exactly the entry's compressed size is read, with the data left as it's stored (ie. neither decompressed nor
+    /// decrypted), and without its CRC32 value being checked.
+    pub async fn reader_raw(&self, index: usize) -> Result<Take<Cursor<&[u8]>>> {
+        crate::base::read::raw_entry_reader(Cursor::new(self.data()), self.file(), index).await
+    }
+
+    /// Returns a seekable reader over the data of the Stored (and unencrypted) entry at the provided index.
+    ///
+    /// See [`StoredEntryReader`] for how this differs from the readers returned by the other methods.
+    pub async fn reader_stored(&self, index: usize) -> Result<StoredEntryReader<Cursor<&[u8]>>> {
+        crate::base::read::stored_entry_reader(Cursor::new(self.data()), self.file(), index).await
+    }
+
+    /// Opens the ZIP file nested within the entry at the provided index.
+    ///
+    /// See the [`nested`] module for how the entry is read.
+    pub async fn open_nested(
+        &self,
+        index: usize,
+        options: NestedOptions,
+    ) -> Result<seek::ZipFileReader<NestedSource<Cursor<&[u8]>>>> {
+        let reader = Cursor::new(self.data());
+        nested::open(reader, self.file(), index, self.password.as_deref(), self.limits.clone(), &options).await
+    }
+
+    /// Seeks to the data of the entry at the provided index and constructs a reader over it.
+    async fn entry_reader(
+        &self,
+        index: usize,
+        password: Option<&[u8]>,
+    ) -> Result<(ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>, &StoredZipEntry)> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut cursor = Cursor::new(&self.inner.data[..]);
+        let mut cursor = Cursor::new(self.data());
 
-        stored_entry.seek_to_data_offset(&mut cursor).await?;
+        stored_entry.seek_to_data_offset(&mut cursor, self.inner.file.warnings.entry(index)).await?;
+        let decryption = read_encryption_header(&mut cursor, stored_entry, password).await?;
 
         let reader = ZipEntryReader::new_with_owned(
             cursor,
             stored_entry.entry.compression(),
             stored_entry.entry.compressed_size(),
-        );
+            decryption,
+        )
+        .with_limits(self.limits.clone(), Some(stored_entry.entry.uncompressed_size()));
 
-        Ok(reader.into_with_entry(stored_entry))
+        Ok((reader, stored_entry))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ZipFileReader<Bytes> {
+    /// Returns the data of the entry at the provided index as per [`ZipFileReader::entry_bytes()`], sharing the
+    /// buffer (rather than copying from it) when the data is borrowed.
+    pub async fn entry_bytes_shared(&self, index: usize) -> Result<Bytes> {
+        self.entry_bytes_shared_with_check(index, false).await
+    }
+
+    /// Returns the data of the entry at the provided index as per [`ZipFileReader::entry_bytes_shared()`], and
+    /// verifies its CRC32 value.
+    pub async fn entry_bytes_shared_checked(&self, index: usize) -> Result<Bytes> {
+        self.entry_bytes_shared_with_check(index, true).await
+    }
+
+    async fn entry_bytes_shared_with_check(&self, index: usize, checked: bool) -> Result<Bytes> {
+        // Borrowed data is always a slice of the buffer, so can be shared via `Bytes::slice_ref()`.
+        Ok(match self.entry_bytes_with_check(index, checked).await? {
+            Cow::Borrowed(data) => self.inner.data.slice_ref(data),
+            Cow::Owned(data) => Bytes::from(data),
+        })
+    }
+}
+
+/// Returns the data of the Stored (and unencrypted) entry at the provided index, borrowed from the bytes of the ZIP
+/// file, or `None` if the entry's data is compressed or encrypted.
+///
+/// The entry's local file header is validated as when reading the entry, but its CRC32 value isn't checked.
+pub(crate) async fn stored_data<'a>(data: &'a [u8], file: &ZipFile, index: usize) -> Result<Option<&'a [u8]>> {
+    let stored_entry = file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+    if stored_entry.entry.compression() != Compression::Stored || stored_entry.entry.encrypted() {
+        return Ok(None);
     }
+
+    let mut cursor = Cursor::new(data);
+    stored_entry.seek_to_data_offset(&mut cursor, file.warnings.entry(index)).await?;
+
+    let size = usize::try_from(stored_entry.entry.compressed_size()).map_err(|_| ZipError::InvalidEntryDataRange)?;
+    data[cursor.position() as usize..].get(..size).map(Some).ok_or(ZipError::InvalidEntryDataRange)
 }
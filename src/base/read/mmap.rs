@@ -0,0 +1,63 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a memory-mapped file.
+//!
+//! The reader is the [`mem`] reader acting over the mapping, and so is cheap to clone, and should be cloned and moved
+//! into other threads to read entries in parallel. Unlike a buffer of bytes, the ZIP file doesn't need to be read into
+//! memory upfront, with the operating system paging in only those parts of the file which are read. The data of Stored
+//! entries can be borrowed directly from the mapping via [`ZipFileReader::stored_data()`].
+//!
+//! The mapping is created by the caller and passed to [`ZipFileReader::from_mmap()`], as opening and mapping a file
+//! are blocking operations (which may need to be moved off of the async runtime, eg. via `spawn_blocking()`), and
+//! mapping a file is unsafe.
+//!
+//! # Note
+//! The file must not be modified (eg. truncated) whilst mapped, as the mapping would no longer reflect the ZIP file
+//! which was parsed, and accessing a truncated part of the mapping may terminate the process.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::read::mmap::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     let file = std::fs::File::open("./foo.zip")?;
+//!     // SAFETY: The file isn't modified whilst mapped.
+//!     let mmap = unsafe { memmap2::Mmap::map(&file)? };
+//!     let reader = ZipFileReader::from_mmap(mmap).await?;
+//!     let result = tokio::join!(read(&reader, 0), read(&reader, 1));
+//!
+//!     let data_0 = result.0?;
+//!     let data_1 = result.1?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//!
+//! async fn read(reader: &ZipFileReader, index: usize) -> Result<Vec<u8>> {
+//!     let mut entry = reader.reader_without_entry(index).await?;
+//!     let mut data = Vec::new();
+//!     entry.read_to_end(&mut data).await?;
+//!     Ok(data)
+//! }
+//! ```
+
+use crate::base::read::mem;
+use crate::error::Result;
+
+use memmap2::Mmap;
+
+/// A concurrent ZIP reader which acts over a memory-mapped file.
+pub type ZipFileReader = mem::ZipFileReader<Mmap>;
+
+impl mem::ZipFileReader<Mmap> {
+    /// Constructs a new ZIP reader from an existing memory mapping.
+    ///
+    /// The central directory may be parsed with non-default options via [`ZipFileReader::new_with_options()`].
+    pub async fn from_mmap(mmap: Mmap) -> Result<ZipFileReader> {
+        Self::new(mmap).await
+    }
+}
@@ -4,47 +4,105 @@
 //! A module which supports reading ZIP files.
 
 pub mod mem;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod seek;
 pub mod stream;
 
 pub mod cd;
 mod counting;
+pub(crate) mod integrity;
 pub(crate) mod io;
+pub(crate) mod limits;
+pub mod nested;
+pub(crate) mod options;
+pub mod range;
+pub mod recover;
+pub mod split;
+pub(crate) mod verify;
+pub(crate) mod warnings;
 
 use crate::ZipString;
 // Re-exported as part of the public API.
+pub use crate::base::read::integrity::{EntryFailure, TestReport};
 pub use crate::base::read::io::entry::WithEntry;
 pub use crate::base::read::io::entry::WithoutEntry;
 pub use crate::base::read::io::entry::ZipEntryReader;
+pub use crate::base::read::io::raw::RawEntryReader;
+pub use crate::base::read::io::stored::StoredEntryReader;
+pub use crate::base::read::limits::ReadLimits;
+pub use crate::base::read::options::{DuplicateNames, ReadOptions, Strictness};
+pub use crate::base::read::verify::{Finding, HeaderField, VerificationReport};
+pub use crate::base::read::warnings::ReadWarning;
 
 use crate::date::ZipDateTime;
 use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use crate::spec::attribute::AttributeCompatibility;
-use crate::spec::consts::{CDDS_LENGTH, CDDS_SIGNATURE, CDH_LENGTH, LFH_LENGTH};
+use crate::spec::consts::{AES_COMPRESSION_METHOD, CDDS_LENGTH, CDDS_SIGNATURE, CDH_LENGTH, LFH_LENGTH};
 use crate::spec::consts::{
-    CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_SIGNATURE,
+    CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_MIN_SIZE,
+    ZIP64_EOCDR_SIGNATURE,
 };
 use crate::spec::header::InfoZipUnicodeCommentExtraField;
 use crate::spec::header::InfoZipUnicodePathExtraField;
 use crate::spec::header::{
-    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, LocalFileHeader,
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, LocalFileHeader, WinZipAesExtraField,
     Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord, Zip64ExtendedInformationExtraField,
 };
 use crate::spec::Compression;
 use crate::string::StringEncoding;
 
 use crate::base::read::io::CombinedCentralDirectoryRecord;
+use crate::base::read::warnings::{EntryWarnings, WarningKind, Warnings};
 use crate::spec::parse::parse_extra_fields;
+use crate::spec::version::validate_compression_version;
 
-use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom};
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom, Take};
 
 /// The max buffer size used when parsing the central directory, equal to 20MiB.
 const MAX_CD_BUFFER_SIZE: usize = 20 * 1024 * 1024;
 const MIN_CENTRAL_DIRECTORY_ENTRY_SIZE: u64 = (SIGNATURE_LENGTH + CDH_LENGTH) as u64;
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+pub(crate) async fn file<R>(reader: R, options: &ReadOptions) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    file_with_disks(reader, options, &[]).await
+}
+
+/// Parses a ZIP file which may be split across several disks, given the offset at which each disk starts within the
+/// reader (or no offsets if it isn't split).
+pub(crate) async fn file_with_disks<R>(mut reader: R, options: &ReadOptions, disks: &[u64]) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let end_records = end_records(&mut reader, options, disks).await?;
+    file_with_end_records(reader, options, disks, end_records).await
+}
+
+/// The records at the end of a ZIP file, which locate its central directory.
+pub(crate) struct EndRecords {
+    eocdr: CombinedCentralDirectoryRecord,
+    comment: ZipString,
+    zip64: bool,
+    /// The offset of the end record which the central directory is expected to end at.
+    central_directory_boundary: u64,
+    prefix_length: u64,
+}
+
+impl EndRecords {
+    /// Returns the range of the central directory within the reader.
+    pub(crate) fn central_directory(&self) -> std::ops::Range<u64> {
+        let start = self.eocdr.offset_of_start_of_directory;
+        start..start + self.eocdr.directory_size
+    }
+}
+
+/// Finds and parses the end of central directory records (incl. those for ZIP64), correcting the central directory's
+/// offset for the disk it starts on and any prefix.
+pub(crate) async fn end_records<R>(mut reader: R, options: &ReadOptions, disks: &[u64]) -> Result<EndRecords>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
@@ -55,46 +113,76 @@ where
     reader.seek(SeekFrom::Start(eocdr_offset)).await?;
     let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
 
+    options.check_comment(eocdr.file_comm_length)?;
     let comment = io::read_string(&mut reader, eocdr.file_comm_length.into(), crate::StringEncoding::Utf8).await?;
 
     // Check the 20 bytes before the EOCDR for the Zip64 EOCDL, plus an extra 4 bytes because the offset
     // does not include the signature. If the ECODL exists we are dealing with a Zip64 file.
-    let (eocdr, zip64, central_directory_boundary) =
-        match eocdr_offset.checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64) {
-            None => (CombinedCentralDirectoryRecord::try_from(&eocdr)?, false, eocdr_record_offset),
-            Some(offset) => {
-                reader.seek(SeekFrom::Start(offset)).await?;
-                let zip64_locator = Zip64EndOfCentralDirectoryLocator::try_from_reader(&mut reader).await?;
-
-                match zip64_locator {
-                    Some(locator) => {
-                        reader.seek(SeekFrom::Start(locator.relative_offset)).await?;
-                        let signature = {
-                            let mut buffer = [0; SIGNATURE_LENGTH];
-                            reader.read_exact(&mut buffer).await?;
-                            u32::from_le_bytes(buffer)
-                        };
-                        if signature != ZIP64_EOCDR_SIGNATURE {
-                            return Err(ZipError::UnexpectedHeaderError(signature, ZIP64_EOCDR_SIGNATURE));
-                        }
-                        let zip64_eocdr = Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await?;
-                        validate_zip64_end_record_binding(&zip64_eocdr, locator.relative_offset, offset)?;
-                        validate_zip64_entry_count(&zip64_eocdr, locator.relative_offset)?;
-                        (CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr)?, true, locator.relative_offset)
-                    }
-                    None => (CombinedCentralDirectoryRecord::try_from(&eocdr)?, false, eocdr_record_offset),
+    let (mut eocdr, zip64, central_directory_boundary) = match eocdr_offset
+        .checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64)
+    {
+        None => (CombinedCentralDirectoryRecord::try_from(&eocdr)?, false, eocdr_record_offset),
+        Some(offset) => {
+            reader.seek(SeekFrom::Start(offset)).await?;
+            let zip64_locator = Zip64EndOfCentralDirectoryLocator::try_from_reader(&mut reader).await?;
+
+            match zip64_locator {
+                Some(locator) => {
+                    let relative_offset = split::logical_offset(
+                        disks,
+                        locator.number_of_disk_with_start_of_zip64_end_of_central_directory,
+                        locator.relative_offset,
+                    )?;
+                    let zip64_eocdr_offset = locate_zip64_eocdr(&mut reader, relative_offset, offset, options).await?;
+                    let zip64_eocdr = Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await?;
+                    validate_zip64_end_record_binding(&zip64_eocdr, zip64_eocdr_offset, offset)?;
+                    validate_zip64_entry_count(&zip64_eocdr, zip64_eocdr_offset)?;
+                    (CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr)?, true, zip64_eocdr_offset)
                 }
+                None => (CombinedCentralDirectoryRecord::try_from(&eocdr)?, false, eocdr_record_offset),
             }
-        };
+        }
+    };
+
+    if disks.is_empty() {
+        // Outdated feature so unlikely to ever make it into this crate.
+        if eocdr.disk_number != eocdr.disk_number_start_of_cd
+            || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
+        {
+            return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+        }
+    } else if eocdr.disk_number as usize + 1 != disks.len() {
+        // The end of central directory record is on the last disk.
+        return Err(ZipError::SplitSegmentCountMismatch {
+            expected: eocdr.disk_number as u64 + 1,
+            actual: disks.len(),
+        });
+    }
+    eocdr.offset_of_start_of_directory =
+        split::logical_offset(disks, eocdr.disk_number_start_of_cd, eocdr.offset_of_start_of_directory)?;
 
     validate_central_directory_range(&eocdr, central_directory_boundary)?;
 
-    // Outdated feature so unlikely to ever make it into this crate.
-    if eocdr.disk_number != eocdr.disk_number_start_of_cd
-        || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
-    {
-        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
-    }
+    // Offsets relative to the start of the ZIP data (rather than the file) are corrected by the length of the prefix.
+    let prefix_length =
+        if options.allows_prefix() { infer_prefix_length(&eocdr, central_directory_boundary) } else { 0 };
+    eocdr.offset_of_start_of_directory += prefix_length;
+
+    Ok(EndRecords { eocdr, comment, zip64, central_directory_boundary, prefix_length })
+}
+
+/// Parses the central directory located by a ZIP file's end records.
+pub(crate) async fn file_with_end_records<R>(
+    mut reader: R,
+    options: &ReadOptions,
+    disks: &[u64],
+    end_records: EndRecords,
+) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let EndRecords { eocdr, comment, zip64, central_directory_boundary, prefix_length } = end_records;
+    let warnings = Warnings::new(options.is_lenient());
 
     // Find and parse the central directory.
     reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory)).await?;
@@ -103,18 +191,72 @@ where
     // Because `eocdr.offset_of_start_of_directory` is a u64, we use MAX_CD_BUFFER_SIZE to prevent very large buffer sizes.
     let mut buf =
         BufReader::with_capacity(std::cmp::min(eocdr.offset_of_start_of_directory as _, MAX_CD_BUFFER_SIZE), reader);
-    let mut entries = crate::base::read::cd(
-        &mut buf,
-        eocdr.num_entries_in_directory,
-        eocdr.offset_of_start_of_directory,
-        eocdr.directory_size,
-        zip64,
-    )
-    .await?;
-    validate_central_directory_binding(&eocdr, central_directory_boundary)?;
+    // When lenient, an understated size is tolerated once parsed, so the central directory is instead bounded by the
+    // end records which follow it.
+    let directory_limit = if options.is_lenient() {
+        central_directory_boundary.saturating_sub(eocdr.offset_of_start_of_directory)
+    } else {
+        eocdr.directory_size
+    };
+    let mut entries = crate::base::read::cd(&mut buf, &eocdr, directory_limit, options, &warnings, disks).await?;
+    if let Err(err) = validate_central_directory_binding(&eocdr, central_directory_boundary) {
+        warnings.file().tolerate(WarningKind::CentralDirectoryBinding, err)?;
+    }
+    for entry in &mut entries {
+        entry.file_offset = entry.file_offset.checked_add(prefix_length).ok_or(ZipError::InvalidEntryDataRange)?;
+    }
     assign_entry_data_boundaries(&mut entries, eocdr.offset_of_start_of_directory);
+    let entries = options.resolve_duplicates(entries, &warnings)?;
+
+    let directory_offset = eocdr.offset_of_start_of_directory;
+    Ok(ZipFile { entries, comment, zip64, prefix_length, directory_offset, warnings, name_indices: Default::default() })
+}
+
+/// Locates the ZIP64 end record referenced by a ZIP64 locator, leaving the reader positioned after its signature.
+///
+/// If prefixes are allowed and the record isn't at the locator's offset, it's expected immediately before the
+/// locator (as is the case when it has no extensible data), with the locator's offset not accounting for a prefix.
+async fn locate_zip64_eocdr<R>(
+    reader: &mut R,
+    relative_offset: u64,
+    locator_offset: u64,
+    options: &ReadOptions,
+) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let signature = read_signature(reader, relative_offset).await?;
+    if signature == ZIP64_EOCDR_SIGNATURE {
+        return Ok(relative_offset);
+    }
+
+    let adjacent_offset = locator_offset.checked_sub(SIGNATURE_LENGTH as u64 + 8 + ZIP64_EOCDR_MIN_SIZE);
+    if let Some(offset) = adjacent_offset.filter(|offset| options.allows_prefix() && *offset > relative_offset) {
+        if read_signature(reader, offset).await? == ZIP64_EOCDR_SIGNATURE {
+            return Ok(offset);
+        }
+    }
+
+    Err(ZipError::UnexpectedHeaderError(signature, ZIP64_EOCDR_SIGNATURE))
+}
+
+async fn read_signature<R>(reader: &mut R, offset: u64) -> Result<u32>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(offset)).await?;
+    let mut buffer = [0; SIGNATURE_LENGTH];
+    reader.read_exact(&mut buffer).await?;
+    Ok(u32::from_le_bytes(buffer))
+}
 
-    Ok(ZipFile { entries, comment, zip64 })
+/// Infers the length of a prefix from where the central directory actually ends (at the selected end record)
+/// compared to where the end record claims it does.
+fn infer_prefix_length(eocdr: &CombinedCentralDirectoryRecord, boundary: u64) -> u64 {
+    boundary
+        .checked_sub(eocdr.directory_size)
+        .and_then(|start| start.checked_sub(eocdr.offset_of_start_of_directory))
+        .unwrap_or(0)
 }
 
 fn validate_zip64_entry_count(zip64_eocdr: &Zip64EndOfCentralDirectoryRecord, zip64_eocdr_offset: u64) -> Result<()> {
@@ -213,32 +355,58 @@ fn assign_entry_data_boundaries(entries: &mut [StoredZipEntry], directory_start:
 /// Parses exactly the central-directory span declared by the selected end record.
 ///
 /// Once the declared entries have been read, only an optional central-directory digital-signature record may remain.
+/// The entries are read from at most `directory_limit` bytes, which only exceeds the declared size when lenient.
 pub(crate) async fn cd<R>(
     reader: R,
-    num_of_entries: u64,
-    directory_start: u64,
-    directory_size: u64,
-    zip64: bool,
+    eocdr: &CombinedCentralDirectoryRecord,
+    directory_limit: u64,
+    options: &ReadOptions,
+    warnings: &Warnings,
+    disks: &[u64],
 ) -> Result<Vec<StoredZipEntry>>
 where
     R: AsyncRead + Unpin,
 {
+    let num_of_entries = eocdr.num_entries_in_directory;
+    let directory_start = eocdr.offset_of_start_of_directory;
+    let directory_size = eocdr.directory_size;
+    options.check_entries(num_of_entries)?;
+
     let claimed_entries = num_of_entries;
     let num_of_entries: usize = num_of_entries.try_into().map_err(|_| ZipError::TargetZip64NotSupported)?;
     let mut entries = Vec::with_capacity(cd_entry_capacity(num_of_entries, directory_start)?);
-    let mut remaining_directory_size = directory_size;
+    let mut remaining_directory_size = directory_limit;
     let mut reader = counting::Counting::new(reader);
-
-    for _ in 0..num_of_entries {
-        let entry = cd_record(&mut reader, zip64, &mut remaining_directory_size, claimed_entries).await?;
+    let mut filename_bytes = 0;
+
+    for index in 0..num_of_entries {
+        let cd_offset = directory_start + reader.bytes_read();
+        let mut entry = cd_record(
+            &mut reader,
+            &mut remaining_directory_size,
+            claimed_entries,
+            options,
+            &mut filename_bytes,
+            warnings.entry(index),
+            disks,
+        )
+        .await?;
+        entry.cd_offset = cd_offset;
         entries.push(entry);
     }
 
-    consume_central_directory_digital_signature(&mut reader, directory_size).await?;
-
-    let actual = reader.bytes_read();
-    if actual != directory_size {
-        return Err(ZipError::InvalidCentralDirectorySize { expected: directory_size, actual });
+    match consume_central_directory_digital_signature(&mut reader, directory_size).await {
+        Ok(()) => {
+            let actual = reader.bytes_read();
+            if actual != directory_size {
+                let err = ZipError::InvalidCentralDirectorySize { expected: directory_size, actual };
+                warnings.file().tolerate(WarningKind::CentralDirectorySize, err)?;
+            }
+        }
+        Err(err @ ZipError::InvalidCentralDirectorySize { .. }) => {
+            warnings.file().tolerate(WarningKind::CentralDirectorySize, err)?
+        }
+        Err(err) => return Err(err),
     }
 
     Ok(entries)
@@ -307,6 +475,26 @@ pub(crate) fn get_zip64_extra_field_mut(
     None
 }
 
+pub(crate) fn get_winzip_aes_extra_field(extra_fields: &[ExtraField]) -> Option<&WinZipAesExtraField> {
+    extra_fields.iter().find_map(|field| match field {
+        ExtraField::WinZipAes(aes_field) => Some(aes_field),
+        _ => None,
+    })
+}
+
+/// Resolves an entry's compression method, looking through the WinZip AES extra field for AES-encrypted entries (as
+/// their headers record the AES-specific method rather than the one used for the data).
+pub(crate) fn get_compression(compression: u16, extra_fields: &[ExtraField]) -> Result<Compression> {
+    if compression != AES_COMPRESSION_METHOD {
+        return Compression::try_from(compression);
+    }
+
+    match get_winzip_aes_extra_field(extra_fields) {
+        Some(aes_field) => Ok(aes_field.compression),
+        None => Err(ZipError::MissingWinZipAesExtraField),
+    }
+}
+
 pub(crate) fn get_combined_sizes(
     uncompressed_size: u32,
     compressed_size: u32,
@@ -329,9 +517,12 @@ pub(crate) fn get_combined_sizes(
 
 pub(crate) async fn cd_record<R>(
     mut reader: R,
-    _zip64: bool,
     remaining_directory_size: &mut u64,
     claimed_entries: u64,
+    options: &ReadOptions,
+    filename_bytes: &mut u64,
+    warnings: EntryWarnings<'_>,
+    disks: &[u64],
 ) -> Result<StoredZipEntry>
 where
     R: AsyncRead + Unpin,
@@ -342,7 +533,10 @@ where
 
     crate::utils::assert_signature(&mut reader, CDH_SIGNATURE).await?;
 
-    let header = CentralDirectoryRecord::from_reader(&mut reader).await?;
+    let header = CentralDirectoryRecord::from_reader_without_compression_check(&mut reader).await?;
+    if let Err(err) = validate_compression_version(header.v_needed, header.compression) {
+        warnings.tolerate(WarningKind::CentralCompressionVersion, err)?;
+    }
     let central_directory_entry_size = MIN_CENTRAL_DIRECTORY_ENTRY_SIZE
         + header.file_name_length as u64
         + header.extra_field_length as u64
@@ -351,11 +545,11 @@ where
         return Err(ZipError::InvalidCentralDirectoryEntryCount { entries: claimed_entries });
     }
     *remaining_directory_size -= central_directory_entry_size;
+    options.check_record(&header, filename_bytes)?;
 
     let header_size = (SIGNATURE_LENGTH + LFH_LENGTH) as u64;
     let trailing_size = header.file_name_length as u64 + header.extra_field_length as u64;
     let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(
         extra_field,
@@ -364,6 +558,7 @@ where
         Some(header.lh_offset),
         Some(header.disk_start),
     )?;
+    let compression = get_compression(header.compression, &extra_fields)?;
     let comment_basic = io::read_bytes(reader, header.file_comment_length.into()).await?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
@@ -371,13 +566,18 @@ where
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
     let mut file_offset = header.lh_offset as u64;
+    let mut disk = header.disk_start as u32;
     if let Some(zip64_extra_field) = zip64_extra_field {
         if file_offset == NON_ZIP64_MAX_SIZE as u64 {
             if let Some(offset) = zip64_extra_field.relative_header_offset {
                 file_offset = offset;
             }
         }
+        if let Some(disk_start_number) = zip64_extra_field.disk_start_number {
+            disk = disk_start_number;
+        }
     }
+    let file_offset = split::logical_offset(disks, disk, file_offset)?;
 
     let filename = detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref())?;
     let comment = detect_comment(comment_basic, header.flags.filename_unicode, extra_fields.as_ref());
@@ -405,10 +605,20 @@ where
         extra_fields,
         comment,
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        encryption: None,
         file_offset,
     };
 
-    Ok(StoredZipEntry { entry, file_offset, header_size: header_size + trailing_size, data_end_boundary: u64::MAX })
+    Ok(StoredZipEntry {
+        entry,
+        file_offset,
+        header_size: header_size + trailing_size,
+        data_end_boundary: u64::MAX,
+        version_made_by: header.v_made_by,
+        // Set by `cd()`, which tracks the position within the central directory.
+        cd_offset: 0,
+    })
 }
 
 pub(crate) async fn lfh<R>(mut reader: R, file_offset: u64) -> Result<Option<ZipEntry>>
@@ -428,19 +638,14 @@ where
 
     let header = LocalFileHeader::from_reader(&mut reader).await?;
     let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size, None, None)?;
+    let compression = get_compression(header.compression, &extra_fields)?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
     let (uncompressed_size, compressed_size) =
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
-    if header.flags.data_descriptor && compression == Compression::Stored {
-        return Err(ZipError::FeatureNotSupported(
-            "stream reading entries with data descriptors & Stored compression mode",
-        ));
-    }
     let filename = detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref())?;
 
     let entry = ZipEntry {
@@ -466,12 +671,42 @@ where
         extra_fields,
         comment: String::new().into(),
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        encryption: None,
         file_offset,
     };
 
     Ok(Some(entry))
 }
 
+/// Seeks to the data of the entry at the provided index and limits the reader to its compressed size.
+///
+/// The data isn't decrypted, decompressed, or checked against the entry's CRC32 value.
+pub(crate) async fn raw_entry_reader<R>(mut reader: R, file: &ZipFile, index: usize) -> Result<Take<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let stored_entry = file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+    stored_entry.seek_to_data_offset(&mut reader, file.warnings.entry(index)).await?;
+    Ok(reader.take(stored_entry.entry.compressed_size()))
+}
+
+/// Seeks to the data of the Stored (and unencrypted) entry at the provided index and constructs a seekable reader over
+/// it.
+pub(crate) async fn stored_entry_reader<R>(mut reader: R, file: &ZipFile, index: usize) -> Result<StoredEntryReader<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let stored_entry = file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+    if stored_entry.entry.compression() != Compression::Stored || stored_entry.entry.encrypted() {
+        return Err(ZipError::EntryNotStored);
+    }
+
+    stored_entry.seek_to_data_offset(&mut reader, file.warnings.entry(index)).await?;
+    let start = reader.seek(SeekFrom::Current(0)).await?;
+    Ok(StoredEntryReader::new(reader, start, stored_entry.entry.compressed_size()))
+}
+
 fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
     if basic_is_utf8 {
         ZipString::new(basic, StringEncoding::Utf8)
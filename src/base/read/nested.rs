@@ -0,0 +1,286 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Support for reading ZIP files nested within the entries of another (eg. JARs within a WAR file).
+//!
+//! A nested ZIP file is opened via the `open_nested()` method of each random-access reader (eg.
+//! [`mem::ZipFileReader::open_nested()`]), which returns a [`seek::ZipFileReader`] over the entry, providing the nested
+//! file's [`ZipFile`] and readers for its entries.
+//!
+//! Stored (and unencrypted) entries are read in place via a [`StoredEntryReader`], without copying any data. Other
+//! entries have to be decompressed before they can be seeked, so are buffered in full, either in memory or (with the
+//! `tokio-fs` feature) within a temporary file. The size which may be buffered is capped via
+//! [`NestedOptions::max_buffered_size()`].
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::read::mem::ZipFileReader;
+//! # use async_zip::base::read::nested::NestedOptions;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! async fn run(data: Vec<u8>) -> Result<()> {
+//!     let reader = ZipFileReader::new(data).await?;
+//!     let index = reader.file().entry_index("WEB-INF/lib/foo.jar").unwrap();
+//!
+//!     let mut nested = reader.open_nested(index, NestedOptions::new()).await?;
+//!     let mut data = Vec::new();
+//!     nested.reader_with_entry(0).await?.read_to_end_checked(&mut data).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::base::read::io::decrypt::read_encryption_header;
+use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::stored::StoredEntryReader;
+use crate::base::read::limits::SharedLimits;
+#[cfg(doc)]
+use crate::base::read::mem;
+use crate::base::read::options::ReadOptions;
+use crate::base::read::seek;
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::Compression;
+
+use std::io::SeekFrom;
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, Cursor};
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+/// The default maximum number of bytes which may be buffered for a compressed nested ZIP file, equal to 64MiB.
+pub const DEFAULT_MAX_BUFFERED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Options which control how a nested ZIP file is opened.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::base::read::nested::NestedOptions;
+/// # use async_zip::base::read::ReadOptions;
+/// #
+/// let options = NestedOptions::new()
+///     .max_buffered_size(256 * 1024 * 1024)
+///     .read_options(ReadOptions::new().max_entries(10_000));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestedOptions {
+    max_buffered_size: u64,
+    read_options: ReadOptions,
+    #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+    temp_dir: Option<PathBuf>,
+}
+
+impl Default for NestedOptions {
+    fn default() -> Self {
+        Self {
+            max_buffered_size: DEFAULT_MAX_BUFFERED_SIZE,
+            read_options: ReadOptions::default(),
+            #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+            temp_dir: None,
+        }
+    }
+}
+
+impl NestedOptions {
+    /// Constructs the default set of options, which buffer up to [`DEFAULT_MAX_BUFFERED_SIZE`] bytes in memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum (uncompressed) size of a compressed entry which may be buffered.
+    ///
+    /// Larger entries fail with [`ZipError::NestedArchiveLimitExceeded`]. Stored entries aren't buffered, so aren't
+    /// subject to this limit.
+    pub fn max_buffered_size(mut self, bytes: u64) -> Self {
+        self.max_buffered_size = bytes;
+        self
+    }
+
+    /// Sets the options used to parse the nested ZIP file's central directory.
+    pub fn read_options(mut self, options: ReadOptions) -> Self {
+        self.read_options = options;
+        self
+    }
+
+    /// Buffers compressed entries within a temporary file in the provided directory (eg. [`std::env::temp_dir()`]),
+    /// rather than in memory.
+    ///
+    /// The file is removed once the [`NestedSource`] is dropped.
+    #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+}
+
+/// The source of a nested ZIP file, which is either a view of the outer ZIP file or a buffered copy of the entry.
+pub struct NestedSource<R>(Source<R>);
+
+enum Source<R> {
+    Stored(StoredEntryReader<R>),
+    Memory(Cursor<Vec<u8>>),
+    #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+    TempFile(TempFile),
+}
+
+impl<R> NestedSource<R> {
+    /// Returns whether the entry was buffered (as opposed to being read in place).
+    pub fn is_buffered(&self) -> bool {
+        !matches!(self.0, Source::Stored(_))
+    }
+}
+
+impl<R> AsyncRead for NestedSource<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        match &mut self.get_mut().0 {
+            Source::Stored(reader) => Pin::new(reader).poll_read(c, b),
+            Source::Memory(reader) => Pin::new(reader).poll_read(c, b),
+            #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+            Source::TempFile(file) => Pin::new(&mut file.reader).poll_read(c, b),
+        }
+    }
+}
+
+impl<R> AsyncBufRead for NestedSource<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        match &mut self.get_mut().0 {
+            Source::Stored(reader) => Pin::new(reader).poll_fill_buf(c),
+            Source::Memory(reader) => Pin::new(reader).poll_fill_buf(c),
+            #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+            Source::TempFile(file) => Pin::new(&mut file.reader).poll_fill_buf(c),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        match &mut self.get_mut().0 {
+            Source::Stored(reader) => Pin::new(reader).consume(amt),
+            Source::Memory(reader) => Pin::new(reader).consume(amt),
+            #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+            Source::TempFile(file) => Pin::new(&mut file.reader).consume(amt),
+        }
+    }
+}
+
+impl<R> AsyncSeek for NestedSource<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        match &mut self.get_mut().0 {
+            Source::Stored(reader) => Pin::new(reader).poll_seek(c, pos),
+            Source::Memory(reader) => Pin::new(reader).poll_seek(c, pos),
+            #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+            Source::TempFile(file) => Pin::new(&mut file.reader).poll_seek(c, pos),
+        }
+    }
+}
+
+/// Opens the ZIP file nested within the entry at the provided index, reading it in place if it's Stored and buffering
+/// it otherwise.
+pub(crate) async fn open<R>(
+    mut reader: R,
+    file: &ZipFile,
+    index: usize,
+    password: Option<&[u8]>,
+    limits: Option<Arc<SharedLimits>>,
+    options: &NestedOptions,
+) -> Result<seek::ZipFileReader<NestedSource<R>>>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let stored_entry = file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+    let source = if stored_entry.entry.compression() == Compression::Stored && !stored_entry.entry.encrypted() {
+        Source::Stored(crate::base::read::stored_entry_reader(reader, file, index).await?)
+    } else {
+        let size = stored_entry.entry.uncompressed_size();
+        if size > options.max_buffered_size {
+            return Err(ZipError::NestedArchiveLimitExceeded { size, limit: options.max_buffered_size });
+        }
+
+        stored_entry.seek_to_data_offset(&mut reader, file.warnings.entry(index)).await?;
+        let decryption = read_encryption_header(&mut reader, stored_entry, password).await?;
+        let mut entry_reader = ZipEntryReader::new_with_owned(
+            reader,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+            decryption,
+        )
+        .with_limits(limits, Some(size))
+        .into_with_entry(stored_entry);
+
+        #[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+        if let Some(dir) = &options.temp_dir {
+            let mut file = TempFile::create(dir).await?;
+            let mut writer = tokio::io::BufWriter::new(file.reader.get_mut().get_mut()).compat_write();
+            futures_lite::io::copy(&mut entry_reader, &mut writer).await?;
+            futures_lite::io::AsyncWriteExt::flush(&mut writer).await?;
+            if !entry_reader.hash_matches() {
+                return Err(ZipError::CRC32CheckError);
+            }
+            futures_lite::io::AsyncSeekExt::seek(&mut file.reader, SeekFrom::Start(0)).await?;
+
+            let source = NestedSource(Source::TempFile(file));
+            return seek::ZipFileReader::new_with_options(source, options.read_options).await;
+        }
+
+        let mut buffer = Vec::with_capacity(usize::try_from(size).unwrap_or(0));
+        entry_reader.read_to_end(&mut buffer).await?;
+        if !entry_reader.hash_matches() {
+            return Err(ZipError::CRC32CheckError);
+        }
+        Source::Memory(Cursor::new(buffer))
+    };
+
+    seek::ZipFileReader::new_with_options(NestedSource(source), options.read_options).await
+}
+
+/// A temporary file which is removed once dropped.
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+struct TempFile {
+    reader: Compat<tokio::io::BufReader<tokio::fs::File>>,
+    // Declared after the reader so that the file is closed before it's removed.
+    _path: TempPath,
+}
+
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+struct TempPath(PathBuf);
+
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(all(feature = "tokio", feature = "tokio-fs"))]
+impl TempFile {
+    /// Creates a new, uniquely named file within the provided directory.
+    async fn create(dir: &Path) -> std::io::Result<Self> {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        loop {
+            let name = format!(".async_zip_nested_{}_{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed));
+            let path = dir.join(name);
+            match tokio::fs::OpenOptions::new().read(true).write(true).create_new(true).open(&path).await {
+                Ok(file) => {
+                    return Ok(Self { reader: tokio::io::BufReader::new(file).compat(), _path: TempPath(path) });
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
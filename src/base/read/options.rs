@@ -0,0 +1,232 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Options which control how a ZIP file's central directory is parsed.
+
+use crate::base::read::warnings::Warnings;
+use crate::entry::StoredZipEntry;
+use crate::error::{Result, ZipError};
+use crate::file::index::NameMatching;
+use crate::spec::header::CentralDirectoryRecord;
+use crate::ZipString;
+
+use std::collections::HashMap;
+
+/// How entries which share a name are handled.
+///
+/// Names are compared via the [`NameMatching`] set by [`ReadOptions::duplicate_name_matching()`], which matches names
+/// exactly by default. Different tools extract different copies of a duplicated name, so an archive which contains
+/// them can present different files to different consumers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateNames {
+    /// Keep all entries, without checking for duplicates.
+    #[default]
+    Allow,
+    /// Reject the archive with [`ZipError::DuplicateEntryName`].
+    Reject,
+    /// Keep the first entry with each name, discarding later ones.
+    KeepFirst,
+    /// Keep the last entry with each name, discarding earlier ones.
+    KeepLast,
+}
+
+/// How strictly a ZIP file is checked whilst it's read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Fail on any inconsistency.
+    #[default]
+    Strict,
+    /// Tolerate inconsistencies which are commonly produced by buggy writers but don't prevent the entries from being
+    /// located, collecting them as warnings (see [`ZipFile::warnings()`](crate::ZipFile::warnings)) rather than
+    /// failing.
+    ///
+    /// The following are tolerated:
+    /// - A central directory whose size doesn't match the size declared by the end of central directory record
+    ///   ([`ZipError::InvalidCentralDirectorySize`]), including one followed by a malformed digital signature record.
+    ///   The central directory is then bounded by the end records which follow it, rather than by its declared size.
+    /// - A central directory which doesn't end where the end of central directory record begins
+    ///   ([`ZipError::InvalidCentralDirectoryBinding`]).
+    /// - A version needed to extract which is too low for the entry's compression method
+    ///   ([`ZipError::InvalidCompressionVersion`]).
+    /// - Sizes within a local file header which don't match the central directory
+    ///   ([`ZipError::LocalFileHeaderSizeMismatch`]), in which case the central directory's sizes are used.
+    Lenient,
+}
+
+/// Options which control how a ZIP file's central directory is parsed.
+///
+/// The limits are checked whilst parsing (before the data they describe is allocated), and are each disabled by
+/// default. Exceeding one causes the ZIP reader's construction to fail with a dedicated [`ZipError`] variant.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::base::read::{mem::ZipFileReader, DuplicateNames, ReadOptions};
+/// # use async_zip::error::Result;
+/// #
+/// async fn run(data: Vec<u8>) -> Result<()> {
+///     let options = ReadOptions::new()
+///         .max_entries(10_000)
+///         .max_total_filename_size(1024 * 1024)
+///         .duplicate_names(DuplicateNames::Reject);
+///     let reader = ZipFileReader::new_with_options(data, options).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    max_entries: Option<u64>,
+    max_total_filename_size: Option<u64>,
+    max_extra_field_size: Option<u64>,
+    max_comment_size: Option<u64>,
+    duplicate_names: DuplicateNames,
+    duplicate_name_matching: NameMatching,
+    allow_prefix: bool,
+    strictness: Strictness,
+}
+
+impl ReadOptions {
+    /// Constructs the default options, with no limits, duplicate names allowed, and strict checks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of entries which the central directory may declare.
+    pub fn max_entries(mut self, entries: u64) -> Self {
+        self.max_entries = Some(entries);
+        self
+    }
+
+    /// Sets the maximum number of bytes which all entries' filenames may occupy combined.
+    pub fn max_total_filename_size(mut self, bytes: u64) -> Self {
+        self.max_total_filename_size = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of bytes which a single entry's extra fields may occupy.
+    pub fn max_extra_field_size(mut self, bytes: u64) -> Self {
+        self.max_extra_field_size = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of bytes which the archive's comment, or a single entry's comment, may occupy.
+    pub fn max_comment_size(mut self, bytes: u64) -> Self {
+        self.max_comment_size = Some(bytes);
+        self
+    }
+
+    /// Sets how entries which share a name are handled.
+    pub fn duplicate_names(mut self, duplicate_names: DuplicateNames) -> Self {
+        self.duplicate_names = duplicate_names;
+        self
+    }
+
+    /// Sets how names are compared when checking for duplicates, which defaults to [`NameMatching::Exact`].
+    ///
+    /// Names which only differ by case (or Unicode normalization) are extracted to the same path on some file systems,
+    /// which [`NameMatching::CaseInsensitive`] (or `NameMatching::Normalized`, with the `unicode-normalization`
+    /// feature) also treats as duplicates.
+    pub fn duplicate_name_matching(mut self, matching: NameMatching) -> Self {
+        self.duplicate_name_matching = matching;
+        self
+    }
+
+    /// Sets whether data prepended to the ZIP file (eg. a self-extracting stub or a shell script) is allowed, even
+    /// when the ZIP file's offsets don't account for it.
+    ///
+    /// Such a ZIP file's offsets are relative to the start of the ZIP data rather than the start of the file, so are
+    /// otherwise rejected. When allowed, the prefix's length is inferred from where the central directory actually
+    /// starts compared to where the end of central directory record claims it does, and every entry's offset is
+    /// corrected by it. The inferred length is available via
+    /// [`ZipFile::prefix_length()`](crate::ZipFile::prefix_length).
+    pub fn allow_prefix(mut self, allow: bool) -> Self {
+        self.allow_prefix = allow;
+        self
+    }
+
+    /// Sets how strictly the ZIP file is checked, both whilst parsing its central directory and whilst reading its
+    /// entries.
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    pub(crate) fn is_lenient(&self) -> bool {
+        self.strictness == Strictness::Lenient
+    }
+
+    pub(crate) fn allows_prefix(&self) -> bool {
+        self.allow_prefix
+    }
+
+    pub(crate) fn check_entries(&self, entries: u64) -> Result<()> {
+        match self.max_entries {
+            Some(limit) if entries > limit => Err(ZipError::EntryCountLimitExceeded { entries, limit }),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_comment(&self, length: u16) -> Result<()> {
+        match self.max_comment_size {
+            Some(limit) if length as u64 > limit => {
+                Err(ZipError::CommentLimitExceeded { length: length as u64, limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a central directory record's variable-length fields, given the filename bytes of preceding records.
+    pub(crate) fn check_record(&self, header: &CentralDirectoryRecord, filename_bytes: &mut u64) -> Result<()> {
+        *filename_bytes += header.file_name_length as u64;
+        if let Some(limit) = self.max_total_filename_size.filter(|limit| *filename_bytes > *limit) {
+            return Err(ZipError::TotalFileNameLimitExceeded(limit));
+        }
+
+        if let Some(limit) = self.max_extra_field_size.filter(|limit| header.extra_field_length as u64 > *limit) {
+            return Err(ZipError::ExtraFieldLimitExceeded { length: header.extra_field_length as u64, limit });
+        }
+
+        self.check_comment(header.file_comment_length)
+    }
+
+    /// Applies the duplicate name policy to a parsed central directory.
+    ///
+    /// The warnings collected whilst parsing are renumbered to match the entries which are kept.
+    pub(crate) fn resolve_duplicates(
+        &self,
+        entries: Vec<StoredZipEntry>,
+        warnings: &Warnings,
+    ) -> Result<Vec<StoredZipEntry>> {
+        if self.duplicate_names == DuplicateNames::Allow {
+            return Ok(entries);
+        }
+
+        let keys: Vec<_> =
+            entries.iter().map(|entry| self.duplicate_name_matching.key(entry.filename().as_bytes())).collect();
+        let mut keep = vec![true; entries.len()];
+        let mut seen = HashMap::with_capacity(entries.len());
+        for (index, key) in keys.iter().enumerate() {
+            let Some(previous) = seen.insert(key, index) else {
+                continue;
+            };
+            match self.duplicate_names {
+                DuplicateNames::Reject => {
+                    return Err(ZipError::DuplicateEntryName(lossy_name(entries[index].filename())))
+                }
+                DuplicateNames::KeepFirst => {
+                    seen.insert(key, previous);
+                    keep[index] = false;
+                }
+                DuplicateNames::KeepLast => keep[previous] = false,
+                DuplicateNames::Allow => unreachable!("duplicates aren't checked for when allowed"),
+            }
+        }
+
+        warnings.retain_entries(&keep);
+        Ok(entries.into_iter().zip(keep).filter_map(|(entry, keep)| keep.then_some(entry)).collect())
+    }
+}
+
+/// Returns a name for use within error messages.
+pub(crate) fn lossy_name(name: &ZipString) -> String {
+    String::from_utf8_lossy(name.as_bytes()).into_owned()
+}
@@ -0,0 +1,430 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which fetches only the byte ranges it needs from a [`RangeReader`] (eg. a server supporting HTTP range
+//! requests).
+//!
+//! Rather than reading the ZIP file from start to end, the following ranges are fetched:
+//! - The tail of the file, which holds the end of central directory records.
+//! - The central directory in a single request, excluding any part already fetched as part of the tail.
+//! - Each entry's local file header and data as the entry is read, in chunks of up to [`DEFAULT_CHUNK_SIZE`] bytes
+//!   (see [`ZipFileReader::with_chunk_size()`]).
+//!
+//! Entries which are going to be read can be fetched ahead of time via [`ZipFileReader::prefetch()`], which coalesces
+//! adjacent entries into a single request.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::read::range::{RangeReader, ZipFileReader};
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! # use std::ops::Range;
+//! #
+//! struct Remote {
+//!     // An HTTP client and the URL of the ZIP file.
+//! }
+//!
+//! impl RangeReader for Remote {
+//!     async fn length(&self) -> std::io::Result<u64> {
+//!         todo!("Send a `HEAD` request and return its `Content-Length`")
+//!     }
+//!
+//!     async fn read_range(&self, range: Range<u64>) -> std::io::Result<Vec<u8>> {
+//!         todo!("Send a `GET` request with a `Range: bytes={}-{}` header", range.start, range.end - 1)
+//!     }
+//! }
+//!
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new(Remote {}).await?;
+//!
+//!     let mut data = Vec::new();
+//!     let mut entry = reader.reader_with_entry_by_name("package/METADATA").await?;
+//!     entry.read_to_end_checked(&mut data).await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::base::read::integrity::{self, TestReport};
+use crate::base::read::io::decrypt::read_encryption_header;
+use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::base::read::options::ReadOptions;
+use crate::entry::StoredZipEntry;
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek, SeekFrom};
+use futures_lite::ready;
+
+/// The length of the tail fetched to locate the end of central directory record, which covers a record with the
+/// largest possible comment alongside the ZIP64 records which precede it.
+const TAIL_LENGTH: u64 = 72 * 1024;
+
+/// The default maximum number of bytes fetched by each request for an entry's data, equal to 1MiB.
+pub const DEFAULT_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// A source of a ZIP file's bytes which can be fetched by range, such as a server supporting HTTP range requests.
+pub trait RangeReader {
+    /// Returns the total length of the ZIP file in bytes.
+    fn length(&self) -> impl Future<Output = std::io::Result<u64>> + Send;
+
+    /// Fetches the bytes within the provided range, which never extends beyond the total length.
+    fn read_range(&self, range: Range<u64>) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send;
+}
+
+/// A ZIP reader which fetches the byte ranges it needs from a [`RangeReader`].
+pub struct ZipFileReader<R> {
+    source: R,
+    file: ZipFile,
+    /// The ranges fetched ahead of their entries being read, ordered by their start.
+    prefetched: Vec<(u64, Vec<u8>)>,
+    password: Option<Arc<[u8]>>,
+    limits: Option<Arc<SharedLimits>>,
+    chunk_size: u64,
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: RangeReader,
+{
+    /// Constructs a new ZIP reader from a range source, fetching its end records and central directory.
+    pub async fn new(source: R) -> Result<ZipFileReader<R>> {
+        Self::new_with_options(source, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a range source, parsing its central directory with the provided options.
+    pub async fn new_with_options(source: R, options: ReadOptions) -> Result<ZipFileReader<R>> {
+        let length = source.length().await?;
+        let tail_start = length.saturating_sub(TAIL_LENGTH);
+        let tail = fetch(&source, tail_start..length).await?;
+
+        let cursor = RangeCursor::new(&source, tail_start, Cow::Borrowed(&tail));
+        let end_records = crate::base::read::end_records(cursor, &options, &[]).await?;
+
+        // The central directory usually ends within the tail, in which case only the part before it is fetched.
+        let directory_start = end_records.central_directory().start;
+        let cursor = if directory_start < tail_start {
+            let mut data = fetch(&source, directory_start..tail_start).await?;
+            data.extend_from_slice(&tail);
+            RangeCursor::new(&source, directory_start, Cow::Owned(data))
+        } else {
+            RangeCursor::new(&source, tail_start, Cow::Borrowed(&tail))
+        };
+        let file = crate::base::read::file_with_end_records(cursor, &options, &[], end_records).await?;
+
+        Ok(ZipFileReader {
+            source,
+            file,
+            prefetched: Vec::new(),
+            password: None,
+            limits: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        })
+    }
+
+    /// Sets the password used to decrypt encrypted entries when no per-entry password is provided.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> ZipFileReader<R> {
+        self.password = Some(Arc::from(password.as_ref()));
+        self
+    }
+
+    /// Sets the limits applied to the data read from entries.
+    pub fn with_limits(mut self, limits: ReadLimits) -> ZipFileReader<R> {
+        self.limits = Some(SharedLimits::new(limits));
+        self
+    }
+
+    /// Sets the maximum number of bytes fetched by each request for an entry's local file header and data, which is
+    /// [`DEFAULT_CHUNK_SIZE`] by default.
+    ///
+    /// Entries which have been prefetched via [`ZipFileReader::prefetch()`] are read without any further requests.
+    pub fn with_chunk_size(mut self, bytes: u64) -> ZipFileReader<R> {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns a reference to the range source.
+    pub fn inner(&self) -> &R {
+        &self.source
+    }
+
+    /// Consumes this reader, returning the range source.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    /// Fetches the local file headers and data of the entries at the provided indices ahead of them being read,
+    /// coalescing the entries which are adjacent within the ZIP file into a single request.
+    ///
+    /// The fetched data is kept until this reader is dropped.
+    pub async fn prefetch(&mut self, indices: impl IntoIterator<Item = usize>) -> Result<()> {
+        let mut ranges = Vec::new();
+        for index in indices {
+            let range = entry_range(self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?)?;
+            if self.prefetched(&range).is_none() {
+                ranges.push(range);
+            }
+        }
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut coalesced: Vec<Range<u64>> = Vec::new();
+        for range in ranges {
+            match coalesced.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => coalesced.push(range),
+            }
+        }
+
+        for range in coalesced {
+            let data = fetch(&self.source, range.clone()).await?;
+            let index = self.prefetched.partition_point(|(start, _)| *start < range.start);
+            self.prefetched.insert(index, (range.start, data));
+        }
+        Ok(())
+    }
+
+    /// Tests the integrity of every entry, as described by [`TestReport`].
+    pub async fn test_all(&self) -> TestReport {
+        self.test_all_concurrently(1).await
+    }
+
+    /// Tests the integrity of every entry as per [`ZipFileReader::test_all()`], with up to `concurrency` entries
+    /// being tested at once.
+    pub async fn test_all_concurrently(&self, concurrency: usize) -> TestReport {
+        integrity::test_all(self.file().entries().len(), concurrency, |index| async move {
+            integrity::test_entry(self.reader_with_entry(index).await?).await
+        })
+        .await
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_without_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'_, RangeCursor<'_, R>, WithoutEntry>> {
+        let (reader, _) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader)
+    }
+
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_without_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, RangeCursor<'_, R>, WithoutEntry>> {
+        let (reader, _) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader)
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_with_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'_, RangeCursor<'_, R>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a new entry reader for the entry with the provided name, if one exists.
+    ///
+    /// See [`ZipFile::entry_index()`] for how names are matched.
+    pub async fn reader_with_entry_by_name(
+        &self,
+        name: impl AsRef<[u8]>,
+    ) -> Result<ZipEntryReader<'_, RangeCursor<'_, R>, WithEntry<'_>>> {
+        let index = self.file().entry_index(name).ok_or(ZipError::EntryNameNotFound)?;
+        self.reader_with_entry(index).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_with_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, RangeCursor<'_, R>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Constructs a reader over the data of the entry at the provided index, which fetches its local file header and
+    /// data in chunks as they're read (unless already prefetched).
+    async fn entry_reader(
+        &self,
+        index: usize,
+        password: Option<&[u8]>,
+    ) -> Result<(ZipEntryReader<'_, RangeCursor<'_, R>, WithoutEntry>, &StoredZipEntry)> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let range = entry_range(stored_entry)?;
+        let mut cursor = match self.prefetched(&range) {
+            Some((start, data)) => RangeCursor::new(&self.source, start, Cow::Borrowed(data)),
+            None => RangeCursor::chunked(&self.source, range, self.chunk_size),
+        };
+
+        stored_entry.seek_to_data_offset(&mut cursor, self.file.warnings.entry(index)).await?;
+        let decryption = read_encryption_header(&mut cursor, stored_entry, password).await?;
+
+        let reader = ZipEntryReader::new_with_owned(
+            cursor,
+            stored_entry.entry.compression(),
+            stored_entry.entry.compressed_size(),
+            decryption,
+        )
+        .with_limits(self.limits.clone(), Some(stored_entry.entry.uncompressed_size()));
+
+        Ok((reader, stored_entry))
+    }
+
+    /// Returns the prefetched bytes which contain the provided range, alongside the offset at which they start.
+    fn prefetched(&self, range: &Range<u64>) -> Option<(u64, &[u8])> {
+        let index = self.prefetched.partition_point(|(start, _)| *start <= range.start).checked_sub(1)?;
+        let (start, data) = &self.prefetched[index];
+        (range.end <= start + data.len() as u64).then_some((*start, data.as_slice()))
+    }
+}
+
+/// Returns the range spanning an entry's local file header and data, up to the next local file header (or the central
+/// directory) so that any data descriptor is included.
+fn entry_range(entry: &StoredZipEntry) -> Result<Range<u64>> {
+    if entry.data_end_boundary < entry.file_offset {
+        return Err(ZipError::InvalidEntryDataRange);
+    }
+    Ok(entry.file_offset..entry.data_end_boundary)
+}
+
+async fn fetch<R: RangeReader>(source: &R, range: Range<u64>) -> Result<Vec<u8>> {
+    let expected = range.end - range.start;
+    let data = source.read_range(range).await?;
+    if data.len() as u64 != expected {
+        return Err(ZipError::RangeLengthMismatch { expected, actual: data.len() as u64 });
+    }
+    Ok(data)
+}
+
+/// A reader over bytes fetched from a [`RangeReader`], positioned as though within the whole ZIP file.
+///
+/// An entry's bytes are fetched in chunks as they're read, with each chunk replacing the last. Seeking relative to the
+/// end of the reader seeks relative to the end of the fetchable range, and reading from before its start fails.
+pub struct RangeCursor<'a, R> {
+    source: &'a R,
+    /// The range which may be read, and fetched on demand if not yet buffered.
+    range: Range<u64>,
+    chunk_size: u64,
+    /// The offset at which the buffered bytes start.
+    start: u64,
+    data: Cow<'a, [u8]>,
+    position: u64,
+    pending: Option<PendingChunk<'a>>,
+}
+
+/// A request for the chunk of bytes within the provided range.
+type PendingChunk<'a> = (Range<u64>, Pin<Box<dyn Future<Output = std::io::Result<Vec<u8>>> + Send + 'a>>);
+
+impl<'a, R> RangeCursor<'a, R>
+where
+    R: RangeReader,
+{
+    /// Constructs a reader over bytes which have already been fetched.
+    fn new(source: &'a R, start: u64, data: Cow<'a, [u8]>) -> Self {
+        let range = start..start + data.len() as u64;
+        Self { source, range, chunk_size: 0, start, data, position: start, pending: None }
+    }
+
+    /// Constructs a reader over the provided range, fetching it in chunks of up to `chunk_size` bytes.
+    fn chunked(source: &'a R, range: Range<u64>, chunk_size: u64) -> Self {
+        let start = range.start;
+        Self { source, range, chunk_size, start, data: Cow::Borrowed(&[]), position: start, pending: None }
+    }
+
+    /// Fetches the chunk starting at the current position, unless it's already buffered or outside of the range.
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        loop {
+            if let Some((range, future)) = self.pending.as_mut() {
+                let data = ready!(future.as_mut().poll(cx))?;
+                let expected = range.end - range.start;
+                if data.len() as u64 != expected {
+                    let error = ZipError::RangeLengthMismatch { expected, actual: data.len() as u64 };
+                    return Poll::Ready(Err(Error::other(error)));
+                }
+                self.start = range.start;
+                self.data = Cow::Owned(data);
+                self.pending = None;
+            }
+
+            let buffered = self.start..self.start + self.data.len() as u64;
+            if buffered.contains(&self.position) || !self.range.contains(&self.position) {
+                return Poll::Ready(Ok(()));
+            }
+
+            let chunk = self.position..self.range.end.min(self.position.saturating_add(self.chunk_size));
+            self.pending = Some((chunk.clone(), Box::pin(self.source.read_range(chunk))));
+        }
+    }
+
+    fn remaining(&self) -> std::io::Result<&[u8]> {
+        let offset = self.position.checked_sub(self.start).ok_or_else(|| {
+            Error::new(ErrorKind::UnexpectedEof, "attempted to read before the start of the fetched range")
+        })?;
+        Ok(self.data.get(offset as usize..).unwrap_or_default())
+    }
+}
+
+impl<R> AsyncRead for RangeCursor<'_, R>
+where
+    R: RangeReader,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_fetch(cx))?;
+        let remaining = this.remaining()?;
+        let length = remaining.len().min(buf.len());
+        buf[..length].copy_from_slice(&remaining[..length]);
+        this.position += length as u64;
+        Poll::Ready(Ok(length))
+    }
+}
+
+impl<R> AsyncBufRead for RangeCursor<'_, R>
+where
+    R: RangeReader,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        ready!(this.poll_fetch(cx))?;
+        Poll::Ready(this.remaining())
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().position += amt as u64;
+    }
+}
+
+impl<R> AsyncSeek for RangeCursor<'_, R> {
+    fn poll_seek(self: Pin<&mut Self>, _: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.range.end.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+
+        this.position = position
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))?;
+        Poll::Ready(Ok(this.position))
+    }
+}
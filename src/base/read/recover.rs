@@ -0,0 +1,302 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Recovery of entries from a ZIP file whose central directory is missing or damaged (eg. a truncated upload).
+//!
+//! Rather than relying on the end of central directory record, [`recover()`] scans forwards through the file for
+//! local file headers, delimiting each entry's data via its declared sizes or its data descriptor. Each unencrypted
+//! entry is decompressed and checked against its CRC32 value and sizes before being salvaged, so data which merely
+//! resembles a local file header isn't mistaken for one.
+//!
+//! Information which is only stored within the central directory (such as comments and file attributes) can't be
+//! recovered, and encrypted entries followed by a data descriptor are lost (as their data can't be delimited without
+//! decrypting it). Entries whose name exactly matches that of an earlier salvaged entry are also lost, so that the
+//! salvaged entries can be written to a [`ZipFileWriter`] (which rejects such duplicates by default).
+//!
+//! The data of entries followed by a data descriptor isn't bounded by a declared size, so untrusted files should be
+//! recovered via [`recover_with_limits()`].
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::read::{recover, seek};
+//! # use async_zip::base::write::ZipFileWriter;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::Cursor;
+//! #
+//! async fn run(data: Vec<u8>) -> Result<Vec<u8>> {
+//!     let mut reader = Cursor::new(data);
+//!     let recovery = recover::recover(&mut reader).await?;
+//!     for lost in recovery.lost() {
+//!         println!("Lost the entry at {}: {}", lost.offset(), lost.error());
+//!     }
+//!
+//!     let mut writer = ZipFileWriter::new(Vec::new());
+//!     recovery.write(&mut reader, &mut writer).await?;
+//!     writer.close().await
+//! }
+//! ```
+
+use crate::base::read::counting::Counting;
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::base::read::options::lossy_name;
+use crate::base::read::stream;
+use crate::base::read::{assign_entry_data_boundaries, get_zip64_extra_field, lfh};
+use crate::base::write::ZipFileWriter;
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::consts::LFH_SIGNATURE;
+use crate::string::ZipString;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, BufReader, SeekFrom};
+
+/// The size of the buffer used whilst scanning for local file headers, and whilst checking entries' data.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// An entry whose local file header was found, but whose data couldn't be recovered.
+#[derive(Debug)]
+pub struct LostEntry {
+    offset: u64,
+    filename: ZipString,
+    error: ZipError,
+}
+
+impl LostEntry {
+    /// Returns the offset of the entry's local file header.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the entry's filename, as recorded in its local file header.
+    pub fn filename(&self) -> &ZipString {
+        &self.filename
+    }
+
+    /// Returns the error which prevented the entry from being recovered.
+    pub fn error(&self) -> &ZipError {
+        &self.error
+    }
+}
+
+/// The entries recovered from a ZIP file, alongside those which were lost.
+pub struct Recovery {
+    file: ZipFile,
+    lost: Vec<LostEntry>,
+}
+
+impl Recovery {
+    /// Returns a ZIP file made up of the salvaged entries, in the order they were found.
+    ///
+    /// This can be used alongside the recovered source to construct a ZIP reader (eg. via
+    /// [`seek::ZipFileReader::from_raw_parts()`](crate::base::read::seek::ZipFileReader::from_raw_parts)).
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Consumes this recovery and returns the ZIP file made up of the salvaged entries.
+    pub fn into_file(self) -> ZipFile {
+        self.file
+    }
+
+    /// Returns the entries which were lost, in the order they were found.
+    pub fn lost(&self) -> &[LostEntry] {
+        &self.lost
+    }
+
+    /// Writes the salvaged entries to a ZIP writer, copying their data from the recovered source without
+    /// recompressing it.
+    ///
+    /// Once closed, the writer produces a repaired archive with a rebuilt central directory. Names which only differ by
+    /// case (or Unicode normalization) are still salvaged, so fail to be written if the writer rejects them.
+    pub async fn write<R, W>(&self, mut reader: R, writer: &mut ZipFileWriter<W>) -> Result<()>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        for entry in &self.file.entries {
+            writer.copy_raw_entry(entry, &mut reader).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Recovers the entries of a ZIP file by scanning it for local file headers.
+///
+/// Candidate headers which can't be parsed are skipped, whereas entries whose headers parse but whose data doesn't
+/// check out are recorded as lost. Scanning resumes after the end of each salvaged entry, so the data of nested
+/// archives isn't mistaken for entries of this one.
+pub async fn recover<R>(reader: R) -> Result<Recovery>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    recover_with_limits(reader, ReadLimits::default()).await
+}
+
+/// Recovers the entries of a ZIP file as per [`recover()`], applying the provided limits to the data decompressed
+/// whilst checking entries.
+///
+/// Entries which exceed a limit are recorded as lost, with the total shared between all entries checked.
+pub async fn recover_with_limits<R>(mut reader: R, limits: ReadLimits) -> Result<Recovery>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let length = reader.seek(SeekFrom::End(0)).await?;
+    let limits = SharedLimits::new(limits);
+    let mut entries = Vec::new();
+    let mut names = HashSet::new();
+    let mut lost = Vec::new();
+    let mut offset = 0;
+
+    while let Some(candidate) = find_local_header(&mut reader, offset).await? {
+        let Ok((entry, header_size)) = read_local_header(&mut reader, candidate).await else {
+            offset = candidate + 1;
+            continue;
+        };
+
+        match salvage(&mut reader, candidate, entry.clone(), header_size, length, &limits).await {
+            Ok((entry, end)) if !names.insert(entry.filename.as_bytes().to_vec()) => {
+                let error = ZipError::DuplicateEntryName(lossy_name(&entry.filename));
+                lost.push(LostEntry { offset: candidate, filename: entry.filename, error });
+                offset = end;
+            }
+            Ok((entry, end)) => {
+                // Local file headers don't record the version made by, so the recovered entry is treated as though
+                // it were written by this crate.
+                let version_made_by = crate::spec::version::as_made_by();
+                let stored = StoredZipEntry {
+                    entry,
+                    file_offset: candidate,
+                    header_size,
+                    data_end_boundary: 0,
+                    version_made_by,
+                    cd_offset: 0,
+                };
+                entries.push(stored);
+                offset = end;
+            }
+            Err(error) => {
+                lost.push(LostEntry { offset: candidate, filename: entry.filename, error });
+                offset = candidate + 1;
+            }
+        }
+    }
+
+    assign_entry_data_boundaries(&mut entries, length);
+    let zip64 = entries.iter().any(|entry| get_zip64_extra_field(entry.extra_fields()).is_some());
+    let file = ZipFile {
+        entries,
+        zip64,
+        comment: String::new().into(),
+        prefix_length: 0,
+        directory_offset: 0,
+        warnings: Default::default(),
+        name_indices: Default::default(),
+    };
+
+    Ok(Recovery { file, lost })
+}
+
+/// Returns the offset of the first local file header signature at or after the provided offset.
+async fn find_local_header<R>(reader: &mut R, offset: u64) -> Result<Option<u64>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(offset)).await?;
+    let signature = LFH_SIGNATURE.to_le_bytes();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut start = offset;
+    let mut filled = 0;
+
+    loop {
+        let read = reader.read(&mut buffer[filled..]).await?;
+        filled += read;
+        if let Some(position) = buffer[..filled].windows(signature.len()).position(|window| window == signature) {
+            return Ok(Some(start + position as u64));
+        }
+        if read == 0 {
+            return Ok(None);
+        }
+
+        // Keep the bytes which may begin a signature that's split across reads.
+        let kept = filled.min(signature.len() - 1);
+        buffer.copy_within(filled - kept..filled, 0);
+        start += (filled - kept) as u64;
+        filled = kept;
+    }
+}
+
+/// Parses the local file header at the provided offset, returning its entry and its size.
+async fn read_local_header<R>(reader: &mut R, offset: u64) -> Result<(ZipEntry, u64)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(offset)).await?;
+    let mut counting = Counting::new(BufReader::new(reader));
+    let entry = lfh(&mut counting, offset).await?.ok_or(ZipError::UnexpectedHeaderError(0, LFH_SIGNATURE))?;
+    Ok((entry, counting.bytes_read()))
+}
+
+/// Delimits and checks the data of the entry whose local file header is at the provided offset, returning the entry
+/// (with its CRC32 value and sizes taken from any data descriptor) and the offset at which its record ends.
+async fn salvage<R>(
+    reader: &mut R,
+    offset: u64,
+    mut entry: ZipEntry,
+    header_size: u64,
+    length: u64,
+    limits: &Arc<SharedLimits>,
+) -> Result<(ZipEntry, u64)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    if entry.encrypted {
+        if entry.data_descriptor {
+            return Err(ZipError::PasswordRequired);
+        }
+
+        // Without the password, the data can't be checked beyond it being present.
+        let end = offset + header_size + entry.compressed_size;
+        if end > length {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        return Ok((entry, end));
+    }
+
+    reader.seek(SeekFrom::Start(offset)).await?;
+    let zip = stream::ZipFileReader::new(BufReader::new(&mut *reader)).with_shared_limits(limits.clone());
+    let mut zip = zip.next_without_entry().await?.ok_or(ZipError::UnexpectedHeaderError(0, LFH_SIGNATURE))?;
+
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut produced = 0;
+    loop {
+        match zip.reader_mut().read(&mut buffer).await? {
+            0 => break,
+            read => produced += read as u64,
+        }
+    }
+    let crc = zip.reader_mut().compute_hash();
+    let consumed = zip.reader().bytes_read();
+    let (descriptor, zip) = zip.done().await?;
+
+    if entry.data_descriptor {
+        let descriptor = descriptor.ok_or(ZipError::DataDescriptorNotFound)?;
+        entry.crc32 = descriptor.crc;
+        entry.compressed_size = descriptor.compressed_size;
+        entry.uncompressed_size = descriptor.uncompressed_size;
+    }
+
+    if crc != entry.crc32 {
+        return Err(ZipError::CRC32CheckError);
+    }
+    if produced != entry.uncompressed_size {
+        return Err(ZipError::UncompressedSizeMismatch { expected: entry.uncompressed_size, actual: produced });
+    }
+    if consumed != entry.compressed_size {
+        return Err(ZipError::CompressedSizeMismatch(entry.compressed_size));
+    }
+
+    Ok((entry, offset + zip.offset()))
+}
@@ -26,7 +26,16 @@
 //! }
 //! ```
 
+use crate::base::read::integrity::{self, TestReport};
+use crate::base::read::io::decrypt::read_encryption_header;
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::stored::StoredEntryReader;
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::base::read::nested::{self, NestedOptions, NestedSource};
+use crate::base::read::options::ReadOptions;
+use crate::base::read::split::SegmentedReader;
+use crate::base::read::verify::VerificationReport;
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 use std::sync::Arc;
@@ -34,7 +43,7 @@ use std::sync::Arc;
 #[cfg(feature = "tokio")]
 use crate::tokio::read::seek::ZipFileReader as TokioZipFileReader;
 
-use futures_lite::io::{AsyncBufRead, AsyncSeek};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek, BufReader, Take};
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
@@ -46,6 +55,8 @@ use super::io::entry::{WithEntry, WithoutEntry};
 pub struct ZipFileReader<R> {
     reader: R,
     file: Arc<ZipFile>,
+    password: Option<Arc<[u8]>>,
+    limits: Option<Arc<SharedLimits>>,
 }
 
 impl<R> ZipFileReader<R>
@@ -53,8 +64,13 @@ where
     R: AsyncBufRead + AsyncSeek + Unpin,
 {
     /// Constructs a new ZIP reader from a seekable source.
-    pub async fn new(mut reader: R) -> Result<ZipFileReader<R>> {
-        let file = crate::base::read::file(&mut reader).await?;
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::new_with_options(reader, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, parsing its central directory with the provided options.
+    pub async fn new_with_options(mut reader: R, options: ReadOptions) -> Result<ZipFileReader<R>> {
+        let file = crate::base::read::file(&mut reader, &options).await?;
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 
@@ -62,7 +78,19 @@ where
     ///
     /// Providing a [`ZipFile`] that wasn't derived from that source may lead to inaccurate parsing.
     pub fn from_raw_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
-        ZipFileReader { reader, file: Arc::new(file) }
+        ZipFileReader { reader, file: Arc::new(file), password: None, limits: None }
+    }
+
+    /// Sets the password used to decrypt encrypted entries when no per-entry password is provided.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> ZipFileReader<R> {
+        self.password = Some(Arc::from(password.as_ref()));
+        self
+    }
+
+    /// Sets the limits applied to the data read from entries.
+    pub fn with_limits(mut self, limits: ReadLimits) -> ZipFileReader<R> {
+        self.limits = Some(SharedLimits::new(limits));
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -77,6 +105,25 @@ where
         &mut self.reader
     }
 
+    /// Tests the integrity of every entry, as described by [`TestReport`].
+    pub async fn test_all(&mut self) -> TestReport {
+        let mut report = TestReport::default();
+        for index in 0..self.file.entries.len() {
+            let result = match self.reader_with_entry(index).await {
+                Ok(reader) => integrity::test_entry(reader).await,
+                Err(err) => Err(err),
+            };
+            report.record(index, result);
+        }
+        report
+    }
+
+    /// Audits every entry's local file header against its central directory record, as described by
+    /// [`VerificationReport`].
+    pub async fn verify(&mut self) -> Result<VerificationReport> {
+        crate::base::read::verify::verify(&mut self.reader, &self.file).await
+    }
+
     /// Returns the inner seekable source by consuming self.
     pub fn into_inner(self) -> R {
         self.reader
@@ -84,31 +131,78 @@ where
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_without_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithoutEntry>> {
-        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        let (reader, _) =
+            entry_reader(&mut self.reader, &self.file, index, self.password.as_deref(), self.limits.clone()).await?;
+        Ok(reader)
+    }
 
-        Ok(ZipEntryReader::new_with_borrow(
-            &mut self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_without_entry_with_password(
+        &mut self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, R, WithoutEntry>> {
+        let (reader, _) =
+            entry_reader(&mut self.reader, &self.file, index, Some(password), self.limits.clone()).await?;
+        Ok(reader)
     }
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_with_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
-        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-
-        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        let (reader, stored_entry) =
+            entry_reader(&mut self.reader, &self.file, index, self.password.as_deref(), self.limits.clone()).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
 
-        let reader = ZipEntryReader::new_with_borrow(
-            &mut self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+    /// Returns a new entry reader for the entry with the provided name, if one exists.
+    ///
+    /// See [`ZipFile::entry_index()`] for how names are matched.
+    pub async fn reader_with_entry_by_name(
+        &mut self,
+        name: impl AsRef<[u8]>,
+    ) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
+        let index = self.file().entry_index(name).ok_or(ZipError::EntryNameNotFound)?;
+        self.reader_with_entry(index).await
+    }
 
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_with_entry_with_password(
+        &mut self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
+        let (reader, stored_entry) =
+            entry_reader(&mut self.reader, &self.file, index, Some(password), self.limits.clone()).await?;
         Ok(reader.into_with_entry(stored_entry))
     }
 
+    /// Returns a reader over the raw data of the entry at the provided index, if the index is valid.
+    ///
+    /// Exactly the entry's compressed size is read, with the data left as it's stored (ie. neither decompressed nor
+    /// decrypted), and without its CRC32 value being checked.
+    pub async fn reader_raw(&mut self, index: usize) -> Result<Take<&mut R>> {
+        crate::base::read::raw_entry_reader(&mut self.reader, &self.file, index).await
+    }
+
+    /// Returns a seekable reader over the data of the Stored (and unencrypted) entry at the provided index.
+    ///
+    /// See [`StoredEntryReader`] for how this differs from the readers returned by the other methods.
+    pub async fn reader_stored(&mut self, index: usize) -> Result<StoredEntryReader<&mut R>> {
+        crate::base::read::stored_entry_reader(&mut self.reader, &self.file, index).await
+    }
+
+    /// Opens the ZIP file nested within the entry at the provided index.
+    ///
+    /// See the [`nested`] module for how the entry is read.
+    pub async fn open_nested(
+        &mut self,
+        index: usize,
+        options: NestedOptions,
+    ) -> Result<ZipFileReader<NestedSource<&mut R>>> {
+        let password = self.password.as_deref();
+        nested::open(&mut self.reader, &self.file, index, password, self.limits.clone(), &options).await
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     /// Consumes self
     pub async fn into_entry<'a>(mut self, index: usize) -> Result<ZipEntryReader<'a, R, WithoutEntry>>
@@ -117,13 +211,66 @@ where
     {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
 
-        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        stored_entry.seek_to_data_offset(&mut self.reader, self.file.warnings.entry(index)).await?;
+        let decryption = read_encryption_header(&mut self.reader, stored_entry, self.password.as_deref()).await?;
 
         Ok(ZipEntryReader::new_with_owned(
             self.reader,
             stored_entry.entry.compression(),
             stored_entry.entry.compressed_size(),
-        ))
+            decryption,
+        )
+        .with_limits(self.limits, Some(stored_entry.entry.uncompressed_size())))
+    }
+}
+
+/// Seeks to the data of the entry at the provided index and constructs a reader over it.
+async fn entry_reader<'a, R>(
+    reader: &'a mut R,
+    file: &'a ZipFile,
+    index: usize,
+    password: Option<&[u8]>,
+    limits: Option<Arc<SharedLimits>>,
+) -> Result<(ZipEntryReader<'a, R, WithoutEntry>, &'a StoredZipEntry)>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let stored_entry = file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+    stored_entry.seek_to_data_offset(reader, file.warnings.entry(index)).await?;
+    let decryption = read_encryption_header(&mut *reader, stored_entry, password).await?;
+
+    let reader = ZipEntryReader::new_with_borrow(
+        reader,
+        stored_entry.entry.compression(),
+        stored_entry.entry.compressed_size(),
+        decryption,
+    )
+    .with_limits(limits, Some(stored_entry.entry.uncompressed_size()));
+
+    Ok((reader, stored_entry))
+}
+
+impl<R> ZipFileReader<BufReader<SegmentedReader<R>>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from the ordered segments of a split ZIP file (eg. `archive.z01`, `archive.z02`,
+    /// ..., `archive.zip`).
+    ///
+    /// Each segment is treated as one disk, with the last segment holding the end of central directory record. Entry
+    /// readers may then read across the boundaries between segments.
+    pub async fn new_split(segments: Vec<R>) -> Result<Self> {
+        Self::new_split_with_options(segments, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from the ordered segments of a split ZIP file, parsing its central directory with
+    /// the provided options.
+    pub async fn new_split_with_options(segments: Vec<R>, options: ReadOptions) -> Result<Self> {
+        let mut reader = SegmentedReader::new(segments).await?;
+        let disks = reader.segment_offsets().to_vec();
+        let file = crate::base::read::file_with_disks(&mut reader, &options, &disks).await?;
+        Ok(ZipFileReader::from_raw_parts(BufReader::new(reader), file))
     }
 }
 
@@ -152,8 +299,12 @@ where
 {
     /// Constructs a new tokio-specific ZIP reader from a seekable source.
     pub async fn with_tokio(reader: R) -> Result<TokioZipFileReader<R>> {
-        let mut reader = reader.compat();
-        let file = crate::base::read::file(&mut reader).await?;
-        Ok(ZipFileReader::from_raw_parts(reader, file))
+        Self::with_tokio_and_options(reader, ReadOptions::default()).await
+    }
+
+    /// Constructs a new tokio-specific ZIP reader from a seekable source, parsing its central directory with the
+    /// provided options.
+    pub async fn with_tokio_and_options(reader: R, options: ReadOptions) -> Result<TokioZipFileReader<R>> {
+        ZipFileReader::new_with_options(reader.compat(), options).await
     }
 }
@@ -0,0 +1,147 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Support for reading split ZIP files, as produced by WinZip and `zip -s` (eg. `archive.z01`, `archive.z02`, ...,
+//! `archive.zip`).
+//!
+//! Each segment of a split ZIP file is treated as a disk, with the offsets stored within the central directory being
+//! relative to the start of the disk they point into. A [`SegmentedReader`] presents the segments as one contiguous
+//! address space, which the seeking ZIP reader then maps those offsets into (see
+//! [`seek::ZipFileReader::new_split()`](crate::base::read::seek::ZipFileReader::new_split), or `tokio::read::split`
+//! with the `tokio-fs` feature).
+
+use crate::error::{Result, ZipError};
+
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures_lite::ready;
+
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A reader which presents an ordered list of segments as one contiguous source.
+///
+/// The length of each segment is determined on construction, after which the segments must not change.
+pub struct SegmentedReader<R> {
+    segments: Vec<R>,
+    /// The offset at which each segment starts, followed by the total length.
+    offsets: Vec<u64>,
+    position: u64,
+    /// Whether the segment containing `position` is known to be positioned at it.
+    synced: bool,
+}
+
+impl<R> SegmentedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new reader from an ordered list of segments, seeking to the end of each to determine its length.
+    pub async fn new(mut segments: Vec<R>) -> Result<SegmentedReader<R>> {
+        let mut offsets = Vec::with_capacity(segments.len() + 1);
+        let mut length = 0_u64;
+        for segment in &mut segments {
+            offsets.push(length);
+            let segment_length = segment.seek(SeekFrom::End(0)).await?;
+            length = length.checked_add(segment_length).ok_or(ZipError::InvalidEntryDataRange)?;
+        }
+        offsets.push(length);
+
+        Ok(Self { segments, offsets, position: 0, synced: false })
+    }
+}
+
+impl<R> SegmentedReader<R> {
+    /// Returns the offset at which each segment starts within the combined source.
+    pub fn segment_offsets(&self) -> &[u64] {
+        &self.offsets[..self.segments.len()]
+    }
+
+    /// Returns the combined length of all segments.
+    pub fn len(&self) -> u64 {
+        self.offsets[self.segments.len()]
+    }
+
+    /// Returns whether all segments are empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Consumes this reader, returning the segments.
+    pub fn into_inner(self) -> Vec<R> {
+        self.segments
+    }
+}
+
+impl<R> AsyncRead for SegmentedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if this.position >= this.len() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        // The last segment starting at or before the position, skipping any empty segments.
+        let index = this.offsets[..this.segments.len()].partition_point(|start| *start <= this.position) - 1;
+        let within = this.position - this.offsets[index];
+        let segment = Pin::new(&mut this.segments[index]);
+
+        if !this.synced {
+            ready!(segment.poll_seek(cx, SeekFrom::Start(within)))?;
+            this.synced = true;
+        }
+
+        let remaining = this.offsets[index + 1] - this.position;
+        let limit = buf.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let read = ready!(Pin::new(&mut this.segments[index]).poll_read(cx, &mut buf[..limit]))?;
+        if read == 0 {
+            return Poll::Ready(Err(ErrorKind::UnexpectedEof.into()));
+        }
+
+        this.position += read as u64;
+        if this.position == this.offsets[index + 1] {
+            // The next read starts within the next segment, which must first be positioned at its start.
+            this.synced = false;
+        }
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl<R> AsyncSeek for SegmentedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, _: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.len().checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+        let Some(position) = position else {
+            return Poll::Ready(Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )));
+        };
+
+        if position != this.position {
+            this.position = position;
+            this.synced = false;
+        }
+        Poll::Ready(Ok(position))
+    }
+}
+
+/// Maps an offset relative to the start of a disk into the combined source.
+///
+/// An empty list of disk offsets denotes a ZIP file which isn't split, whose offsets are used as-is.
+pub(crate) fn logical_offset(disks: &[u64], disk: u32, offset: u64) -> Result<u64> {
+    if disks.is_empty() {
+        return Ok(offset);
+    }
+
+    let start = disks.get(disk as usize).ok_or(ZipError::MissingSplitSegment(disk))?;
+    start.checked_add(offset).ok_or(ZipError::InvalidEntryDataRange)
+}
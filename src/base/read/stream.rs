@@ -18,7 +18,9 @@
 //! As the central directory of a ZIP archive is stored at the end of it, a non-seekable reader doesn't have access
 //! to it. We have to rely on information provided within the local file header which may not be accurate or complete.
 //! This results in:
-//! - The inability to read ZIP entries using the combination of a data descriptor and the Stored compression method.
+//! - Stored entries followed by a data descriptor being delimited by scanning for the data descriptor's signature.
+//!   A candidate descriptor is only accepted if it agrees with the preceding data's size (and CRC32 value), so
+//!   descriptors written without the (optional) signature aren't supported for such entries.
 //! - No file comment being available (defaults to an empty string).
 //! - No internal or external file attributes being available (defaults to 0).
 //! - The extra field data potentially being inconsistent with what's stored in the central directory.
@@ -47,7 +49,11 @@
 //! ```
 
 use crate::base::read::counting::Counting;
+use crate::base::read::io::decrypt::read_encryption_header;
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::raw::RawEntryReader;
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::entry::ZipEntry;
 use crate::error::Result;
 use crate::error::ZipError;
 use crate::spec::data_descriptor::{CombinedDataDescriptor, DataDescriptor, Zip64DataDescriptor};
@@ -57,17 +63,34 @@ use crate::tokio::read::stream::Ready as TokioReady;
 use futures_lite::io::AsyncBufRead;
 use futures_lite::io::AsyncReadExt;
 
+use std::sync::Arc;
+
 use super::io::entry::WithEntry;
 use super::io::entry::WithoutEntry;
 use crate::spec::header::HeaderId;
+use crate::spec::Compression;
 #[cfg(feature = "tokio")]
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
 /// A type which encodes that [`ZipFileReader`] is ready to open a new entry.
-pub struct Ready<R>(R);
+pub struct Ready<R>(R, Settings);
 
 /// A type which encodes that [`ZipFileReader`] is currently reading an entry.
-pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, Option<Suffix>);
+pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, Option<Suffix>, Settings);
+
+/// A type which encodes that [`ZipFileReader`] is currently reading an entry's raw data.
+pub struct ReadingRaw<R>(RawEntryReader<R>, ZipEntry, Option<Suffix>, Settings);
+
+/// An opened entry, alongside the state needed to return to the [`Ready`] state once it has been read.
+type Next<'a, R> = (ZipEntryReader<'a, R, WithoutEntry>, ZipEntry, Option<Suffix>, Settings);
+
+/// The settings which are carried between entries.
+#[derive(Clone, Default)]
+struct Settings {
+    /// The password used to decrypt encrypted entries when no per-entry password is provided.
+    password: Option<Arc<[u8]>>,
+    limits: Option<Arc<SharedLimits>>,
+}
 
 #[derive(Copy, Clone, Debug)]
 enum Suffix {
@@ -89,55 +112,130 @@ where
 {
     /// Constructs a new ZIP reader from a non-seekable source.
     pub fn new(reader: R) -> Self {
-        Self(Ready(Counting::new(reader)))
+        Self(Ready(Counting::new(reader), Settings::default()))
+    }
+
+    /// Sets the password used to decrypt encrypted entries when no per-entry password is provided.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> Self {
+        self.0 .1.password = Some(Arc::from(password.as_ref()));
+        self
+    }
+
+    /// Sets the limits applied to the data read from entries.
+    ///
+    /// Entries followed by a data descriptor don't declare their uncompressed size upfront, so only these limits
+    /// (and not the declared size) bound the data read from them.
+    pub fn with_limits(self, limits: ReadLimits) -> Self {
+        self.with_shared_limits(SharedLimits::new(limits))
+    }
+
+    /// Sets limits whose totals are shared with other readers.
+    pub(crate) fn with_shared_limits(mut self, limits: Arc<SharedLimits>) -> Self {
+        self.0 .1.limits = Some(limits);
+        self
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
-    pub async fn next_without_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithoutEntry>>>> {
+    pub async fn next_without_entry(self) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithoutEntry>>>> {
+        let password = self.0 .1.password.clone();
+        let Some((reader, _, suffix, settings)) = self.next(password.as_deref()).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ZipFileReader(Reading(reader, suffix, settings))))
+    }
+
+    /// Opens the next entry for reading if the central directory hasn’t yet been reached, decrypting the entry with
+    /// the provided password.
+    pub async fn next_without_entry_with_password(
+        self,
+        password: &[u8],
+    ) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithoutEntry>>>> {
+        let Some((reader, _, suffix, settings)) = self.next(Some(password)).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ZipFileReader(Reading(reader, suffix, settings))))
+    }
+
+    /// Opens the next entry for reading if the central directory hasn’t yet been reached.
+    pub async fn next_with_entry(self) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithEntry<'a>>>>> {
+        let password = self.0 .1.password.clone();
+        let Some((reader, entry, suffix, settings)) = self.next(password.as_deref()).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), suffix, settings))))
+    }
+
+    /// Opens the next entry for reading if the central directory hasn’t yet been reached, decrypting the entry with
+    /// the provided password.
+    pub async fn next_with_entry_with_password(
+        self,
+        password: &[u8],
+    ) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithEntry<'a>>>>> {
+        let Some((reader, entry, suffix, settings)) = self.next(Some(password)).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), suffix, settings))))
+    }
+
+    /// Opens the next entry for reading its raw data if the central directory hasn’t yet been reached.
+    ///
+    /// Exactly the entry's compressed size is read, with the data left as it's stored (ie. neither decompressed nor
+    /// decrypted), and without its CRC32 value being checked.
+    ///
+    /// The compressed size of an entry followed by a data descriptor isn't known upfront, so its data is instead
+    /// delimited by scanning for the descriptor (for Stored entries), or by decompressing it (for any other entry). As
+    /// the data can't be decrypted, such entries aren't supported when encrypted.
+    pub async fn next_raw(mut self) -> Result<Option<ZipFileReader<ReadingRaw<Counting<R>>>>> {
         let file_offset = self.0 .0.bytes_read();
         let entry = match crate::base::read::lfh(&mut self.0 .0, file_offset).await? {
             Some(entry) => entry,
             None => return Ok(None),
         };
 
-        let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
-
-        let suffix = if entry.data_descriptor {
-            if entry.extra_fields.iter().any(|ef| ef.header_id() == HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD) {
-                Some(Suffix::Zip64DataDescriptor)
-            } else {
-                Some(Suffix::DataDescriptor)
+        let suffix = suffix(&entry);
+        let reader = match suffix {
+            None => RawEntryReader::sized(self.0 .0, entry.compressed_size),
+            Some(_) if entry.encrypted => {
+                return Err(ZipError::FeatureNotSupported(
+                    "raw reading of encrypted stream entries followed by a data descriptor",
+                ));
+            }
+            Some(suffix) => {
+                let zip64 = matches!(suffix, Suffix::Zip64DataDescriptor);
+                RawEntryReader::until_descriptor(self.0 .0, entry.compression, zip64)
             }
-        } else {
-            None
         };
-
-        Ok(Some(ZipFileReader(Reading(reader, suffix))))
+        Ok(Some(ZipFileReader(ReadingRaw(reader, entry, suffix, self.0 .1))))
     }
 
-    /// Opens the next entry for reading if the central directory hasn’t yet been reached.
-    pub async fn next_with_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, Counting<R>, WithEntry<'a>>>>> {
+    /// Parses the next local file header and constructs a reader over the entry's data.
+    async fn next(mut self, password: Option<&[u8]>) -> Result<Option<Next<'a, Counting<R>>>> {
         let file_offset = self.0 .0.bytes_read();
         let entry = match crate::base::read::lfh(&mut self.0 .0, file_offset).await? {
             Some(entry) => entry,
             None => return Ok(None),
         };
 
-        let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
+        let decryption = read_encryption_header(&mut self.0 .0, &entry, password).await?;
 
-        let suffix = if entry.data_descriptor {
-            if entry.extra_fields.iter().any(|ef| ef.header_id() == HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD) {
-                Some(Suffix::Zip64DataDescriptor)
-            } else {
-                Some(Suffix::DataDescriptor)
-            }
+        let suffix = suffix(&entry);
+
+        // Stored data isn't self-terminating, so its end has to be found by scanning for the data descriptor.
+        let reader = if entry.data_descriptor && entry.compression == Compression::Stored {
+            let zip64 = matches!(suffix, Some(Suffix::Zip64DataDescriptor));
+            ZipEntryReader::new_with_owned_until_descriptor(self.0 .0, entry.compression, decryption, zip64)
         } else {
-            None
+            let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
+            ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length, decryption)
         };
+        let uncompressed_size = (!entry.data_descriptor).then_some(entry.uncompressed_size);
+        let reader = reader.with_limits(self.0 .1.limits.clone(), uncompressed_size);
 
-        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), suffix))))
+        Ok(Some((reader, entry, suffix, self.0 .1)))
     }
 
     /// Consumes the `ZipFileReader` returning the original `reader`
@@ -158,11 +256,22 @@ where
 {
     /// Constructs a new tokio-specific ZIP reader from a non-seekable source.
     pub fn with_tokio(reader: R) -> ZipFileReader<TokioReady<R>> {
-        Self(Ready(reader.compat()))
+        Self(Ready(reader.compat(), Settings::default()))
     }
 }
 
-type Next<R> = (Option<CombinedDataDescriptor>, ZipFileReader<Ready<R>>);
+type Done<R> = (Option<CombinedDataDescriptor>, ZipFileReader<Ready<R>>);
+
+/// Returns the data descriptor which follows an entry, if any.
+fn suffix(entry: &ZipEntry) -> Option<Suffix> {
+    if !entry.data_descriptor {
+        None
+    } else if entry.extra_fields.iter().any(|ef| ef.header_id() == HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD) {
+        Some(Suffix::Zip64DataDescriptor)
+    } else {
+        Some(Suffix::DataDescriptor)
+    }
+}
 
 impl<'a, R, E> ZipFileReader<Reading<'a, R, E>>
 where
@@ -179,14 +288,16 @@ where
     }
 
     /// Converts the reader back into the Ready state if EOF has been reached.
-    pub async fn done(mut self) -> Result<Next<R>> {
+    pub async fn done(mut self) -> Result<Done<R>> {
         if self.0 .0.read(&mut [0; 1]).await? != 0 {
             return Err(ZipError::EOFNotReached);
         }
 
+        let scanned_descriptor = self.0 .0.take_data_descriptor();
         let mut inner = self.0 .0.into_inner();
 
         let data_descriptor = match self.0 .1 {
+            _ if scanned_descriptor.is_some() => scanned_descriptor,
             Some(Suffix::DataDescriptor) => {
                 Some(CombinedDataDescriptor::from(DataDescriptor::from_reader(&mut inner).await?))
             }
@@ -196,18 +307,20 @@ where
             None => None,
         };
 
-        let reader = ZipFileReader(Ready(inner));
+        let reader = ZipFileReader(Ready(inner, self.0 .2));
 
         Ok((data_descriptor, reader))
     }
 
     /// Reads until EOF and converts the reader back into the Ready state.
-    pub async fn skip(mut self) -> Result<Next<R>> {
+    pub async fn skip(mut self) -> Result<Done<R>> {
         let mut buf = [0u8; 8192];
         while self.0 .0.read(&mut buf).await? != 0 {}
+        let scanned_descriptor = self.0 .0.take_data_descriptor();
         let mut inner = self.0 .0.into_inner();
 
         let data_descriptor = match self.0 .1 {
+            _ if scanned_descriptor.is_some() => scanned_descriptor,
             Some(Suffix::DataDescriptor) => {
                 Some(CombinedDataDescriptor::from(DataDescriptor::from_reader(&mut inner).await?))
             }
@@ -217,8 +330,67 @@ where
             None => None,
         };
 
-        let reader = ZipFileReader(Ready(inner));
+        let reader = ZipFileReader(Ready(inner, self.0 .2));
 
         Ok((data_descriptor, reader))
     }
 }
+
+impl<R> ZipFileReader<ReadingRaw<R>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Returns the entry whose raw data is being read.
+    pub fn entry(&self) -> &ZipEntry {
+        &self.0 .1
+    }
+
+    /// Returns a mutable reference to the reader over the entry's raw data.
+    pub fn reader_mut(&mut self) -> &mut RawEntryReader<R> {
+        &mut self.0 .0
+    }
+
+    /// Converts the reader back into the Ready state if EOF has been reached, returning the entry's data descriptor
+    /// (if any).
+    pub async fn done(mut self) -> Result<Done<R>> {
+        if self.0 .0.read(&mut [0; 1]).await? != 0 || !self.0 .0.exhausted() {
+            return Err(ZipError::EOFNotReached);
+        }
+
+        self.finish().await
+    }
+
+    /// Reads until EOF and converts the reader back into the Ready state, returning the entry's data descriptor (if
+    /// any).
+    pub async fn skip(mut self) -> Result<Done<R>> {
+        futures_lite::io::copy(&mut self.0 .0, &mut futures_lite::io::sink()).await?;
+        if !self.0 .0.exhausted() {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        self.finish().await
+    }
+
+    /// Reads the data descriptor which follows the raw data (unless consumed whilst scanning), and checks that it
+    /// agrees with the size of the raw data.
+    async fn finish(self) -> Result<Done<R>> {
+        let read = self.0 .0.bytes_read();
+        let (mut inner, scanned_descriptor) = self.0 .0.into_inner();
+
+        let data_descriptor = match self.0 .2 {
+            _ if scanned_descriptor.is_some() => scanned_descriptor,
+            Some(Suffix::DataDescriptor) => {
+                Some(CombinedDataDescriptor::from(DataDescriptor::from_reader(&mut inner).await?))
+            }
+            Some(Suffix::Zip64DataDescriptor) => {
+                Some(CombinedDataDescriptor::from(Zip64DataDescriptor::from_reader(&mut inner).await?))
+            }
+            None => None,
+        };
+        if let Some(descriptor) = data_descriptor.as_ref().filter(|descriptor| descriptor.compressed_size != read) {
+            return Err(ZipError::CompressedSizeMismatch(descriptor.compressed_size));
+        }
+
+        Ok((data_descriptor, ZipFileReader(Ready(inner, self.0 .3))))
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! An audit of a ZIP file's local file headers against its central directory.
+//!
+//! A streaming reader only sees the local file headers, whereas a seeking reader trusts the central directory, so an
+//! archive in which the two disagree can present different files to different consumers.
+
+use crate::base::read::{detect_filename, get_combined_sizes, get_zip64_extra_field, io};
+use crate::entry::StoredZipEntry;
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::consts::{
+    CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH, SPANNING_SIGNATURE,
+    TEMPORARY_SPANNING_MARKER,
+};
+use crate::spec::header::{CentralDirectoryRecord, ExtraField, HeaderId, LocalFileHeader};
+use crate::spec::parse::parse_extra_fields;
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+/// A field which differed between an entry's local file header and its central directory record.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderField {
+    /// The version needed to extract.
+    VersionNeeded,
+    /// The general purpose bit flags.
+    Flags,
+    /// The compression method.
+    Compression,
+    /// The last modification date or time.
+    LastModification,
+    /// The CRC32 of the uncompressed data.
+    Crc32,
+    /// The compressed size, taking into account ZIP64 if necessary.
+    CompressedSize,
+    /// The uncompressed size, taking into account ZIP64 if necessary.
+    UncompressedSize,
+    /// The raw filename.
+    FileName,
+    /// The filename taken from an Info-ZIP Unicode Path extra field.
+    UnicodePath,
+    /// The set of extra fields present, excluding those which legitimately differ (eg. ZIP64 information).
+    ExtraFields,
+    /// The data descriptor following the entry's data, which was either missing or didn't match the CRC32 and sizes
+    /// recorded in the central directory.
+    DataDescriptor,
+}
+
+/// An inconsistency found whilst verifying a ZIP file.
+///
+/// Entries are referred to by their index within [`ZipFile::entries()`](crate::ZipFile::entries). The local records of
+/// any duplicated entries which were discarded whilst reading are reported as unaccounted bytes.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Finding {
+    /// The entry's local file header couldn't be read.
+    UnreadableLocalFileHeader { entry: usize, error: ZipError },
+    /// A field of the entry's local file header didn't match its central directory record.
+    Mismatch { entry: usize, field: HeaderField },
+    /// The entry's local file header or data overlapped that of another entry.
+    Overlap { entry: usize, other: usize },
+    /// The entry's local file header or data extended into the central directory.
+    OverlapsCentralDirectory { entry: usize },
+    /// Bytes before the central directory which don't belong to any entry, such as a gap between two entries or data
+    /// preceding the first local file header.
+    UnaccountedBytes { start: u64, end: u64 },
+}
+
+/// The result of verifying a ZIP file, as returned by each ZIP reader's `verify()` method.
+///
+/// Every entry's local file header is audited against its central directory record, and the entries' local records
+/// are checked for gaps or hidden data between them. Inconsistencies are collected into the report rather than causing
+/// an error, which is only returned if the entries' central directory records can't be re-read from the source.
+#[derive(Debug, Default)]
+pub struct VerificationReport {
+    findings: Vec<Finding>,
+}
+
+impl VerificationReport {
+    /// Returns whether no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns the inconsistencies found.
+    ///
+    /// Those concerning individual entries are ordered by entry, and are followed by those concerning the layout of
+    /// the entries' local records.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+}
+
+/// Walks every entry of a parsed ZIP file, comparing its local file header with its central directory record and
+/// checking that the local records tile the space before the central directory.
+///
+/// The reader must be the source the file was parsed from, so that the offsets recorded whilst parsing (which account
+/// for any prefix or split segments) remain valid.
+pub(crate) async fn verify<R>(mut reader: R, file: &ZipFile) -> Result<VerificationReport>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut findings = Vec::new();
+    let mut spans = Vec::with_capacity(file.entries.len());
+    for (index, stored) in file.entries.iter().enumerate() {
+        // Re-read the central directory record, as `ZipEntry` doesn't surface all of the fields being compared.
+        reader.seek(SeekFrom::Start(stored.cd_offset)).await?;
+        crate::utils::assert_signature(&mut reader, CDH_SIGNATURE).await?;
+        let central = CentralDirectoryRecord::from_reader_without_compression_check(&mut reader).await?;
+
+        match audit_entry(&mut reader, index, stored, &central, &mut findings).await {
+            Ok(end) => spans.push((stored.file_offset, end, index)),
+            Err(error) => findings.push(Finding::UnreadableLocalFileHeader { entry: index, error }),
+        }
+    }
+
+    let start = split_marker_length(&mut reader).await?;
+    audit_layout(spans, start, file.directory_offset, &mut findings);
+    Ok(VerificationReport { findings })
+}
+
+/// Returns the length of the marker which starts the first segment of a split ZIP file (or a split ZIP file which only
+/// required a single segment), if present.
+async fn split_marker_length<R>(reader: &mut R) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(0)).await?;
+    match read_u32(reader).await {
+        Ok(SPANNING_SIGNATURE | TEMPORARY_SPANNING_MARKER) => Ok(SIGNATURE_LENGTH as u64),
+        _ => Ok(0),
+    }
+}
+
+/// Compares an entry's local file header with its central directory record, returning the offset at which the
+/// entry's local record (including any data descriptor) ends.
+async fn audit_entry<R>(
+    reader: &mut R,
+    index: usize,
+    stored: &StoredZipEntry,
+    central: &CentralDirectoryRecord,
+    findings: &mut Vec<Finding>,
+) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(stored.file_offset)).await?;
+    crate::utils::assert_signature(reader, LFH_SIGNATURE).await?;
+    let local = LocalFileHeader::from_reader(&mut *reader).await?;
+    let filename = io::read_bytes(&mut *reader, local.file_name_length.into()).await?;
+    let extra_field = io::read_bytes(&mut *reader, local.extra_field_length.into()).await?;
+    let extra_fields = parse_extra_fields(extra_field, local.uncompressed_size, local.compressed_size, None, None)?;
+
+    let mut mismatch = |field| findings.push(Finding::Mismatch { entry: index, field });
+
+    if local.version != central.v_needed {
+        mismatch(HeaderField::VersionNeeded);
+    }
+    if local.flags.as_slice() != central.flags.as_slice() {
+        mismatch(HeaderField::Flags);
+    }
+    if local.compression != central.compression {
+        mismatch(HeaderField::Compression);
+    }
+    if (local.mod_date, local.mod_time) != (central.mod_date, central.mod_time) {
+        mismatch(HeaderField::LastModification);
+    }
+
+    let central_filename = stored.filename();
+    if filename.as_slice() != central_filename.alternative().unwrap_or(central_filename.as_bytes()) {
+        mismatch(HeaderField::FileName);
+    }
+    match detect_filename(filename, local.flags.filename_unicode, &extra_fields) {
+        Ok(local_filename) if local_filename.alternative().is_none() && central_filename.alternative().is_none() => {}
+        Ok(local_filename) if local_filename.as_bytes() == central_filename.as_bytes() => {}
+        _ => mismatch(HeaderField::UnicodePath),
+    }
+    if compared_extra_fields(&extra_fields) != compared_extra_fields(stored.extra_fields()) {
+        mismatch(HeaderField::ExtraFields);
+    }
+
+    let zip64_extra_field = get_zip64_extra_field(&extra_fields);
+    let data_end = stored
+        .file_offset
+        .checked_add((SIGNATURE_LENGTH + LFH_LENGTH) as u64)
+        .and_then(|offset| offset.checked_add(local.file_name_length as u64 + local.extra_field_length as u64))
+        .and_then(|offset| offset.checked_add(stored.compressed_size()))
+        .ok_or(ZipError::InvalidEntryDataRange)?;
+
+    if !local.flags.data_descriptor {
+        if local.crc != central.crc {
+            mismatch(HeaderField::Crc32);
+        }
+        match get_combined_sizes(local.uncompressed_size, local.compressed_size, &zip64_extra_field) {
+            Ok((uncompressed_size, compressed_size)) => {
+                if compressed_size != stored.compressed_size() {
+                    mismatch(HeaderField::CompressedSize);
+                }
+                if uncompressed_size != stored.uncompressed_size() {
+                    mismatch(HeaderField::UncompressedSize);
+                }
+            }
+            Err(_) => mismatch(HeaderField::CompressedSize),
+        }
+        return Ok(data_end);
+    }
+
+    match read_data_descriptor(reader, data_end, zip64_extra_field.is_some()).await {
+        Ok((crc, compressed_size, uncompressed_size, end)) => {
+            if (crc, compressed_size, uncompressed_size)
+                != (stored.crc32(), stored.compressed_size(), stored.uncompressed_size())
+            {
+                mismatch(HeaderField::DataDescriptor);
+            }
+            Ok(end)
+        }
+        Err(_) => {
+            mismatch(HeaderField::DataDescriptor);
+            Ok(data_end)
+        }
+    }
+}
+
+/// Reads the data descriptor at the provided offset, returning its CRC32, compressed size, uncompressed size, and the
+/// offset at which it ends.
+async fn read_data_descriptor<R>(reader: &mut R, offset: u64, zip64: bool) -> Result<(u32, u64, u64, u64)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(offset)).await?;
+    let mut length = 0;
+
+    // The descriptor's signature is optional.
+    let mut crc = read_u32(reader).await?;
+    if crc == DATA_DESCRIPTOR_SIGNATURE {
+        crc = read_u32(reader).await?;
+        length += SIGNATURE_LENGTH as u64;
+    }
+
+    let (compressed_size, uncompressed_size) = if zip64 {
+        let mut buffer = [0; 16];
+        reader.read_exact(&mut buffer).await?;
+        length += 20;
+        (u64::from_le_bytes(buffer[..8].try_into().unwrap()), u64::from_le_bytes(buffer[8..].try_into().unwrap()))
+    } else {
+        length += 12;
+        (read_u32(reader).await? as u64, read_u32(reader).await? as u64)
+    };
+
+    Ok((crc, compressed_size, uncompressed_size, offset + length))
+}
+
+async fn read_u32<R: AsyncRead + Unpin>(reader: &mut R) -> Result<u32> {
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer).await?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+/// Returns the sorted header IDs of the extra fields which are expected to match between both headers.
+///
+/// ZIP64 information only holds the fields which overflowed in that header, and Info-ZIP Unicode Comment fields only
+/// belong in the central directory. Info-ZIP Unicode Path fields are compared via the filename they resolve to.
+fn compared_extra_fields(extra_fields: &[ExtraField]) -> Vec<HeaderId> {
+    let mut header_ids: Vec<_> = extra_fields
+        .iter()
+        .filter(|field| {
+            !matches!(
+                field,
+                ExtraField::Zip64ExtendedInformation(_)
+                    | ExtraField::InfoZipUnicodeComment(_)
+                    | ExtraField::InfoZipUnicodePath(_)
+            )
+        })
+        .map(ExtraField::header_id)
+        .collect();
+    header_ids.sort_unstable_by_key(|id| u16::from(*id));
+    header_ids
+}
+
+/// Checks that the entries' local records, given as `(start, end, index)` spans, neither overlap one another nor
+/// leave any bytes between the start offset and the central directory unaccounted for.
+fn audit_layout(mut spans: Vec<(u64, u64, usize)>, start: u64, directory_offset: u64, findings: &mut Vec<Finding>) {
+    spans.sort_unstable();
+
+    let mut covered = start;
+    let mut last = None;
+    for (start, end, index) in spans {
+        if start > covered {
+            findings.push(Finding::UnaccountedBytes { start: covered, end: start });
+        } else if let Some(other) = last.filter(|_| start < covered) {
+            findings.push(Finding::Overlap { entry: index, other });
+        }
+        if end > directory_offset {
+            findings.push(Finding::OverlapsCentralDirectory { entry: index });
+        }
+        if end >= covered {
+            covered = end;
+            last = Some(index);
+        }
+    }
+
+    if covered < directory_offset {
+        findings.push(Finding::UnaccountedBytes { start: covered, end: directory_offset });
+    }
+}
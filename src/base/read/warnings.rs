@@ -0,0 +1,150 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Warnings collected whilst leniently reading a ZIP file.
+
+use crate::error::{Result, ZipError};
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+/// A check which failed whilst leniently reading a ZIP file, and was tolerated rather than returned as an error.
+///
+/// See [`Strictness::Lenient`](crate::base::read::Strictness::Lenient).
+#[derive(Clone, Debug)]
+pub struct ReadWarning {
+    entry: Option<usize>,
+    error: Arc<ZipError>,
+}
+
+impl ReadWarning {
+    /// Returns the index of the entry which the warning concerns, if it doesn't concern the ZIP file as a whole.
+    pub fn entry(&self) -> Option<usize> {
+        self.entry
+    }
+
+    /// Returns the error which would have been returned had the ZIP file been read strictly.
+    pub fn error(&self) -> &ZipError {
+        &self.error
+    }
+}
+
+impl Display for ReadWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.entry {
+            Some(entry) => write!(f, "entry {entry}: {}", self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+/// The warnings collected for a ZIP file, shared between its clones and the readers constructed from it.
+///
+/// When reading strictly, nothing is collected and each tolerable error is returned as-is.
+#[derive(Clone, Default)]
+pub(crate) struct Warnings(Option<Arc<Mutex<Collected>>>);
+
+/// The check which failed when an error was tolerated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum WarningKind {
+    /// The central directory's size, compared against that declared by the end of central directory record.
+    CentralDirectorySize,
+    /// The end of the central directory, compared against the start of the end of central directory record.
+    CentralDirectoryBinding,
+    /// The version needed to extract within a central directory record.
+    CentralCompressionVersion,
+    /// The version needed to extract within a local file header.
+    LocalCompressionVersion,
+    /// The sizes within a local file header, compared against those within the central directory.
+    LocalHeaderSizes,
+}
+
+/// The warnings collected so far, alongside the entry and check of each, so that a check which fails every time an
+/// entry is read is only recorded once.
+#[derive(Default)]
+struct Collected {
+    warnings: Vec<(ReadWarning, WarningKind)>,
+    seen: HashSet<(Option<usize>, WarningKind)>,
+}
+
+/// The warnings of a ZIP file reading strictly, for use where a ZIP file's own warnings aren't available.
+static STRICT: Warnings = Warnings(None);
+
+impl Warnings {
+    pub(crate) fn new(lenient: bool) -> Self {
+        Self(lenient.then(Default::default))
+    }
+
+    /// Returns a handle for tolerating errors concerning the entry at the provided index.
+    pub(crate) fn entry(&self, entry: usize) -> EntryWarnings<'_> {
+        EntryWarnings { warnings: self, entry: Some(entry) }
+    }
+
+    /// Returns a handle for tolerating errors concerning the ZIP file as a whole.
+    pub(crate) fn file(&self) -> EntryWarnings<'_> {
+        EntryWarnings { warnings: self, entry: None }
+    }
+
+    /// Returns a handle which never tolerates errors.
+    pub(crate) fn strict() -> EntryWarnings<'static> {
+        STRICT.file()
+    }
+
+    pub(crate) fn collected(&self) -> Vec<ReadWarning> {
+        let Some(collected) = self.0.as_ref() else {
+            return Vec::new();
+        };
+        collected.lock().unwrap().warnings.iter().map(|(warning, _)| warning.clone()).collect()
+    }
+
+    /// Renumbers the warnings concerning entries once some have been discarded, given whether each entry was kept.
+    ///
+    /// Warnings concerning a discarded entry are kept, but no longer refer to an entry.
+    pub(crate) fn retain_entries(&self, kept: &[bool]) {
+        let Some(collected) = self.0.as_ref() else {
+            return;
+        };
+
+        let mut next = 0;
+        let indices: Vec<_> = kept
+            .iter()
+            .map(|kept| {
+                let index = kept.then_some(next);
+                next += usize::from(*kept);
+                index
+            })
+            .collect();
+
+        let mut collected = collected.lock().unwrap();
+        for (warning, _) in &mut collected.warnings {
+            warning.entry = warning.entry.and_then(|entry| indices.get(entry).copied().flatten());
+        }
+        let seen = collected.warnings.iter().map(|(warning, check)| (warning.entry, *check)).collect();
+        collected.seen = seen;
+    }
+}
+
+/// A handle for tolerating errors, recording the entry (if any) that they concern.
+#[derive(Clone, Copy)]
+pub(crate) struct EntryWarnings<'a> {
+    warnings: &'a Warnings,
+    entry: Option<usize>,
+}
+
+impl EntryWarnings<'_> {
+    /// Records the error as a warning when reading leniently, or otherwise returns it.
+    ///
+    /// An error which the same check has already recorded for the same entry (eg. when the entry is read again) is
+    /// ignored.
+    pub(crate) fn tolerate(self, kind: WarningKind, error: ZipError) -> Result<()> {
+        let Some(collected) = self.warnings.0.as_ref() else {
+            return Err(error);
+        };
+        let mut collected = collected.lock().unwrap();
+        if collected.seen.insert((self.entry, kind)) {
+            collected.warnings.push((ReadWarning { entry: self.entry, error: Arc::new(error) }, kind));
+        }
+        Ok(())
+    }
+}
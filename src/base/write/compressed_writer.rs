@@ -1,6 +1,7 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::write::encrypted_writer::{EncryptedAsyncWriter, Encryptor};
 use crate::base::write::io::offset::AsyncOffsetWriter;
 use crate::error::Result;
 #[cfg(feature = "deflate64")]
@@ -15,54 +16,61 @@ use std::task::{Context, Poll};
 use async_compression::futures::write;
 use futures_lite::io::AsyncWrite;
 
+/// The writer beneath any compression, which encrypts the compressed data if needed.
+type DataWriter<'b, W> = EncryptedAsyncWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>;
+
 pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
-    Stored(ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>),
+    Stored(DataWriter<'b, W>),
     #[cfg(feature = "deflate")]
-    Deflate(write::DeflateEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Deflate(write::DeflateEncoder<DataWriter<'b, W>>),
     #[cfg(feature = "bzip2")]
-    Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Bz(write::BzEncoder<DataWriter<'b, W>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<DataWriter<'b, W>>),
     #[cfg(feature = "zstd")]
-    Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Zstd(write::ZstdEncoder<DataWriter<'b, W>>),
     #[cfg(feature = "xz")]
-    Xz(write::XzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Xz(write::XzEncoder<DataWriter<'b, W>>),
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
-    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression) -> Result<Self> {
+    pub fn from_raw(
+        writer: &'b mut AsyncOffsetWriter<W>,
+        compression: Compression,
+        encryptor: Option<Encryptor>,
+    ) -> Result<Self> {
+        let writer = EncryptedAsyncWriter::new(ShutdownIgnoredWriter(writer), encryptor);
+
         Ok(match compression {
-            Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
+            Compression::Stored => CompressedAsyncWriter::Stored(writer),
             #[cfg(feature = "deflate")]
-            Compression::Deflate => {
-                CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
-            }
+            Compression::Deflate => CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(writer)),
             #[cfg(feature = "deflate64")]
             Compression::Deflate64 => return Err(ZipError::FeatureNotSupported("Deflate64 writing")),
             #[cfg(feature = "bzip2")]
-            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(writer)),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(writer)),
             #[cfg(feature = "zstd")]
-            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(writer)),
             #[cfg(feature = "xz")]
-            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(writer)),
         })
     }
 
     pub fn into_inner(self) -> &'b mut AsyncOffsetWriter<W> {
         match self {
-            CompressedAsyncWriter::Stored(inner) => inner.into_inner(),
+            CompressedAsyncWriter::Stored(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "deflate")]
-            CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "bzip2")]
-            CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "zstd")]
-            CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "xz")]
-            CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner().into_inner(),
         }
     }
 }
@@ -0,0 +1,198 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "aes")]
+use crate::crypto::aes::{AesCipher, PASSWORD_VERIFIER_LENGTH};
+use crate::crypto::fill_random;
+use crate::crypto::zip_crypto::{ZipCryptoKeys, ENCRYPTION_HEADER_LENGTH};
+use crate::entry::{EntryEncryption, ZipEntry};
+use crate::error::Result;
+use crate::spec::encryption::Encryption;
+#[cfg(feature = "aes")]
+use crate::spec::{
+    consts::AES_COMPRESSION_METHOD,
+    header::{AesVendorVersion, ExtraField, WinZipAesExtraField},
+};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::AsyncWrite;
+
+/// The maximum number of bytes encrypted per write.
+const ENCRYPT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Prepares an entry for being written with its requested encryption (if any).
+///
+/// This marks the entry as encrypted and, for AES, records the WinZip AES extra field which carries the entry's real
+/// compression method.
+pub(crate) fn prepare_entry(entry: &mut ZipEntry) {
+    entry.encrypted = entry.encryption.is_some();
+
+    #[cfg(feature = "aes")]
+    if let Some(&EntryEncryption { method: Encryption::Aes(strength), .. }) = entry.encryption.as_deref() {
+        entry.extra_fields.retain(|field| !matches!(field, ExtraField::WinZipAes(_)));
+        entry.extra_fields.push(ExtraField::WinZipAes(WinZipAesExtraField {
+            vendor_version: AesVendorVersion::Ae2,
+            strength,
+            compression: entry.compression,
+        }));
+    }
+}
+
+/// Returns the compression method to record in the entry's headers.
+pub(crate) fn header_compression(entry: &ZipEntry) -> u16 {
+    match entry.encryption.as_deref() {
+        #[cfg(feature = "aes")]
+        Some(EntryEncryption { method: Encryption::Aes(_), .. }) => AES_COMPRESSION_METHOD,
+        _ => entry.compression.into(),
+    }
+}
+
+/// Returns the CRC32 value to record for the entry.
+///
+/// AE-2 entries omit their CRC32 value in favour of the authentication code.
+pub(crate) fn header_crc(entry: &ZipEntry, crc: u32) -> u32 {
+    match entry.encryption.as_deref() {
+        #[cfg(feature = "aes")]
+        Some(EntryEncryption { method: Encryption::Aes(_), .. }) => 0,
+        _ => crc,
+    }
+}
+
+/// The cipher state used to encrypt an entry's data.
+pub(crate) enum Encryptor {
+    ZipCrypto(ZipCryptoKeys),
+    #[cfg(feature = "aes")]
+    Aes(Box<AesCipher>),
+}
+
+impl Encryptor {
+    /// Constructs the cipher state for an entry, returning it alongside the header which must precede the encrypted
+    /// data.
+    ///
+    /// `check` is the verification byte stored at the end of a ZipCrypto header; the high byte of the entry's CRC32,
+    /// or of its last modification time when the entry is followed by a data descriptor. This fails if the header's
+    /// random bytes can't be generated.
+    pub(crate) fn new(encryption: &EntryEncryption, check: u8) -> Result<(Self, Vec<u8>)> {
+        match encryption.method {
+            Encryption::ZipCrypto => {
+                let mut header = [0; ENCRYPTION_HEADER_LENGTH];
+                fill_random(&mut header[..ENCRYPTION_HEADER_LENGTH - 1])?;
+                header[ENCRYPTION_HEADER_LENGTH - 1] = check;
+
+                let mut keys = ZipCryptoKeys::new(&encryption.password);
+                keys.encrypt(&mut header);
+                Ok((Encryptor::ZipCrypto(keys), header.to_vec()))
+            }
+            #[cfg(feature = "aes")]
+            Encryption::Aes(strength) => {
+                let mut header = vec![0; strength.salt_length() + PASSWORD_VERIFIER_LENGTH];
+                let (salt, verifier_slot) = header.split_at_mut(strength.salt_length());
+                fill_random(salt)?;
+
+                let (cipher, verifier) = AesCipher::new(&encryption.password, strength, salt);
+                verifier_slot.copy_from_slice(&verifier);
+                Ok((Encryptor::Aes(Box::new(cipher)), header))
+            }
+        }
+    }
+
+    /// Encrypts the provided buffer in place.
+    pub(crate) fn encrypt(&mut self, buffer: &mut [u8]) {
+        match self {
+            Encryptor::ZipCrypto(keys) => keys.encrypt(buffer),
+            #[cfg(feature = "aes")]
+            Encryptor::Aes(cipher) => {
+                cipher.apply_keystream(buffer);
+                cipher.authenticate(buffer);
+            }
+        }
+    }
+
+    /// Returns the trailer which must follow the encrypted data.
+    pub(crate) fn trailer(&self) -> Vec<u8> {
+        match self {
+            Encryptor::ZipCrypto(_) => Vec::new(),
+            #[cfg(feature = "aes")]
+            Encryptor::Aes(cipher) => cipher.authentication_code().to_vec(),
+        }
+    }
+}
+
+/// A writer which encrypts data (if needed) before passing it to an inner writer.
+///
+/// Any trailer needed by the encryption method is written when this writer is closed.
+pub struct EncryptedAsyncWriter<W: AsyncWrite + Unpin> {
+    inner: W,
+    encryptor: Option<Encryptor>,
+    buffer: Vec<u8>,
+    written: usize,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> EncryptedAsyncWriter<W> {
+    pub(crate) fn new(inner: W, encryptor: Option<Encryptor>) -> Self {
+        Self { inner, encryptor, buffer: Vec::new(), written: 0, finished: false }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes out any buffered encrypted data.
+    fn poll_drain(&mut self, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        while self.written < self.buffer.len() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.buffer[self.written..]))?;
+            if written == 0 {
+                return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+            }
+            self.written += written;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for EncryptedAsyncWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        if self.encryptor.is_none() {
+            return Pin::new(&mut self.inner).poll_write(cx, buf);
+        }
+
+        // The cipher state advances as data is encrypted, so encrypted data is buffered until it has all been written.
+        ready!(self.poll_drain(cx))?;
+
+        let this = &mut *self;
+        let length = buf.len().min(ENCRYPT_BUFFER_SIZE);
+        this.buffer.clear();
+        this.buffer.extend_from_slice(&buf[..length]);
+        this.written = 0;
+        if let Some(encryptor) = this.encryptor.as_mut() {
+            encryptor.encrypt(&mut this.buffer);
+        }
+
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        ready!(self.poll_drain(cx))?;
+
+        if !self.finished {
+            if let Some(encryptor) = self.encryptor.as_ref() {
+                self.buffer = encryptor.trailer();
+                self.written = 0;
+            }
+            self.finished = true;
+            ready!(self.poll_drain(cx))?;
+        }
+
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::get_winzip_aes_extra_field;
+use crate::base::read::warnings::Warnings;
+use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, Zip64ErrorCase, ZipError};
+use crate::spec::consts::{
+    AES_COMPRESSION_METHOD, DATA_DESCRIPTOR_LENGTH, LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE,
+    SIGNATURE_LENGTH, ZIP64_DATA_DESCRIPTOR_LENGTH,
+};
+use crate::spec::data_descriptor::{DataDescriptor, Zip64DataDescriptor};
+use crate::spec::extra_field::{ExtraFieldAsBytes, Zip64ExtendedInformationExtraFieldBuilder};
+use crate::spec::header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, LocalFileHeader};
+use crate::spec::version::ZIP64_VERSION_NEEDED;
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, AsyncWriteExt};
+
+/// A writer which copies an entry's compressed data from another ZIP file, as-is.
+pub(crate) struct EntryRawWriter<'b, 'c, W: AsyncWrite + Unpin, R> {
+    writer: &'b mut ZipFileWriter<W>,
+    source: &'c StoredZipEntry,
+    reader: R,
+}
+
+impl<'b, 'c, W: AsyncWrite + Unpin, R: AsyncRead + AsyncSeek + Unpin> EntryRawWriter<'b, 'c, W, R> {
+    pub(crate) fn from_raw(writer: &'b mut ZipFileWriter<W>, source: &'c StoredZipEntry, reader: R) -> Self {
+        Self { writer, source, reader }
+    }
+
+    pub(crate) async fn write(mut self) -> Result<()> {
+        if self.writer.force_no_zip64 && self.writer.cd_entries.len() >= NON_ZIP64_MAX_NUM_FILES as usize {
+            return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
+        }
+
+        // Validates the source's local file header, leaving the reader positioned at the start of its data.
+        self.source.seek_to_data_offset(&mut self.reader, Warnings::strict()).await?;
+
+        let mut entry: ZipEntry = self.source.entry.clone();
+        entry.encryption = None;
+        entry.extra_fields.retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformation(_)));
+
+        let compressed_size = entry.compressed_size;
+        let uncompressed_size = entry.uncompressed_size;
+        let lh_offset = self.writer.writer.offset();
+
+        let requires_zip64_sizes =
+            uncompressed_size >= NON_ZIP64_MAX_SIZE as u64 || compressed_size >= NON_ZIP64_MAX_SIZE as u64;
+        let requires_zip64_offset = lh_offset >= NON_ZIP64_MAX_SIZE as u64;
+        if requires_zip64_sizes || requires_zip64_offset {
+            if self.writer.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+            }
+            self.writer.is_zip64 = true;
+        }
+
+        let (lfh_uncompressed_size, lfh_compressed_size) = if requires_zip64_sizes {
+            let builder = Zip64ExtendedInformationExtraFieldBuilder::new().sizes(compressed_size, uncompressed_size);
+            entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
+            (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
+        } else {
+            (uncompressed_size as u32, compressed_size as u32)
+        };
+
+        let aes = entry.encrypted && get_winzip_aes_extra_field(&entry.extra_fields).is_some();
+        let mut version = crate::spec::version::as_needed_to_extract(&entry);
+        if requires_zip64_sizes || requires_zip64_offset {
+            version = version.max(ZIP64_VERSION_NEEDED);
+        }
+
+        let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
+        let comment_basic = entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes());
+        let file_comment_length = comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?;
+
+        let lf_header = LocalFileHeader {
+            compressed_size: lfh_compressed_size,
+            uncompressed_size: lfh_uncompressed_size,
+            compression: if aes { AES_COMPRESSION_METHOD } else { entry.compression.into() },
+            crc: entry.crc32,
+            extra_field_length: entry
+                .extra_fields()
+                .count_bytes()
+                .try_into()
+                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
+            file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
+            mod_time: entry.last_modification_date().time,
+            mod_date: entry.last_modification_date().date,
+            version,
+            flags: GeneralPurposeFlag {
+                // Kept as ZipCrypto headers of entries followed by a data descriptor are verified differently.
+                data_descriptor: entry.data_descriptor,
+                encrypted: entry.encrypted,
+                strong_encryption: false,
+                compressed_patched: false,
+                filename_unicode: entry.filename().is_utf8_without_alternative()
+                    && entry.comment().is_utf8_without_alternative(),
+            },
+        };
+
+        // The header may be moved to the next segment when splitting, where offsets never need a ZIP64 field.
+        let lfh_length = (SIGNATURE_LENGTH + LFH_LENGTH) as u64
+            + u64::from(lf_header.file_name_length)
+            + u64::from(lf_header.extra_field_length);
+        self.writer.writer.reserve(lfh_length).await?;
+        let lh_offset = if self.writer.writer.is_split() { self.writer.writer.offset() } else { lh_offset };
+        let disk_start = self.writer.writer.disk() as u16;
+
+        self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
+        self.writer.writer.write_all(&lf_header.as_slice()).await?;
+        self.writer.writer.write_all(filename_basic).await?;
+        self.writer.writer.write_all(&entry.extra_fields().as_bytes()).await?;
+
+        let copied = futures_lite::io::copy((&mut self.reader).take(compressed_size), &mut self.writer.writer).await?;
+        if copied != compressed_size {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        if entry.data_descriptor {
+            let descriptor_length =
+                if requires_zip64_sizes { ZIP64_DATA_DESCRIPTOR_LENGTH } else { DATA_DESCRIPTOR_LENGTH };
+            self.writer.writer.reserve((SIGNATURE_LENGTH + descriptor_length) as u64).await?;
+            self.writer.writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            if requires_zip64_sizes {
+                let descriptor = Zip64DataDescriptor { crc: entry.crc32, compressed_size, uncompressed_size };
+                self.writer.writer.write_all(&descriptor.as_bytes()).await?;
+            } else {
+                let descriptor = DataDescriptor {
+                    crc: entry.crc32,
+                    compressed_size: compressed_size as u32,
+                    uncompressed_size: uncompressed_size as u32,
+                };
+                self.writer.writer.write_all(&descriptor.as_bytes()).await?;
+            }
+        }
+
+        let cd_lh_offset = if requires_zip64_offset {
+            let builder = Zip64ExtendedInformationExtraFieldBuilder::new().relative_header_offset(lh_offset);
+            match entry.extra_fields.last_mut() {
+                Some(ExtraField::Zip64ExtendedInformation(zip64)) => zip64.relative_header_offset = Some(lh_offset),
+                _ => entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?)),
+            }
+            NON_ZIP64_MAX_SIZE
+        } else {
+            lh_offset as u32
+        };
+
+        let header = CentralDirectoryRecord {
+            v_made_by: self.source.version_made_by,
+            v_needed: lf_header.version,
+            compressed_size: lf_header.compressed_size,
+            uncompressed_size: lf_header.uncompressed_size,
+            compression: lf_header.compression,
+            crc: lf_header.crc,
+            extra_field_length: entry
+                .extra_fields()
+                .count_bytes()
+                .try_into()
+                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
+            file_name_length: lf_header.file_name_length,
+            file_comment_length,
+            mod_time: lf_header.mod_time,
+            mod_date: lf_header.mod_date,
+            flags: lf_header.flags,
+            disk_start,
+            inter_attr: entry.internal_file_attribute(),
+            exter_attr: entry.external_file_attribute(),
+            lh_offset: cd_lh_offset,
+        };
+
+        self.writer.names.insert(entry.filename());
+        self.writer.cd_entries.push(CentralDirectoryEntry::new(header, entry));
+        // Mark the archive as Zip64 once the central directory no longer fits in the legacy count field.
+        if self.writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize && !self.writer.is_zip64 {
+            self.writer.is_zip64 = true;
+        }
+        Ok(())
+    }
+}
@@ -2,18 +2,23 @@
 
 use crate::base::read::get_zip64_extra_field_mut;
 use crate::base::write::compressed_writer::CompressedAsyncWriter;
+use crate::base::write::encrypted_writer::{self, Encryptor};
 use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
 use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
 use crate::base::write::io::offset::AsyncOffsetWriter;
+use crate::base::write::names::EntryNames;
 use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
 use crate::entry::ZipEntry;
 use crate::error::{Result, Zip64ErrorCase, ZipError};
 use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::data_descriptor::{DataDescriptor, Zip64DataDescriptor};
+use crate::spec::encryption::Encryption;
 use crate::spec::extra_field::ExtraFieldAsBytes;
 use crate::spec::header::{
     CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, InfoZipUnicodeCommentExtraField,
     InfoZipUnicodePathExtraField, LocalFileHeader, Zip64ExtendedInformationExtraField,
 };
+use crate::spec::version::ZIP64_VERSION_NEEDED;
 use crate::StringEncoding;
 
 use crc32fast::Hasher;
@@ -22,8 +27,6 @@ use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-const ZIP64_VERSION_NEEDED: u16 = 45;
-
 /// An entry writer which streams data to a seekable ZIP output.
 ///
 /// Unlike [`EntryStreamWriter`](crate::base::write::EntryStreamWriter), this writer doesn't use
@@ -35,10 +38,14 @@ const ZIP64_VERSION_NEEDED: u16 = 45;
 /// fields when the size is known to exceed the non-Zip64 limit, or use
 /// [`ZipFileWriter::write_entry_stream`] for fully unknown Zip64-sized entries.
 ///
+/// ZipCrypto entries are the exception to the above: their encryption header must be verifiable before the CRC is
+/// known, so they're flagged as being followed by a data descriptor (which is written alongside the patched header).
+///
 /// [`ZipEntryBuilder::size`]: crate::ZipEntryBuilder::size
 pub struct EntrySeekableWriter<'b, W: AsyncWrite + AsyncSeek + Unpin> {
     writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
     cd_entries: &'b mut Vec<CentralDirectoryEntry>,
+    names: &'b mut EntryNames,
     entry: ZipEntry,
     hasher: Hasher,
     lfh: LocalFileHeader,
@@ -64,18 +71,35 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
             return Err(ZipError::FeatureNotSupported("Deflate64 writing"));
         }
 
+        encrypted_writer::prepare_entry(&mut entry);
+
         let lfh_offset = writer.writer.offset();
         let (lfh, local_header_has_zip64_sizes) = EntrySeekableWriter::write_lfh(writer, &mut entry).await?;
         let data_offset = writer.writer.offset();
         let force_no_zip64 = writer.force_no_zip64;
 
+        let encryptor = match entry.encryption.as_ref() {
+            Some(encryption) => {
+                let (encryptor, header) = Encryptor::new(encryption, (lfh.mod_time >> 8) as u8)?;
+                writer.writer.write_all(&header).await?;
+                Some(encryptor)
+            }
+            None => None,
+        };
+
         let cd_entries = &mut writer.cd_entries;
+        let names = &mut writer.names;
         let is_zip64 = &mut writer.is_zip64;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression())?);
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(
+            &mut writer.writer,
+            entry.compression(),
+            encryptor,
+        )?);
 
         Ok(EntrySeekableWriter {
             writer,
             cd_entries,
+            names,
             entry,
             lfh,
             lfh_offset,
@@ -144,12 +168,13 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
         }
 
         let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
+        let data_descriptor = matches!(entry.encryption.as_ref().map(|e| e.method), Some(Encryption::ZipCrypto));
 
         let lfh = LocalFileHeader {
             compressed_size: if local_header_has_zip64_sizes { NON_ZIP64_MAX_SIZE } else { 0 },
             uncompressed_size: if local_header_has_zip64_sizes { NON_ZIP64_MAX_SIZE } else { 0 },
-            compression: entry.compression().into(),
-            crc: entry.crc32,
+            compression: encrypted_writer::header_compression(entry),
+            crc: encrypted_writer::header_crc(entry, entry.crc32),
             extra_field_length: entry
                 .extra_fields()
                 .count_bytes()
@@ -160,8 +185,8 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
             mod_date: entry.last_modification_date().date,
             version: crate::spec::version::as_needed_to_extract(entry),
             flags: GeneralPurposeFlag {
-                data_descriptor: false,
-                encrypted: false,
+                data_descriptor,
+                encrypted: entry.encrypted,
                 strong_encryption: false,
                 compressed_patched: false,
                 filename_unicode: utf8_without_alternative,
@@ -181,6 +206,7 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
     /// This includes:
     /// - Finalising the CRC32 hash value for the written data.
     /// - Calculating the compressed and uncompressed byte sizes.
+    /// - Writing a data descriptor, if the entry requires one.
     /// - Seeking back to patch the local file header.
     /// - Constructing a central directory header.
     /// - Pushing that central directory header to the [`ZipFileWriter`]'s store.
@@ -189,11 +215,10 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
     pub async fn close(mut self) -> Result<()> {
         self.writer.close().await?;
 
-        let crc = self.hasher.finalize();
+        let crc = encrypted_writer::header_crc(&self.entry, self.hasher.finalize());
         let uncompressed_size = self.writer.offset();
         let inner_writer = self.writer.into_inner().into_inner();
         let compressed_size = inner_writer.offset() - self.data_offset;
-        let end_offset = inner_writer.offset();
 
         let requires_zip64_sizes =
             uncompressed_size >= NON_ZIP64_MAX_SIZE as u64 || compressed_size >= NON_ZIP64_MAX_SIZE as u64;
@@ -241,6 +266,22 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
         self.lfh.extra_field_length =
             self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
 
+        if self.lfh.flags.data_descriptor {
+            inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            if uses_zip64_sizes {
+                let descriptor = Zip64DataDescriptor { crc, compressed_size, uncompressed_size };
+                inner_writer.write_all(&descriptor.as_bytes()).await?;
+            } else {
+                let descriptor = DataDescriptor {
+                    crc,
+                    compressed_size: compressed_size as u32,
+                    uncompressed_size: uncompressed_size as u32,
+                };
+                inner_writer.write_all(&descriptor.as_bytes()).await?;
+            }
+        }
+        let end_offset = inner_writer.offset();
+
         let filename_basic = self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes());
         let local_extra_fields = self.entry.extra_fields().as_bytes();
 
@@ -300,7 +341,8 @@ impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntrySeekableWriter<'b, W> {
             lh_offset,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        self.names.insert(self.entry.filename());
+        self.cd_entries.push(CentralDirectoryEntry::new(cdh, self.entry));
         // Mark the archive as Zip64 once the central directory no longer fits in the legacy count field.
         if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize && !*self.is_zip64 {
             *self.is_zip64 = true;
@@ -2,9 +2,11 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::write::compressed_writer::CompressedAsyncWriter;
+use crate::base::write::encrypted_writer::{self, Encryptor};
 use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
 use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
 use crate::base::write::io::offset::AsyncOffsetWriter;
+use crate::base::write::names::EntryNames;
 use crate::base::write::CentralDirectoryEntry;
 use crate::base::write::ZipFileWriter;
 use crate::entry::ZipEntry;
@@ -23,7 +25,10 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::base::read::get_zip64_extra_field_mut;
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::consts::{
+    DATA_DESCRIPTOR_LENGTH, LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH,
+    ZIP64_DATA_DESCRIPTOR_LENGTH,
+};
 use crc32fast::Hasher;
 use futures_lite::io::{AsyncWrite, AsyncWriteExt};
 
@@ -36,10 +41,13 @@ use futures_lite::io::{AsyncWrite, AsyncWriteExt};
 pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
     writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
     cd_entries: &'b mut Vec<CentralDirectoryEntry>,
+    names: &'b mut EntryNames,
     entry: ZipEntry,
     hasher: Hasher,
     lfh: LocalFileHeader,
     lfh_offset: u64,
+    lfh_disk: u32,
+    /// The number of bytes written by the ZIP file writer before the entry's data, across all segments.
     data_offset: u64,
     force_no_zip64: bool,
     /// To write back to the original writer if zip64 is required.
@@ -60,21 +68,40 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             return Err(ZipError::FeatureNotSupported("Deflate64 writing"));
         }
 
-        let lfh_offset = writer.writer.offset();
-        let lfh = EntryStreamWriter::write_lfh(writer, &mut entry).await?;
-        let data_offset = writer.writer.offset();
+        encrypted_writer::prepare_entry(&mut entry);
+
+        let (lfh, lfh_offset) = EntryStreamWriter::write_lfh(writer, &mut entry).await?;
+        let lfh_disk = writer.writer.disk();
+        let data_offset = writer.writer.written();
         let force_no_zip64 = writer.force_no_zip64;
 
+        // As the CRC32 isn't yet known, the modification time is used to verify ZipCrypto passwords instead.
+        let encryptor = match entry.encryption.as_ref() {
+            Some(encryption) => {
+                let (encryptor, header) = Encryptor::new(encryption, (lfh.mod_time >> 8) as u8)?;
+                writer.writer.write_all(&header).await?;
+                Some(encryptor)
+            }
+            None => None,
+        };
+
         let cd_entries = &mut writer.cd_entries;
+        let names = &mut writer.names;
         let is_zip64 = &mut writer.is_zip64;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression())?);
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(
+            &mut writer.writer,
+            entry.compression(),
+            encryptor,
+        )?);
 
         Ok(EntryStreamWriter {
             writer,
             cd_entries,
+            names,
             entry,
             lfh,
             lfh_offset,
+            lfh_disk,
             data_offset,
             hasher: Hasher::new(),
             force_no_zip64,
@@ -82,7 +109,8 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         })
     }
 
-    async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &mut ZipEntry) -> Result<LocalFileHeader> {
+    /// Writes the local file header, returning it alongside the offset at which it was written.
+    async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &mut ZipEntry) -> Result<(LocalFileHeader, u64)> {
         // Always emit a zip64 extended field, even if we don't need it, because we *might* need it.
         // If we are forcing no zip, we will have to error later if the file is too large.
         let (lfh_compressed, lfh_uncompressed) = if !writer.force_no_zip64 {
@@ -140,8 +168,8 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         let lfh = LocalFileHeader {
             compressed_size: lfh_compressed,
             uncompressed_size: lfh_uncompressed,
-            compression: entry.compression().into(),
-            crc: entry.crc32,
+            compression: encrypted_writer::header_compression(entry),
+            crc: encrypted_writer::header_crc(entry, entry.crc32),
             extra_field_length: entry
                 .extra_fields()
                 .count_bytes()
@@ -153,19 +181,25 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             version: crate::spec::version::as_needed_to_extract(entry),
             flags: GeneralPurposeFlag {
                 data_descriptor: true,
-                encrypted: false,
+                encrypted: entry.encrypted,
                 strong_encryption: false,
                 compressed_patched: false,
                 filename_unicode: utf8_without_alternative,
             },
         };
 
+        let lfh_length = (SIGNATURE_LENGTH + LFH_LENGTH) as u64
+            + u64::from(lfh.file_name_length)
+            + u64::from(lfh.extra_field_length);
+        writer.writer.reserve(lfh_length).await?;
+        let lfh_offset = writer.writer.offset();
+
         writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         writer.writer.write_all(&lfh.as_slice()).await?;
         writer.writer.write_all(filename_basic).await?;
         writer.writer.write_all(&entry.extra_fields().as_bytes()).await?;
 
-        Ok(lfh)
+        Ok((lfh, lfh_offset))
     }
 
     /// Consumes this entry writer and completes all closing tasks.
@@ -180,10 +214,10 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
     pub async fn close(mut self) -> Result<()> {
         self.writer.close().await?;
 
-        let crc = self.hasher.finalize();
+        let crc = encrypted_writer::header_crc(&self.entry, self.hasher.finalize());
         let uncompressed_size = self.writer.offset();
         let inner_writer = self.writer.into_inner().into_inner();
-        let compressed_size = inner_writer.offset() - self.data_offset;
+        let compressed_size = inner_writer.written() - self.data_offset;
 
         let (cdr_compressed_size, cdr_uncompressed_size, lh_offset) = if self.force_no_zip64 {
             if uncompressed_size > NON_ZIP64_MAX_SIZE as u64
@@ -219,6 +253,8 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
         };
 
+        let descriptor_length = if self.force_no_zip64 { DATA_DESCRIPTOR_LENGTH } else { ZIP64_DATA_DESCRIPTOR_LENGTH };
+        inner_writer.reserve((SIGNATURE_LENGTH + descriptor_length) as u64).await?;
         inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
         if self.force_no_zip64 {
             let descriptor =
@@ -244,13 +280,14 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             mod_time: self.lfh.mod_time,
             mod_date: self.lfh.mod_date,
             flags: self.lfh.flags,
-            disk_start: 0,
+            disk_start: self.lfh_disk as u16,
             inter_attr: self.entry.internal_file_attribute(),
             exter_attr: self.entry.external_file_attribute(),
             lh_offset,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        self.names.insert(self.entry.filename());
+        self.cd_entries.push(CentralDirectoryEntry::new(cdh, self.entry));
         // Mark the archive as Zip64 once the central directory no longer fits in the legacy count field.
         if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize && !*self.is_zip64 {
             *self.is_zip64 = true;
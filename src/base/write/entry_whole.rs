@@ -1,6 +1,7 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::write::encrypted_writer::{self, Encryptor};
 use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
 use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
 use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
@@ -17,10 +18,11 @@ use crate::StringEncoding;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use futures_lite::io::Cursor;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::consts::{LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::futures::write;
 use futures_lite::io::{AsyncWrite, AsyncWriteExt};
+use std::borrow::Cow;
 
 pub struct EntryWholeWriter<'b, 'c, W: AsyncWrite + Unpin> {
     writer: &'b mut ZipFileWriter<W>,
@@ -38,16 +40,26 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
         }
 
-        let mut _compressed_data: Option<Vec<u8>> = None;
         let compressed_data = match self.entry.compression() {
-            Compression::Stored => self.data,
+            Compression::Stored => Cow::Borrowed(self.data),
             #[cfg(feature = "deflate64")]
             Compression::Deflate64 => return Err(ZipError::FeatureNotSupported("Deflate64 writing")),
             #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-            _ => {
-                _compressed_data =
-                    Some(compress(self.entry.compression(), self.data, self.entry.compression_level).await?);
-                _compressed_data.as_ref().unwrap()
+            _ => Cow::Owned(compress(self.entry.compression(), self.data, self.entry.compression_level).await?),
+        };
+
+        let crc = crc32fast::hash(self.data);
+        encrypted_writer::prepare_entry(&mut self.entry);
+
+        let compressed_data = match self.entry.encryption.as_ref() {
+            None => compressed_data,
+            Some(encryption) => {
+                let (mut encryptor, mut encrypted_data) = Encryptor::new(encryption, (crc >> 24) as u8)?;
+                let data_offset = encrypted_data.len();
+                encrypted_data.extend_from_slice(&compressed_data);
+                encryptor.encrypt(&mut encrypted_data[data_offset..]);
+                encrypted_data.extend_from_slice(&encryptor.trailer());
+                Cow::Owned(encrypted_data)
             }
         };
 
@@ -140,8 +152,8 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
         let lf_header = LocalFileHeader {
             compressed_size: lfh_compressed_size,
             uncompressed_size: lfh_uncompressed_size,
-            compression: self.entry.compression().into(),
-            crc: crc32fast::hash(self.data),
+            compression: encrypted_writer::header_compression(&self.entry),
+            crc: encrypted_writer::header_crc(&self.entry, crc),
             extra_field_length: self
                 .entry
                 .extra_fields()
@@ -154,7 +166,7 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             version: crate::spec::version::as_needed_to_extract(&self.entry),
             flags: GeneralPurposeFlag {
                 data_descriptor: false,
-                encrypted: false,
+                encrypted: self.entry.encrypted,
                 strong_encryption: false,
                 compressed_patched: false,
                 filename_unicode: utf8_without_alternative,
@@ -180,11 +192,22 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             lh_offset,
         };
 
+        // Splitting limits each segment to offsets which don't require a ZIP64 field, so if the header is moved to the
+        // start of the next segment, its offset can be taken as-is.
+        let lfh_length = (SIGNATURE_LENGTH + LFH_LENGTH) as u64
+            + u64::from(lf_header.file_name_length)
+            + u64::from(lf_header.extra_field_length);
+        self.writer.writer.reserve(lfh_length).await?;
+        if self.writer.writer.is_split() {
+            header.lh_offset = self.writer.writer.offset() as u32;
+        }
+        header.disk_start = self.writer.writer.disk() as u16;
+
         self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         self.writer.writer.write_all(&lf_header.as_slice()).await?;
         self.writer.writer.write_all(filename_basic).await?;
         self.writer.writer.write_all(&self.entry.extra_fields().as_bytes()).await?;
-        self.writer.writer.write_all(compressed_data).await?;
+        self.writer.writer.write_all(&compressed_data).await?;
 
         if let Some(builder) = zip64_extra_field_builder {
             self.entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
@@ -192,7 +215,8 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
                 self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
         }
 
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
+        self.writer.names.insert(self.entry.filename());
+        self.writer.cd_entries.push(CentralDirectoryEntry::new(header, self.entry));
         // Mark the archive as Zip64 once the central directory no longer fits in the legacy count field.
         if self.writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize && !self.writer.is_zip64 {
             self.writer.is_zip64 = true;
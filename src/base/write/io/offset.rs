@@ -5,15 +5,30 @@ use std::io::{Error, IoSlice};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use std::sync::Arc;
+
 use futures_lite::io::{AsyncSeek, AsyncWrite, SeekFrom};
 use pin_project::pin_project;
 
+use crate::base::write::split::{SegmentFactory, SplitState, SplitWriter};
+use crate::error::ZipError;
+
 /// A wrapper around an [`AsyncWrite`] implementation which tracks the current byte offset.
+///
+/// When wrapping a [`SplitWriter`](crate::base::write::split::SplitWriter), the offset is instead relative to the
+/// start of the current segment.
 #[pin_project(project = OffsetWriterProj)]
 pub struct AsyncOffsetWriter<W> {
     #[pin]
     inner: W,
     offset: u64,
+    split: Option<Split<W>>,
+}
+
+/// The position of a [`SplitWriter`] being wrapped, alongside a means of starting its next segment.
+struct Split<W> {
+    state: Arc<SplitState>,
+    next_segment: fn(&mut W, &mut Context<'_>) -> Poll<Result<(), Error>>,
 }
 
 impl<W> AsyncOffsetWriter<W>
@@ -22,14 +37,53 @@ where
 {
     /// Constructs a new wrapper from an inner [`AsyncWrite`] writer.
     pub fn new(inner: W) -> Self {
-        Self { inner, offset: 0 }
+        Self { inner, offset: 0, split: None }
+    }
+
+    /// Constructs a new wrapper from an inner [`AsyncWrite`] writer which is already positioned at `offset`.
+    pub(crate) fn with_offset(inner: W, offset: u64) -> Self {
+        Self { inner, offset, split: None }
     }
 
-    /// Returns the current byte offset.
+    /// Returns the current byte offset, relative to the start of the current segment if splitting.
     pub fn offset(&self) -> u64 {
+        match &self.split {
+            Some(split) => split.state.offset(),
+            None => self.offset,
+        }
+    }
+
+    /// Returns the number of bytes written, including those written to any previous segments.
+    pub(crate) fn written(&self) -> u64 {
         self.offset
     }
 
+    /// Returns whether the inner writer splits its output into segments.
+    pub(crate) fn is_split(&self) -> bool {
+        self.split.is_some()
+    }
+
+    /// Returns the disk number of the current segment, which is always zero unless splitting.
+    pub(crate) fn disk(&self) -> u32 {
+        self.split.as_ref().map(|split| split.state.disk()).unwrap_or_default()
+    }
+
+    /// Ensures that the next `length` bytes are written within a single segment, moving on to the next segment if
+    /// they wouldn't fit within the current one. This is a no-op unless splitting.
+    pub(crate) async fn reserve(&mut self, length: u64) -> crate::error::Result<()> {
+        let Some(split) = self.split.as_ref() else {
+            return Ok(());
+        };
+        if length > split.state.segment_size {
+            return Err(ZipError::SplitRecordTooLarge(length));
+        }
+        if split.state.offset() + length > split.state.segment_size {
+            let next_segment = split.next_segment;
+            futures_lite::future::poll_fn(|cx| next_segment(&mut self.inner, cx)).await?;
+        }
+        Ok(())
+    }
+
     /// Consumes this wrapper and returns the inner [`AsyncWrite`] writer.
     pub fn into_inner(self) -> W {
         self.inner
@@ -40,6 +94,20 @@ where
     }
 }
 
+impl<W, F> AsyncOffsetWriter<SplitWriter<W, F>>
+where
+    W: AsyncWrite + Unpin,
+    F: SegmentFactory<W>,
+{
+    /// Constructs a new wrapper from a writer which splits its output into segments, sharing its position.
+    ///
+    /// The first segment starts with a marker written by the [`SplitWriter`] itself, which is counted as written.
+    pub(crate) fn with_split(inner: SplitWriter<W, F>) -> Self {
+        let split = Split { state: inner.state(), next_segment: SplitWriter::poll_next_segment };
+        Self { inner, offset: crate::spec::consts::SIGNATURE_LENGTH as u64, split: Some(split) }
+    }
+}
+
 impl<W> AsyncWrite for AsyncOffsetWriter<W>
 where
     W: AsyncWrite + Unpin,
@@ -50,19 +50,25 @@
 //! ```
 
 pub(crate) mod compressed_writer;
+pub(crate) mod encrypted_writer;
+pub(crate) mod entry_raw;
 pub(crate) mod entry_seekable;
 pub(crate) mod entry_stream;
 pub(crate) mod entry_whole;
 pub(crate) mod io;
+pub(crate) mod names;
+pub mod split;
 
 pub use entry_seekable::EntrySeekableWriter;
 pub use entry_stream::EntryStreamWriter;
+pub use names::DuplicateNames;
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
-use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::base::read::ReadOptions;
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, ZipError};
 use crate::spec::extra_field::ExtraFieldAsBytes;
 use crate::spec::header::{
     CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, InfoZipUnicodeCommentExtraField,
@@ -72,17 +78,34 @@ use crate::spec::header::{
 #[cfg(feature = "tokio")]
 use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
 
+use entry_raw::EntryRawWriter;
 use entry_whole::EntryWholeWriter;
 use io::offset::AsyncOffsetWriter;
+use names::EntryNames;
+use split::{SegmentFactory, SplitWriter};
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
-use futures_lite::io::{AsyncSeek, AsyncWrite, AsyncWriteExt};
+use crate::spec::consts::{
+    CDH_LENGTH, CDH_SIGNATURE, EOCDR_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH,
+    ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_MIN_SIZE,
+};
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufReader, SeekFrom};
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
     pub entry: ZipEntry,
 }
 
+impl CentralDirectoryEntry {
+    /// Constructs the record of a written entry.
+    ///
+    /// The entry's password is no longer needed once its data has been written, so is dropped here rather than being
+    /// retained until the writer is closed.
+    pub(crate) fn new(header: CentralDirectoryRecord, mut entry: ZipEntry) -> Self {
+        entry.encryption = None;
+        Self { header, entry }
+    }
+}
+
 /// A ZIP file writer which acts over AsyncWrite implementers.
 ///
 /// # Note
@@ -94,7 +117,8 @@ pub struct ZipFileWriter<W> {
     force_no_zip64: bool,
     /// Whether to write Zip64 end of directory structs.
     pub(crate) is_zip64: bool,
-    comment_opt: Option<String>,
+    comment_opt: Option<Vec<u8>>,
+    pub(crate) names: EntryNames,
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
@@ -106,9 +130,29 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            names: EntryNames::new(DuplicateNames::default()),
         }
     }
 
+    /// Sets how entries which share a name are handled.
+    ///
+    /// By default, writing an entry whose name exactly matches that of an earlier entry fails with
+    /// [`ZipError::DuplicateEntryName`].
+    pub fn duplicate_names(mut self, duplicate_names: DuplicateNames) -> Self {
+        self.names = EntryNames::new(duplicate_names);
+        for entry in &self.cd_entries {
+            self.names.insert(entry.entry.filename());
+        }
+        self
+    }
+
+    /// Allow entries which share a name to be written.
+    ///
+    /// This is equivalent to [`DuplicateNames::Allow`].
+    pub fn allow_duplicate_names(self) -> Self {
+        self.duplicate_names(DuplicateNames::Allow)
+    }
+
     /// Force the ZIP writer to operate in non-ZIP64 mode.
     /// If any files would need ZIP64, an error will be raised.
     pub fn force_no_zip64(mut self) -> Self {
@@ -125,14 +169,18 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
 
     /// Write a new ZIP entry of known size and data.
     pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_raw(self, entry.into(), data).write().await
+        let entry = entry.into();
+        self.names.check(entry.filename())?;
+        EntryWholeWriter::from_raw(self, entry, data).write().await
     }
 
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
     /// The generated Local File Header will be invalid, with no compressed size, uncompressed size,
     /// and a null CRC. This might cause problems with the destination reader.
     pub async fn write_entry_stream<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<EntryStreamWriter<'_, W>> {
-        EntryStreamWriter::from_raw(self, entry.into()).await
+        let entry = entry.into();
+        self.names.check(entry.filename())?;
+        EntryStreamWriter::from_raw(self, entry).await
     }
 
     /// Write an entry of unknown size and data via streaming to a seekable output.
@@ -143,12 +191,29 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     where
         W: AsyncSeek,
     {
-        EntrySeekableWriter::from_raw(self, entry.into()).await
+        let entry = entry.into();
+        self.names.check(entry.filename())?;
+        EntrySeekableWriter::from_raw(self, entry).await
+    }
+
+    /// Copy an entry from another ZIP file without decompressing or recompressing its data.
+    ///
+    /// `reader` should be the reader of the ZIP file which `entry` was read from (eg. via
+    /// [`seek::ZipFileReader::inner_mut()`](crate::base::read::seek::ZipFileReader::inner_mut)). The entry's
+    /// compressed data, CRC32 value, sizes, compression method, extra fields, and attributes are all kept as-is, with
+    /// only its local file header offset (and any ZIP64 fields) rewritten. Encrypted entries are copied without needing
+    /// their password.
+    pub async fn copy_raw_entry<R>(&mut self, entry: &StoredZipEntry, reader: R) -> Result<()>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        self.names.check(entry.filename())?;
+        EntryRawWriter::from_raw(self, entry, reader).write().await
     }
 
     /// Set the ZIP file comment.
     pub fn comment(&mut self, comment: String) {
-        self.comment_opt = Some(comment);
+        self.comment_opt = Some(comment.into_bytes());
     }
 
     /// Returns a mutable reference to the inner writer.
@@ -166,7 +231,16 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// - Writing the file comment.
     ///
     /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<W> {
+    pub async fn close(self) -> Result<W> {
+        self.close_with_len().await.map(|(writer, _)| writer)
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks (as with [`ZipFileWriter::close()`]), also returning
+    /// the final length of the ZIP file.
+    ///
+    /// When [appending](ZipFileWriter::append) to an existing file, the file should be truncated to this length, as
+    /// the merged central directory may end before the original did.
+    pub async fn close_with_len(mut self) -> Result<(W, u64)> {
         let file_comment_length = self
             .comment_opt
             .as_ref()
@@ -174,21 +248,45 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             .transpose()
             .map_err(|_| crate::error::ZipError::CommentTooLarge)?
             .unwrap_or_default();
-        let cd_offset = self.writer.offset();
+
+        // The disk and offset at which the central directory starts, and the number of its records on the last disk.
+        let mut cd_start = None;
+        let mut entries_on_disk = (self.writer.disk(), 0_u64);
+        let mut central_directory_size = 0;
 
         for entry in &self.cd_entries {
             let filename_basic =
                 entry.entry.filename().alternative().unwrap_or_else(|| entry.entry.filename().as_bytes());
             let comment_basic = entry.entry.comment().alternative().unwrap_or_else(|| entry.entry.comment().as_bytes());
+            let record_length = (SIGNATURE_LENGTH + CDH_LENGTH) as u64
+                + filename_basic.len() as u64
+                + entry.entry.extra_fields().count_bytes() as u64
+                + comment_basic.len() as u64;
+
+            self.writer.reserve(record_length).await?;
+            cd_start.get_or_insert((self.writer.disk(), self.writer.offset()));
+            if entries_on_disk.0 != self.writer.disk() {
+                entries_on_disk = (self.writer.disk(), 0);
+            }
 
             self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
             self.writer.write_all(&entry.header.as_slice()).await?;
             self.writer.write_all(filename_basic).await?;
             self.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?;
             self.writer.write_all(comment_basic).await?;
+            central_directory_size += record_length;
+            entries_on_disk.1 += 1;
         }
 
-        let central_directory_size = self.writer.offset() - cd_offset;
+        // Keep the end of central directory records together, allowing for the ZIP64 records regardless of whether
+        // they turn out to be needed.
+        let zip64_eocdr_length = SIGNATURE_LENGTH as u64 + 8 + ZIP64_EOCDR_MIN_SIZE;
+        let eocdr_length = (SIGNATURE_LENGTH + EOCDR_LENGTH) as u64 + file_comment_length as u64;
+        self.writer.reserve(zip64_eocdr_length + ZIP64_EOCDL_LENGTH + eocdr_length).await?;
+        let disk = self.writer.disk();
+        let (cd_disk, cd_offset) = cd_start.unwrap_or((disk, self.writer.offset()));
+        let num_entries_in_directory_on_disk = if entries_on_disk.0 == disk { entries_on_disk.1 } else { 0 };
+
         let central_directory_size_u32 =
             central_directory_size_field(central_directory_size, self.force_no_zip64, &mut self.is_zip64)?;
         let num_entries_in_directory = self.cd_entries.len() as u64;
@@ -197,6 +295,8 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         } else {
             num_entries_in_directory as u16
         };
+        let num_entries_in_directory_on_disk_u16 =
+            num_entries_in_directory_on_disk.min(NON_ZIP64_MAX_NUM_FILES as u64) as u16;
         let cd_offset_u32 = if cd_offset > NON_ZIP64_MAX_SIZE as u64 {
             if self.force_no_zip64 {
                 return Err(crate::error::ZipError::Zip64Needed(crate::error::Zip64ErrorCase::LargeFile));
@@ -216,9 +316,9 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
                 size_of_zip64_end_of_cd_record: crate::spec::consts::ZIP64_EOCDR_MIN_SIZE,
                 version_made_by: crate::spec::version::as_made_by(),
                 version_needed_to_extract: 46,
-                disk_number: 0,
-                disk_number_start_of_cd: 0,
-                num_entries_in_directory_on_disk: num_entries_in_directory,
+                disk_number: disk,
+                disk_number_start_of_cd: cd_disk,
+                num_entries_in_directory_on_disk,
                 num_entries_in_directory,
                 directory_size: central_directory_size,
                 offset_of_start_of_directory: cd_offset,
@@ -227,18 +327,18 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             self.writer.write_all(&eocdr.as_bytes()).await?;
 
             let eocdl = Zip64EndOfCentralDirectoryLocator {
-                number_of_disk_with_start_of_zip64_end_of_central_directory: 0,
+                number_of_disk_with_start_of_zip64_end_of_central_directory: disk,
                 relative_offset: eocdr_offset,
-                total_number_of_disks: 1,
+                total_number_of_disks: disk + 1,
             };
             self.writer.write_all(&crate::spec::consts::ZIP64_EOCDL_SIGNATURE.to_le_bytes()).await?;
             self.writer.write_all(&eocdl.as_bytes()).await?;
         }
 
         let header = EndOfCentralDirectoryHeader {
-            disk_num: 0,
-            start_cent_dir_disk: 0,
-            num_of_entries_disk: num_entries_in_directory_u16,
+            disk_num: disk as u16,
+            start_cent_dir_disk: cd_disk as u16,
+            num_of_entries_disk: num_entries_in_directory_on_disk_u16,
             num_of_entries: num_entries_in_directory_u16,
             size_cent_dir: central_directory_size_u32,
             cent_dir_offset: cd_offset_u32,
@@ -248,10 +348,11 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         self.writer.write_all(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()).await?;
         self.writer.write_all(&header.as_slice()).await?;
         if let Some(comment) = self.comment_opt {
-            self.writer.write_all(comment.as_bytes()).await?;
+            self.writer.write_all(&comment).await?;
         }
 
-        Ok(self.writer.into_inner())
+        let len = self.writer.written();
+        Ok((self.writer.into_inner(), len))
     }
 }
 
@@ -271,6 +372,89 @@ pub(crate) fn central_directory_size_field(
     }
 }
 
+impl<W: AsyncRead + AsyncWrite + AsyncSeek + Unpin> ZipFileWriter<W> {
+    /// Constructs a ZIP file writer which appends entries to an existing ZIP file.
+    ///
+    /// The existing central directory is loaded and new entries are written over it, with a merged central directory
+    /// (holding both the existing and new entries) written on [`ZipFileWriter::close()`]. The existing entries' data
+    /// is left untouched, and the file's comment is preserved unless replaced via [`ZipFileWriter::comment()`].
+    ///
+    /// # Note
+    /// The merged central directory may end before the original did (eg. if the comment is replaced with a shorter
+    /// one, or the original's end held additional records such as a central directory digital signature), which
+    /// would leave the original's end of central directory record in place. The writer should therefore be closed
+    /// via [`ZipFileWriter::close_with_len()`], and the underlying file truncated to the returned length.
+    pub async fn append(mut writer: W) -> Result<Self> {
+        let file = crate::base::read::file(&mut writer, &ReadOptions::default()).await?;
+        let cd_offset = file.directory_offset;
+
+        // Re-read the existing central directory headers so that fields not surfaced by `ZipEntry` (such as the
+        // version made by & general purpose flags) are preserved as-is.
+        writer.seek(SeekFrom::Start(cd_offset)).await?;
+        let mut reader = BufReader::new(&mut writer);
+        let mut cd_entries = Vec::with_capacity(file.entries.len());
+        for stored in file.entries {
+            crate::utils::assert_signature(&mut reader, CDH_SIGNATURE).await?;
+            let mut header = CentralDirectoryRecord::from_reader(&mut reader).await?;
+            let trailing_size =
+                header.file_name_length as u64 + header.extra_field_length as u64 + header.file_comment_length as u64;
+            crate::base::read::io::skip_bytes(&mut reader, trailing_size).await?;
+
+            header.extra_field_length =
+                stored.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
+            cd_entries.push(CentralDirectoryEntry::new(header, stored.entry));
+        }
+
+        writer.seek(SeekFrom::Start(cd_offset)).await?;
+        let mut names = EntryNames::new(DuplicateNames::default());
+        for entry in &cd_entries {
+            names.insert(entry.entry.filename());
+        }
+        let comment_opt = match file.comment.as_bytes() {
+            [] => None,
+            comment => Some(comment.to_vec()),
+        };
+
+        Ok(Self {
+            writer: AsyncOffsetWriter::with_offset(writer, cd_offset),
+            cd_entries,
+            comment_opt,
+            is_zip64: file.zip64,
+            force_no_zip64: false,
+            names,
+        })
+    }
+}
+
+impl<W, F> ZipFileWriter<SplitWriter<W, F>>
+where
+    W: AsyncWrite + Unpin,
+    F: SegmentFactory<W>,
+{
+    /// Construct a new ZIP file writer which splits its output into segments of at most `segment_size` bytes (eg. for
+    /// uploading to a store which caps the size of each object).
+    ///
+    /// The writer for each segment is created by `segments` as it's needed, with entries' data spanning segments.
+    /// Headers and the records at the end of the ZIP file are never split, and are instead moved to the start of the
+    /// next segment when they wouldn't fit within the current one.
+    ///
+    /// The segment size must be between [`MIN_SEGMENT_SIZE`](split::MIN_SEGMENT_SIZE) and
+    /// [`MAX_SEGMENT_SIZE`](split::MAX_SEGMENT_SIZE) bytes. Once closed, [`SplitWriter::into_inner()`] returns the
+    /// final segment, with the preceding segments having been passed to [`SegmentFactory::finish_segment()`]. The
+    /// first segment is buffered in memory until it's full, as its leading marker differs for a ZIP file which only
+    /// requires a single segment.
+    pub async fn new_split(segment_size: u64, segments: F) -> Result<Self> {
+        Ok(Self {
+            writer: AsyncOffsetWriter::with_split(SplitWriter::new(segment_size, segments)?),
+            cd_entries: Vec::new(),
+            comment_opt: None,
+            is_zip64: false,
+            force_no_zip64: false,
+            names: EntryNames::new(DuplicateNames::default()),
+        })
+    }
+}
+
 #[cfg(feature = "tokio")]
 impl<W> ZipFileWriter<Compat<W>>
 where
@@ -284,8 +468,19 @@ where
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            names: EntryNames::new(DuplicateNames::default()),
         }
     }
+
+    /// Constructs a tokio-specific ZIP file writer which appends entries to an existing ZIP file.
+    ///
+    /// See [`ZipFileWriter::append()`] for more information.
+    pub async fn append_with_tokio(writer: W) -> Result<TokioZipFileWriter<W>>
+    where
+        W: tokio::io::AsyncRead + tokio::io::AsyncSeek,
+    {
+        ZipFileWriter::append(writer.compat_write()).await
+    }
 }
 
 pub(crate) fn get_or_put_info_zip_unicode_path_extra_field_mut(
@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::options::lossy_name;
+use crate::error::{Result, ZipError};
+use crate::file::index::NameMatching;
+use crate::string::ZipString;
+
+use std::collections::HashSet;
+
+/// How a ZIP file writer handles entries which share a name.
+///
+/// Different tools extract different copies of a duplicated name, so an archive which contains them can present
+/// different files to different consumers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateNames {
+    /// Write all entries, without checking for duplicates.
+    Allow,
+    /// Fail with [`ZipError::DuplicateEntryName`] when an entry's name matches that of an earlier entry, as compared
+    /// via the provided [`NameMatching`].
+    ///
+    /// Names which only differ by case (or Unicode normalization) are extracted to the same path on some file systems,
+    /// which [`NameMatching::CaseInsensitive`] (or `NameMatching::Normalized`, with the `unicode-normalization`
+    /// feature) also rejects.
+    Reject(NameMatching),
+}
+
+impl Default for DuplicateNames {
+    /// Rejects names which exactly match that of an earlier entry.
+    fn default() -> Self {
+        DuplicateNames::Reject(NameMatching::Exact)
+    }
+}
+
+/// The names of the entries written so far, if duplicates are being rejected.
+pub(crate) struct EntryNames {
+    duplicate_names: DuplicateNames,
+    names: HashSet<Vec<u8>>,
+}
+
+impl EntryNames {
+    pub(crate) fn new(duplicate_names: DuplicateNames) -> Self {
+        Self { duplicate_names, names: HashSet::new() }
+    }
+
+    /// Checks that the name of an entry about to be written doesn't duplicate an earlier entry's name.
+    ///
+    /// The name is only recorded (via [`EntryNames::insert()`]) once the entry has been written, so that a failed
+    /// write doesn't leave it unusable.
+    pub(crate) fn check(&self, name: &ZipString) -> Result<()> {
+        match self.duplicate_names {
+            DuplicateNames::Reject(matching) if self.names.contains(&matching.key(name.as_bytes())) => {
+                Err(ZipError::DuplicateEntryName(lossy_name(name)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the name of an entry which has been written.
+    pub(crate) fn insert(&mut self, name: &ZipString) {
+        if let DuplicateNames::Reject(matching) = self.duplicate_names {
+            self.names.insert(matching.key(name.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Support for writing split ZIP files, as produced by WinZip and `zip -s` (eg. `archive.z01`, `archive.z02`, ...,
+//! `archive.zip`).
+//!
+//! See [`ZipFileWriter::new_split()`](crate::base::write::ZipFileWriter::new_split).
+
+use crate::error::ZipError;
+use crate::spec::consts::{SIGNATURE_LENGTH, SPANNING_SIGNATURE, TEMPORARY_SPANNING_MARKER};
+
+use futures_lite::io::AsyncWrite;
+use futures_lite::ready;
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// The smallest segment size permitted by the specification.
+pub const MIN_SEGMENT_SIZE: u64 = 64 * 1024;
+
+/// The largest segment size, beyond which offsets within a segment would require ZIP64 fields.
+pub const MAX_SEGMENT_SIZE: u64 = u32::MAX as u64;
+
+/// The number of disks which can be referenced without ZIP64 fields (with `0xFFFF` reserved as a sentinel).
+const MAX_DISKS: u32 = u16::MAX as u32;
+
+/// Creates the writer for each segment of a split ZIP file, and receives each segment once it has been written.
+///
+/// As the number of segments isn't known until the ZIP file has been written, the final segment isn't passed to
+/// [`SegmentFactory::finish_segment()`] but returned by [`SplitWriter::into_inner()`] instead. It's conventionally
+/// named `archive.zip`, with the preceding segments named `archive.z01`, `archive.z02`, and so on.
+///
+/// This trait is implemented for closures which only create segments, leaving any finished segments to be dropped.
+pub trait SegmentFactory<W> {
+    /// Returns the writer for the segment with the provided disk number, starting from zero.
+    fn create_segment(&mut self, disk: u32) -> std::io::Result<W>;
+
+    /// Receives a segment once it has been completely written and closed.
+    fn finish_segment(&mut self, disk: u32, segment: W) -> std::io::Result<()> {
+        let _ = (disk, segment);
+        Ok(())
+    }
+}
+
+impl<W, F> SegmentFactory<W> for F
+where
+    F: FnMut(u32) -> std::io::Result<W>,
+{
+    fn create_segment(&mut self, disk: u32) -> std::io::Result<W> {
+        self(disk)
+    }
+}
+
+/// The position of a [`SplitWriter`], shared with the ZIP file writer which writes to it.
+pub(crate) struct SplitState {
+    pub(crate) segment_size: u64,
+    disk: AtomicU32,
+    offset: AtomicU64,
+}
+
+impl SplitState {
+    /// Returns the disk number of the current segment.
+    pub(crate) fn disk(&self) -> u32 {
+        self.disk.load(Ordering::Relaxed)
+    }
+
+    /// Returns the offset relative to the start of the current segment.
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset.load(Ordering::Relaxed)
+    }
+}
+
+/// A writer which splits its output into segments of a maximum size, each created by a [`SegmentFactory`].
+///
+/// This is the writer used by [`ZipFileWriter::new_split()`](crate::base::write::ZipFileWriter::new_split), which
+/// additionally keeps headers from being split across segments.
+///
+/// The first segment starts with a marker whose value depends on whether further segments are needed, so its data is
+/// buffered in memory until it's either full or this writer is closed.
+pub struct SplitWriter<W, F> {
+    segment: W,
+    segments: F,
+    state: Arc<SplitState>,
+    first: Option<FirstSegment>,
+}
+
+/// The buffered data of the first segment, starting with space for its marker.
+struct FirstSegment {
+    data: Vec<u8>,
+    /// The number of bytes written to the segment, once its marker has been chosen.
+    written: Option<usize>,
+}
+
+impl<W, F> SplitWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: SegmentFactory<W>,
+{
+    /// Constructs a new writer which splits its output into segments of at most `segment_size` bytes.
+    ///
+    /// The segment size must be between [`MIN_SEGMENT_SIZE`] and [`MAX_SEGMENT_SIZE`] bytes.
+    pub fn new(segment_size: u64, mut segments: F) -> crate::error::Result<Self> {
+        if !(MIN_SEGMENT_SIZE..=MAX_SEGMENT_SIZE).contains(&segment_size) {
+            return Err(ZipError::InvalidSplitSegmentSize(segment_size));
+        }
+
+        let state =
+            SplitState { segment_size, disk: AtomicU32::new(0), offset: AtomicU64::new(SIGNATURE_LENGTH as u64) };
+        let first = FirstSegment { data: vec![0; SIGNATURE_LENGTH], written: None };
+        Ok(Self { segment: segments.create_segment(0)?, segments, state: Arc::new(state), first: Some(first) })
+    }
+
+    /// Closes the current segment and replaces it with the next, even if the current segment isn't full.
+    pub(crate) fn poll_next_segment(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        ready!(self.poll_write_first(cx, SPANNING_SIGNATURE))?;
+        ready!(Pin::new(&mut self.segment).poll_close(cx))?;
+
+        let disk = self.state.disk() + 1;
+        if disk >= MAX_DISKS {
+            return Poll::Ready(Err(std::io::Error::other(ZipError::TooManySplitSegments)));
+        }
+
+        let next = self.segments.create_segment(disk)?;
+        let finished = std::mem::replace(&mut self.segment, next);
+        self.segments.finish_segment(disk - 1, finished)?;
+
+        self.state.disk.store(disk, Ordering::Relaxed);
+        self.state.offset.store(0, Ordering::Relaxed);
+        Poll::Ready(Ok(()))
+    }
+
+    /// Consumes this writer, returning the current (and once the ZIP file has been written, final) segment alongside
+    /// the segment factory.
+    ///
+    /// If the first segment is still buffered (ie. the ZIP file only required a single segment), it's written with the
+    /// temporary spanning marker ("PK00") first, leaving a complete ZIP file.
+    pub async fn into_inner(mut self) -> std::io::Result<(W, F)> {
+        futures_lite::future::poll_fn(|cx| self.poll_write_first(cx, TEMPORARY_SPANNING_MARKER)).await?;
+        Ok((self.segment, self.segments))
+    }
+
+    /// Writes the buffered first segment (if not yet written), starting with the provided marker.
+    fn poll_write_first(&mut self, cx: &mut Context<'_>, marker: u32) -> Poll<std::io::Result<()>> {
+        let Some(first) = self.first.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+        let written = first.written.get_or_insert_with(|| {
+            first.data[..SIGNATURE_LENGTH].copy_from_slice(&marker.to_le_bytes());
+            0
+        });
+
+        while *written < first.data.len() {
+            match ready!(Pin::new(&mut self.segment).poll_write(cx, &first.data[*written..]))? {
+                0 => return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into())),
+                length => *written += length,
+            }
+        }
+
+        self.first = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, F> SplitWriter<W, F> {
+    /// Returns the disk number of the current segment, starting from zero.
+    pub fn disk(&self) -> u32 {
+        self.state.disk()
+    }
+
+    pub(crate) fn state(&self) -> Arc<SplitState> {
+        self.state.clone()
+    }
+}
+
+// The segment factory is never pinned.
+impl<W: Unpin, F> Unpin for SplitWriter<W, F> {}
+
+impl<W, F> AsyncWrite for SplitWriter<W, F>
+where
+    W: AsyncWrite + Unpin,
+    F: SegmentFactory<W>,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if this.state.offset() >= this.state.segment_size && !buf.is_empty() {
+            ready!(this.poll_next_segment(cx))?;
+        }
+
+        let remaining = this.state.segment_size - this.state.offset();
+        let limit = buf.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let written = match this.first.as_mut() {
+            Some(FirstSegment { data, written: None }) => {
+                data.extend_from_slice(&buf[..limit]);
+                limit
+            }
+            _ => ready!(Pin::new(&mut this.segment).poll_write(cx, &buf[..limit]))?,
+        };
+        this.state.offset.fetch_add(written as u64, Ordering::Relaxed);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().segment).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_first(cx, TEMPORARY_SPANNING_MARKER))?;
+        Pin::new(&mut this.segment).poll_close(cx)
+    }
+}
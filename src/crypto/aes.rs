@@ -0,0 +1,136 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! WinZip AES encryption (AE-1 & AE-2).
+//!
+//! See the [WinZip documentation](https://www.winzip.com/en/support/aes-encryption/) for the format. In short, keys
+//! are derived from the password and a per-entry salt via PBKDF2-HMAC-SHA1, data is encrypted with AES in CTR mode
+//! using a little-endian counter starting at one, and the encrypted data is authenticated with HMAC-SHA1.
+
+use crate::spec::header::AesStrength;
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::{Aes128, Aes192, Aes256};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// The length of the password verification value which follows the salt of every AES-encrypted entry.
+pub(crate) const PASSWORD_VERIFIER_LENGTH: usize = 2;
+
+/// The length of the authentication code which follows the data of every AES-encrypted entry.
+pub(crate) const AUTHENTICATION_CODE_LENGTH: usize = 10;
+
+/// The number of PBKDF2 iterations used to derive the keys of an entry.
+const KEY_DERIVATION_ITERATIONS: u32 = 1000;
+
+/// The block size of AES, and therefore the length of each keystream block.
+const BLOCK_LENGTH: usize = 16;
+
+enum BlockCipher {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl BlockCipher {
+    fn new(strength: AesStrength, key: &[u8]) -> Self {
+        match strength {
+            AesStrength::Aes128 => BlockCipher::Aes128(Aes128::new(GenericArray::from_slice(key))),
+            AesStrength::Aes192 => BlockCipher::Aes192(Aes192::new(GenericArray::from_slice(key))),
+            AesStrength::Aes256 => BlockCipher::Aes256(Aes256::new(GenericArray::from_slice(key))),
+        }
+    }
+
+    fn encrypt_block(&self, block: &mut [u8; BLOCK_LENGTH]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            BlockCipher::Aes128(cipher) => cipher.encrypt_block(block),
+            BlockCipher::Aes192(cipher) => cipher.encrypt_block(block),
+            BlockCipher::Aes256(cipher) => cipher.encrypt_block(block),
+        }
+    }
+}
+
+/// The cipher and authentication state of a WinZip AES stream.
+pub(crate) struct AesCipher {
+    cipher: BlockCipher,
+    mac: Hmac<Sha1>,
+    counter: u128,
+    keystream: [u8; BLOCK_LENGTH],
+    position: usize,
+}
+
+impl AesCipher {
+    /// Derives the cipher state for the provided password and salt, returning it alongside the password verifier which
+    /// should be compared against the one stored after the salt.
+    pub(crate) fn new(password: &[u8], strength: AesStrength, salt: &[u8]) -> (Self, [u8; PASSWORD_VERIFIER_LENGTH]) {
+        let key_length = strength.key_length();
+        let mut derived = vec![0; 2 * key_length + PASSWORD_VERIFIER_LENGTH];
+        pbkdf2::pbkdf2_hmac::<Sha1>(password, salt, KEY_DERIVATION_ITERATIONS, &mut derived);
+
+        let cipher = BlockCipher::new(strength, &derived[..key_length]);
+        let mac = <Hmac<Sha1> as Mac>::new_from_slice(&derived[key_length..2 * key_length])
+            .expect("HMAC should accept keys of any length");
+        let verifier = [derived[2 * key_length], derived[2 * key_length + 1]];
+
+        (Self { cipher, mac, counter: 0, keystream: [0; BLOCK_LENGTH], position: BLOCK_LENGTH }, verifier)
+    }
+
+    /// Feeds encrypted data into the authentication code.
+    ///
+    /// This is kept separate from [`AesCipher::apply_keystream()`] as readers may decrypt ahead of what's actually
+    /// consumed as entry data.
+    pub(crate) fn authenticate(&mut self, encrypted: &[u8]) {
+        self.mac.update(encrypted);
+    }
+
+    /// Returns the authentication code of all data authenticated so far, to be written after the entry's data.
+    pub(crate) fn authentication_code(&self) -> [u8; AUTHENTICATION_CODE_LENGTH] {
+        let mut code = [0; AUTHENTICATION_CODE_LENGTH];
+        code.copy_from_slice(&self.mac.clone().finalize().into_bytes()[..AUTHENTICATION_CODE_LENGTH]);
+        code
+    }
+
+    /// Checks the authentication code of all data authenticated so far against the one stored after the entry's data.
+    pub(crate) fn verify(&self, code: &[u8; AUTHENTICATION_CODE_LENGTH]) -> bool {
+        self.mac.clone().verify_truncated_left(code).is_ok()
+    }
+
+    /// Encrypts or decrypts the provided buffer in place.
+    pub(crate) fn apply_keystream(&mut self, buffer: &mut [u8]) {
+        for byte in buffer {
+            if self.position == BLOCK_LENGTH {
+                self.counter = self.counter.wrapping_add(1);
+                self.keystream = self.counter.to_le_bytes();
+                self.cipher.encrypt_block(&mut self.keystream);
+                self.position = 0;
+            }
+
+            *byte ^= self.keystream[self.position];
+            self.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_starts_at_one_and_is_little_endian() {
+        let key = [0x42; 16];
+        let (mut cipher, _) = AesCipher::new(b"password", AesStrength::Aes128, &[0; 8]);
+        cipher.cipher = BlockCipher::new(AesStrength::Aes128, &key);
+
+        let mut data = [0; 2 * BLOCK_LENGTH];
+        cipher.apply_keystream(&mut data);
+
+        let reference = Aes128::new(GenericArray::from_slice(&key));
+        for (index, chunk) in data.chunks(BLOCK_LENGTH).enumerate() {
+            let mut block = GenericArray::from((index as u128 + 1).to_le_bytes());
+            reference.encrypt_block(&mut block);
+            assert_eq!(chunk, block.as_slice());
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! A module which holds the cipher implementations used for encrypted ZIP entries.
+
+#[cfg(feature = "aes")]
+pub(crate) mod aes;
+pub(crate) mod zip_crypto;
+
+use crate::error::{Result, ZipError};
+
+/// Fills the provided buffer with random bytes from the operating system's random number generator, as needed for
+/// encryption headers and salts.
+#[cfg(feature = "encryption")]
+pub(crate) fn fill_random(buffer: &mut [u8]) -> Result<()> {
+    getrandom::getrandom(buffer).map_err(|err| ZipError::RandomSourceError(err.into()))
+}
+
+/// Encrypted entries can't be written without a source of secure random bytes.
+#[cfg(not(feature = "encryption"))]
+pub(crate) fn fill_random(_: &mut [u8]) -> Result<()> {
+    Err(ZipError::FeatureNotSupported("writing encrypted entries without the `encryption` feature"))
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Traditional PKWARE encryption (often referred to as ZipCrypto).
+//!
+//! See the [specification](https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#61) for the key schedule.
+
+/// The length of the encryption header which precedes the data of every ZipCrypto-encrypted entry.
+pub(crate) const ENCRYPTION_HEADER_LENGTH: usize = 12;
+
+/// The CRC32 lookup table used by the key schedule, which operates on the raw (non-inverted) CRC register.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(crc: u32, byte: u8) -> u32 {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+}
+
+/// The three-key cipher state of a ZipCrypto stream.
+#[derive(Clone)]
+pub(crate) struct ZipCryptoKeys {
+    key0: u32,
+    key1: u32,
+    key2: u32,
+}
+
+impl ZipCryptoKeys {
+    /// Constructs the initial cipher state for the provided password.
+    pub(crate) fn new(password: &[u8]) -> Self {
+        let mut keys = Self { key0: 0x12345678, key1: 0x23456789, key2: 0x34567890 };
+        for byte in password {
+            keys.update(*byte);
+        }
+        keys
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.key0 = crc32_update(self.key0, byte);
+        self.key1 = self.key1.wrapping_add(self.key0 & 0xff).wrapping_mul(134775813).wrapping_add(1);
+        self.key2 = crc32_update(self.key2, (self.key1 >> 24) as u8);
+    }
+
+    fn stream_byte(&self) -> u8 {
+        let temp = (self.key2 | 2) as u16;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    /// Decrypts the provided buffer in place, advancing the cipher state.
+    pub(crate) fn decrypt(&mut self, buffer: &mut [u8]) {
+        for byte in buffer {
+            let plain = *byte ^ self.stream_byte();
+            self.update(plain);
+            *byte = plain;
+        }
+    }
+
+    /// Encrypts the provided buffer in place, advancing the cipher state.
+    pub(crate) fn encrypt(&mut self, buffer: &mut [u8]) {
+        for byte in buffer {
+            let plain = *byte;
+            *byte = plain ^ self.stream_byte();
+            self.update(plain);
+        }
+    }
+
+    /// Decrypts an entry's encryption header and checks it against the expected verification byte.
+    ///
+    /// The final header byte holds the high byte of the entry's CRC32, or the high byte of its last modification time
+    /// when the CRC32 is deferred to a data descriptor.
+    pub(crate) fn verify_header(&mut self, mut header: [u8; ENCRYPTION_HEADER_LENGTH], check: u8) -> bool {
+        self.decrypt(&mut header);
+        header[ENCRYPTION_HEADER_LENGTH - 1] == check
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trips() {
+        let mut data = *b"Some plaintext content.";
+        ZipCryptoKeys::new(b"secret").encrypt(&mut data);
+        assert_ne!(&data, b"Some plaintext content.");
+
+        ZipCryptoKeys::new(b"secret").decrypt(&mut data);
+        assert_eq!(&data, b"Some plaintext content.");
+    }
+
+    #[test]
+    fn crc32_table_matches_reference() {
+        assert_eq!(CRC32_TABLE[1], 0x77073096);
+        assert_eq!(CRC32_TABLE[255], 0x2D02EF8D);
+    }
+}
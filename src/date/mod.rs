@@ -8,6 +8,8 @@ use jiff::{civil, tz::Offset, Timestamp};
 
 use self::builder::ZipDateTimeBuilder;
 
+use std::time::{Duration, SystemTime};
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#446
 // https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-dosdatetimetovarianttime
 
@@ -70,6 +72,51 @@ impl ZipDateTime {
         ((self.time & 0x1F) << 1).into()
     }
 
+    /// Constructs the [`SystemTime`] represented by this date & time, interpreting it as UTC.
+    ///
+    /// MS-DOS dates & times have no associated time zone, and are conventionally recorded in the local time of the
+    /// machine which wrote the ZIP file, so the result is offset by that machine's UTC offset. Where the time zone is
+    /// known, the civil date & time should instead be interpreted within it (eg. via `as_jiff()` with the `jiff`
+    /// feature).
+    ///
+    /// Returns `None` if this date & time isn't valid.
+    pub fn as_system_time(&self) -> Option<SystemTime> {
+        let (month, day) = (self.month() as i64, self.day() as i64);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let seconds = days_from_civil(self.year().into(), month, day) * 86400
+            + self.hour() as i64 * 3600
+            + self.minute() as i64 * 60
+            + self.second() as i64;
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds.try_into().ok()?))
+    }
+
+    /// Constructs this date & time from a [`SystemTime`], in UTC.
+    ///
+    /// As with [`ZipDateTime::as_system_time()`], this differs from the convention of recording local time, which
+    /// keeps archives reproducible regardless of the time zone they're written in.
+    ///
+    /// Returns `None` if the time falls outside of the years representable by MS-DOS (1980 to 2107).
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let seconds = time.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs() as i64;
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+        if !(1980..=2107).contains(&year) {
+            return None;
+        }
+
+        let seconds = seconds.rem_euclid(86400);
+        let builder = ZipDateTimeBuilder::new()
+            .year(year as i32)
+            .month(month as u32)
+            .day(day as u32)
+            .hour((seconds / 3600) as u32)
+            .minute((seconds % 3600 / 60) as u32)
+            .second((seconds % 60) as u32);
+        Some(builder.build())
+    }
+
     /// Constructs Jiff's [`civil::DateTime`] representation of this date & time.
     ///
     /// Note that this requires the `jiff` feature.
@@ -87,6 +134,33 @@ impl ZipDateTime {
     }
 }
 
+// The conversions below between (proleptic Gregorian) civil dates and days since the Unix epoch are as per:
+// https://howardhinnant.github.io/date_algorithms.html
+//
+// They don't account for time zones, so are only correct for dates & times in UTC.
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
 impl From<ZipDateTimeBuilder> for ZipDateTime {
     fn from(builder: ZipDateTimeBuilder) -> Self {
         builder.0
@@ -1,10 +1,12 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::entry::ZipEntry;
-use crate::spec::{attribute::AttributeCompatibility, header::ExtraField, Compression};
+use crate::entry::{EntryEncryption, ZipEntry};
+use crate::spec::{attribute::AttributeCompatibility, encryption::Encryption, header::ExtraField, Compression};
 use crate::{date::ZipDateTime, string::ZipString};
 
+use std::sync::Arc;
+
 /// A builder for [`ZipEntry`].
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
 
@@ -34,6 +36,14 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the encryption method used to write the entry's data, and the password to encrypt it with.
+    ///
+    /// Writing encrypted entries requires the `encryption` feature (and the `aes` feature for AES encryption).
+    pub fn encryption(mut self, encryption: Encryption, password: impl AsRef<[u8]>) -> Self {
+        self.0.encryption = Some(Arc::new(EntryEncryption { method: encryption, password: password.as_ref().into() }));
+        self
+    }
+
     /// Set a size hint for the file, to be written into the local file header.
     /// Unlikely to be useful except for the case of streaming files to be Store'd.
     /// This size hint does not affect the central directory, nor does it affect whole files.
@@ -3,18 +3,23 @@
 
 pub mod builder;
 
+use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
+use std::sync::Arc;
 
 use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
 
+use crate::base::read::warnings::{EntryWarnings, WarningKind};
 use crate::base::read::{get_combined_sizes, get_zip64_extra_field};
 use crate::entry::builder::ZipEntryBuilder;
 use crate::error::{Result, ZipError};
 use crate::spec::{
     attribute::AttributeCompatibility,
     consts::{LFH_LENGTH, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH},
+    encryption::Encryption,
     header::{ExtraField, LocalFileHeader},
     parse::parse_extra_fields,
+    version::validate_compression_version,
     Compression,
 };
 use crate::{string::ZipString, ZipDateTime};
@@ -47,9 +52,23 @@ pub struct ZipEntry {
     pub(crate) extra_fields: Vec<ExtraField>,
     pub(crate) comment: ZipString,
     pub(crate) data_descriptor: bool,
+    pub(crate) encrypted: bool,
+    pub(crate) encryption: Option<Arc<EntryEncryption>>,
     pub(crate) file_offset: u64,
 }
 
+/// The encryption to apply when writing an entry, alongside the password to key it with.
+pub(crate) struct EntryEncryption {
+    pub(crate) method: Encryption,
+    pub(crate) password: Box<[u8]>,
+}
+
+impl Debug for EntryEncryption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EntryEncryption").field("method", &self.method).finish_non_exhaustive()
+    }
+}
+
 impl From<ZipEntryBuilder> for ZipEntry {
     fn from(builder: ZipEntryBuilder) -> Self {
         builder.0
@@ -80,6 +99,8 @@ impl ZipEntry {
             extra_fields: Vec::new(),
             comment: String::new().into(),
             data_descriptor: false,
+            encrypted: false,
+            encryption: None,
             file_offset: 0,
         }
     }
@@ -166,6 +187,11 @@ impl ZipEntry {
         self.data_descriptor
     }
 
+    /// Returns whether or not the entry's data is encrypted.
+    pub fn encrypted(&self) -> bool {
+        self.encrypted
+    }
+
     /// Returns the file offset in bytes of the local file header for this entry.
     pub fn file_offset(&self) -> u64 {
         self.file_offset
@@ -183,6 +209,10 @@ pub struct StoredZipEntry {
     pub(crate) file_offset: u64,
     pub(crate) header_size: u64,
     pub(crate) data_end_boundary: u64,
+    /// The "version made by" field of the entry's central directory record.
+    pub(crate) version_made_by: u16,
+    /// The offset of the entry's central directory record.
+    pub(crate) cd_offset: u64,
 }
 
 impl StoredZipEntry {
@@ -200,7 +230,14 @@ impl StoredZipEntry {
     }
 
     /// Seek to the offset in bytes where the data of the entry starts.
-    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<()> {
+    ///
+    /// The local file header is validated against the central directory, with tolerable inconsistencies passed to
+    /// `warnings`.
+    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(
+        &self,
+        mut reader: &mut R,
+        warnings: EntryWarnings<'_>,
+    ) -> Result<()> {
         // Seek to the header
         reader.seek(SeekFrom::Start(self.file_offset)).await?;
 
@@ -217,7 +254,10 @@ impl StoredZipEntry {
         };
 
         // Read and validate the local file header's trailing data.
-        let header = LocalFileHeader::from_reader(&mut reader).await?;
+        let header = LocalFileHeader::from_reader_without_compression_check(&mut reader).await?;
+        if let Err(err) = validate_compression_version(header.version, header.compression) {
+            warnings.tolerate(WarningKind::LocalCompressionVersion, err)?;
+        }
         let data_start = self
             .file_offset
             .checked_add((SIGNATURE_LENGTH + LFH_LENGTH) as u64)
@@ -237,18 +277,22 @@ impl StoredZipEntry {
             return Err(ZipError::LocalFileHeaderDataDescriptorMismatch);
         }
 
+        if header.flags.encrypted != self.entry.encrypted {
+            return Err(ZipError::LocalFileHeaderEncryptionMismatch);
+        }
+
         let local_filename = crate::base::read::io::read_bytes(&mut reader, header.file_name_length.into()).await?;
         if local_filename.as_slice() != self.entry.filename().as_bytes() {
             return Err(ZipError::LocalFileHeaderNameMismatch);
         }
 
-        if !header.flags.data_descriptor
+        let size_mismatch = !header.flags.data_descriptor
             && ((header.compressed_size != NON_ZIP64_MAX_SIZE
                 && header.compressed_size as u64 != self.entry.compressed_size)
                 || (header.uncompressed_size != NON_ZIP64_MAX_SIZE
-                    && header.uncompressed_size as u64 != self.entry.uncompressed_size))
-        {
-            return Err(ZipError::LocalFileHeaderSizeMismatch);
+                    && header.uncompressed_size as u64 != self.entry.uncompressed_size));
+        if size_mismatch {
+            warnings.tolerate(WarningKind::LocalHeaderSizes, ZipError::LocalFileHeaderSizeMismatch)?;
         }
 
         let mut extra_field = vec![0; usize::from(header.extra_field_length)];
@@ -257,12 +301,17 @@ impl StoredZipEntry {
             parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size, None, None)?;
         let zip64_extra_field = get_zip64_extra_field(&extra_fields);
 
+        // The sizes have already been found to mismatch (and that was tolerated), so aren't compared again.
+        if size_mismatch {
+            return Ok(());
+        }
+
         if !header.flags.data_descriptor
             && zip64_extra_field.is_none()
             && extra_fields.is_empty()
             && (header.compressed_size == NON_ZIP64_MAX_SIZE || header.uncompressed_size == NON_ZIP64_MAX_SIZE)
         {
-            return Err(ZipError::LocalFileHeaderSizeMismatch);
+            return warnings.tolerate(WarningKind::LocalHeaderSizes, ZipError::LocalFileHeaderSizeMismatch);
         }
 
         let (local_uncompressed_size, local_compressed_size) =
@@ -272,7 +321,7 @@ impl StoredZipEntry {
             && (local_compressed_size != self.entry.compressed_size
                 || local_uncompressed_size != self.entry.uncompressed_size)
         {
-            return Err(ZipError::LocalFileHeaderSizeMismatch);
+            return warnings.tolerate(WarningKind::LocalHeaderSizes, ZipError::LocalFileHeaderSizeMismatch);
         }
 
         Ok(())
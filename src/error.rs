@@ -24,6 +24,19 @@ impl Display for Zip64ErrorCase {
     }
 }
 
+impl From<std::io::Error> for ZipError {
+    /// Converts an IO error, recovering the original error if it's one raised by this crate whilst reading (as
+    /// entry readers can only surface errors via [`std::io::Error`]).
+    fn from(err: std::io::Error) -> Self {
+        if err.get_ref().is_some_and(|inner| inner.is::<ZipError>()) {
+            let inner = err.into_inner().expect("error should have an inner value");
+            return *inner.downcast::<ZipError>().expect("inner error should be a ZipError");
+        }
+
+        ZipError::UpstreamReadError(err)
+    }
+}
+
 /// An enum of possible errors and their descriptions.
 #[non_exhaustive]
 #[derive(Debug, Error)]
@@ -61,15 +74,29 @@ pub enum ZipError {
     DuplicateExtraFieldHeader(u16),
 
     #[error("an upstream reader returned an error: {0}")]
-    UpstreamReadError(#[from] std::io::Error),
+    UpstreamReadError(#[source] std::io::Error),
     #[error("a computed CRC32 value did not match the expected value")]
     CRC32CheckError,
+    #[error("entry is encrypted but no password was provided")]
+    PasswordRequired,
+    #[error("the provided password did not match the entry's encryption header")]
+    IncorrectPassword,
+    #[error(
+        "a computed authentication code did not match the expected value; the entry data may have been tampered with"
+    )]
+    AuthenticationCodeMismatch,
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
+    #[error("no entry with the provided name was found")]
+    EntryNameNotFound,
     #[error("the local file header name did not match the central directory name")]
     LocalFileHeaderNameMismatch,
     #[error("local file header data-descriptor flag did not match the central directory flag")]
     LocalFileHeaderDataDescriptorMismatch,
+    #[error("unable to locate a data descriptor matching the preceding entry data")]
+    DataDescriptorNotFound,
+    #[error("local file header encryption flag did not match the central directory flag")]
+    LocalFileHeaderEncryptionMismatch,
     #[error("local file header sizes did not match the central directory sizes")]
     LocalFileHeaderSizeMismatch,
     #[error("entry data range was invalid")]
@@ -78,6 +105,50 @@ pub enum ZipError {
     EntryDataRangeOverlap { start: u64, end: u64, boundary: u64 },
     #[error("ZIP version {version} is too low for compression method {compression}; expected {required}")]
     InvalidCompressionVersion { version: u16, required: u16, compression: u16 },
+    #[error("entry data exceeded its declared uncompressed size of {0} bytes")]
+    UncompressedSizeExceeded(u64),
+    #[error("entry data ({actual} bytes) did not match its declared uncompressed size of {expected} bytes")]
+    UncompressedSizeMismatch { expected: u64, actual: u64 },
+    #[error("entry data did not span its declared compressed size of {0} bytes")]
+    CompressedSizeMismatch(u64),
+    #[error("entry data exceeded the maximum entry size of {0} bytes")]
+    EntrySizeLimitExceeded(u64),
+    #[error("archive data exceeded the maximum total size of {0} bytes")]
+    TotalSizeLimitExceeded(u64),
+    #[error("entry data exceeded the maximum compression ratio of {0}:1")]
+    CompressionRatioLimitExceeded(u64),
+    #[error("the central directory declared {entries} entries, exceeding the limit of {limit}")]
+    EntryCountLimitExceeded { entries: u64, limit: u64 },
+    #[error("the central directory's filenames exceeded the limit of {0} bytes in total")]
+    TotalFileNameLimitExceeded(u64),
+    #[error("an entry's extra fields ({length} bytes) exceeded the limit of {limit} bytes")]
+    ExtraFieldLimitExceeded { length: u64, limit: u64 },
+    #[error("a comment ({length} bytes) exceeded the limit of {limit} bytes")]
+    CommentLimitExceeded { length: u64, limit: u64 },
+    #[error("the entry name '{0}' was duplicated")]
+    DuplicateEntryName(String),
+    #[error("entry path '{0}' would be extracted outside of the target directory")]
+    UnsafeEntryPath(String),
+    #[error("extraction target '{}' already exists", .0.display())]
+    ExtractTargetExists(std::path::PathBuf),
+    #[error("entry '{0}' is a symbolic link, which can't be extracted")]
+    SymlinkEntry(String),
+    #[error("disk {0} of the split ZIP file was not provided")]
+    MissingSplitSegment(u32),
+    #[error("the split ZIP file spans {expected} disks, but {actual} segments were provided")]
+    SplitSegmentCountMismatch { expected: u64, actual: usize },
+    #[error("a split segment size of {0} bytes is outside of the supported range")]
+    InvalidSplitSegmentSize(u64),
+    #[error("a record of {0} bytes doesn't fit within a single segment of the split ZIP file")]
+    SplitRecordTooLarge(u64),
+    #[error("the split ZIP file requires more segments than can be numbered")]
+    TooManySplitSegments,
+    #[error("a range request for {expected} bytes returned {actual} bytes")]
+    RangeLengthMismatch { expected: u64, actual: u64 },
+    #[error("the entry's data is compressed or encrypted, so can't be accessed as-is")]
+    EntryNotStored,
+    #[error("a nested archive of {size} bytes exceeded the buffering limit of {limit} bytes")]
+    NestedArchiveLimitExceeded { size: u64, limit: u64 },
     #[error("Encountered an unexpected header (actual: {0:#x}, expected: {1:#x}).")]
     UnexpectedHeaderError(u32, u32),
 
@@ -87,6 +158,12 @@ pub enum ZipError {
     InfoZipUnicodePathFieldIncomplete,
     #[error("Info-ZIP Unicode Path Extra Field contains invalid UTF-8")]
     InfoZipUnicodePathFieldInvalidUtf8,
+    #[error("WinZip AES Extra Field was invalid")]
+    InvalidWinZipAesExtraField,
+    #[error("entry uses WinZip AES encryption but has no WinZip AES Extra Field")]
+    MissingWinZipAesExtraField,
+    #[error("the random number generator used to encrypt entries failed")]
+    RandomSourceError(#[source] std::io::Error),
 
     #[error("the end of central directory offset ({0:#x}) did not match the actual offset ({1:#x})")]
     InvalidEndOfCentralDirectoryOffset(u64, u64),
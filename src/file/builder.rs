@@ -14,7 +14,15 @@ impl From<ZipFile> for ZipFileBuilder {
 
 impl Default for ZipFileBuilder {
     fn default() -> Self {
-        ZipFileBuilder(ZipFile { entries: Vec::new(), zip64: false, comment: String::new().into() })
+        ZipFileBuilder(ZipFile {
+            entries: Vec::new(),
+            zip64: false,
+            comment: String::new().into(),
+            prefix_length: 0,
+            directory_offset: 0,
+            warnings: Default::default(),
+            name_indices: Default::default(),
+        })
     }
 }
 
@@ -0,0 +1,211 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::StoredZipEntry;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::UnicodeNormalization;
+
+/// How entry names are compared when looking them up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatching {
+    /// Names must match byte-for-byte.
+    #[default]
+    Exact,
+    /// Names are compared after being lowercased, as is expected of archives created on Windows.
+    ///
+    /// UTF-8 names are lowercased per Unicode, whereas other names only have their ASCII characters lowercased.
+    CaseInsensitive,
+    /// Names are compared case-insensitively, after UTF-8 names have been brought into Unicode Normalization Form C.
+    ///
+    /// This treats names which would be extracted to the same path on most file systems (eg. a precomposed `é` and an
+    /// `e` followed by a combining acute accent) as equal.
+    ///
+    /// Note that this requires the `unicode-normalization` feature.
+    #[cfg(feature = "unicode-normalization")]
+    Normalized,
+}
+
+impl NameMatching {
+    pub(crate) fn key(self, name: &[u8]) -> Vec<u8> {
+        match (self, std::str::from_utf8(name)) {
+            (NameMatching::Exact, _) => name.to_vec(),
+            (NameMatching::CaseInsensitive, Ok(name)) => name.to_lowercase().into_bytes(),
+            #[cfg(feature = "unicode-normalization")]
+            (NameMatching::Normalized, Ok(name)) => name.to_lowercase().nfc().collect::<String>().into_bytes(),
+            (_, Err(_)) => name.to_ascii_lowercase(),
+        }
+    }
+}
+
+/// An index of a ZIP file's entries by name, supporting exact, prefix, and directory lookups.
+///
+/// Each entry is indexed by both its UTF-8 name and, where one is present, the raw name stored alongside it (eg. when
+/// the UTF-8 name was taken from an Info-ZIP Unicode Path extra field). The index is built on first use via
+/// [`ZipFile::name_index()`](crate::ZipFile::name_index).
+#[derive(Clone, Debug)]
+pub struct NameIndex {
+    matching: NameMatching,
+    /// Pairs of names and entry indices, sorted by name and then by index.
+    names: Vec<(Vec<u8>, usize)>,
+}
+
+impl NameIndex {
+    pub(crate) fn new(entries: &[StoredZipEntry], matching: NameMatching) -> Self {
+        let mut names = Vec::with_capacity(entries.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let filename = entry.filename();
+            let name = matching.key(filename.as_bytes());
+            if let Some(alternative) = filename.alternative().map(|name| matching.key(name)) {
+                if alternative != name {
+                    names.push((alternative, index));
+                }
+            }
+            names.push((name, index));
+        }
+
+        names.sort_unstable();
+        Self { matching, names }
+    }
+
+    /// Returns how this index compares names.
+    pub fn matching(&self) -> NameMatching {
+        self.matching
+    }
+
+    /// Returns the index of the entry with the provided name.
+    ///
+    /// If several entries share the name, the index of the first is returned.
+    pub fn get(&self, name: impl AsRef<[u8]>) -> Option<usize> {
+        let name = self.matching.key(name.as_ref());
+        let start = self.names.partition_point(|(key, _)| *key < name);
+        self.names.get(start).filter(|(key, _)| *key == name).map(|(_, index)| *index)
+    }
+
+    /// Returns the indices of all entries whose names start with the provided prefix, in ascending order.
+    pub fn with_prefix(&self, prefix: impl AsRef<[u8]>) -> Vec<usize> {
+        let prefix = self.matching.key(prefix.as_ref());
+        self.collect(&prefix, |_| true)
+    }
+
+    /// Returns the indices of all entries directly within the provided directory, in ascending order.
+    ///
+    /// This includes files and subdirectories which are present as entries, but not their descendants. An empty
+    /// name (or `/`) refers to the root of the archive.
+    pub fn directory(&self, directory: impl AsRef<[u8]>) -> Vec<usize> {
+        let mut prefix = self.matching.key(directory.as_ref());
+        while prefix.last() == Some(&b'/') {
+            prefix.pop();
+        }
+        if !prefix.is_empty() {
+            prefix.push(b'/');
+        }
+
+        self.collect(&prefix, |remainder| {
+            let remainder = remainder.strip_suffix(b"/").unwrap_or(remainder);
+            !remainder.is_empty() && !remainder.contains(&b'/')
+        })
+    }
+
+    fn collect(&self, prefix: &[u8], filter: impl Fn(&[u8]) -> bool) -> Vec<usize> {
+        let start = self.names.partition_point(|(key, _)| key.as_slice() < prefix);
+        let mut indices: Vec<usize> = self.names[start..]
+            .iter()
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .filter(|(key, _)| filter(&key[prefix.len()..]))
+            .map(|(_, index)| *index)
+            .collect();
+
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::ZipFile;
+    use crate::{Compression, ZipEntryBuilder, ZipString};
+
+    fn file(names: Vec<ZipString>) -> ZipFile {
+        let entries = names
+            .into_iter()
+            .map(|name| StoredZipEntry {
+                entry: ZipEntryBuilder::new(name, Compression::Stored).build(),
+                file_offset: 0,
+                header_size: 0,
+                data_end_boundary: 0,
+                version_made_by: 0,
+                cd_offset: 0,
+            })
+            .collect();
+        ZipFile {
+            entries,
+            zip64: false,
+            comment: String::new().into(),
+            prefix_length: 0,
+            directory_offset: 0,
+            warnings: Default::default(),
+            name_indices: Default::default(),
+        }
+    }
+
+    #[test]
+    fn exact_lookup_matches_utf8_and_raw_names() {
+        let file = file(vec![
+            "METADATA".into(),
+            ZipString::new_with_alternative("caf\u{e9}.txt".to_string(), b"caf\x82.txt".to_vec()),
+            "METADATA".into(),
+        ]);
+
+        assert_eq!(file.entry_index("METADATA"), Some(0));
+        assert_eq!(file.entry_index("caf\u{e9}.txt"), Some(1));
+        assert_eq!(file.entry_index(b"caf\x82.txt"), Some(1));
+        assert_eq!(file.entry_index("metadata"), None);
+    }
+
+    #[test]
+    fn case_insensitive_lookup() {
+        let file = file(vec!["Foo/METADATA".into(), "\u{c4}B.TXT".into()]);
+        let index = file.name_index(NameMatching::CaseInsensitive);
+
+        assert_eq!(index.get("foo/metadata"), Some(0));
+        assert_eq!(index.get("\u{e4}b.txt"), Some(1));
+        assert_eq!(index.with_prefix("FOO/"), vec![0]);
+    }
+
+    #[test]
+    fn prefix_and_directory_listing() {
+        let names = ["a/", "a/b.txt", "a/c/", "a/c/d.txt", "a/e/f.txt", "ab.txt", "z.txt"];
+        let file = file(names.iter().map(|name| (*name).into()).collect());
+        let index = file.name_index(NameMatching::Exact);
+
+        assert_eq!(index.with_prefix("a/"), vec![0, 1, 2, 3, 4]);
+        assert_eq!(index.directory("a"), vec![1, 2]);
+        assert_eq!(index.directory("a/"), vec![1, 2]);
+        assert_eq!(index.directory(""), vec![0, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn reader_with_entry_by_name() {
+        use crate::base::read::mem::ZipFileReader;
+        use crate::base::write::ZipFileWriter;
+        use crate::error::ZipError;
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+        writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), b"first").await.unwrap();
+        writer.write_entry_whole(ZipEntryBuilder::new("b".into(), Compression::Stored), b"second").await.unwrap();
+        let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+        let mut entry = reader.reader_with_entry_by_name("b").await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, b"second");
+
+        let Err(err) = reader.reader_with_entry_by_name("c").await else {
+            panic!("expected a missing entry to be rejected");
+        };
+        assert!(matches!(err, ZipError::EntryNameNotFound));
+    }
+}
@@ -2,9 +2,15 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod builder;
+pub(crate) mod index;
 
+use crate::base::read::warnings::Warnings;
+use crate::base::read::ReadWarning;
 use crate::{entry::StoredZipEntry, string::ZipString};
 use builder::ZipFileBuilder;
+use index::{NameIndex, NameMatching};
+
+use std::sync::OnceLock;
 
 /// An immutable store of data about a ZIP file.
 #[derive(Clone)]
@@ -12,6 +18,20 @@ pub struct ZipFile {
     pub(crate) entries: Vec<StoredZipEntry>,
     pub(crate) zip64: bool,
     pub(crate) comment: ZipString,
+    pub(crate) prefix_length: u64,
+    /// The offset at which the central directory starts, within the source this file was parsed from.
+    pub(crate) directory_offset: u64,
+    pub(crate) warnings: Warnings,
+    pub(crate) name_indices: NameIndices,
+}
+
+/// The lazily-built name indices of a ZIP file, one per [`NameMatching`] mode.
+#[derive(Clone, Default)]
+pub(crate) struct NameIndices {
+    exact: OnceLock<NameIndex>,
+    case_insensitive: OnceLock<NameIndex>,
+    #[cfg(feature = "unicode-normalization")]
+    normalized: OnceLock<NameIndex>,
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -35,4 +55,42 @@ impl ZipFile {
     pub fn zip64(&self) -> bool {
         self.zip64
     }
+
+    /// Returns the length of the data prepended to this ZIP file (eg. a self-extracting stub) which its offsets didn't
+    /// account for.
+    ///
+    /// This is only inferred when reading with
+    /// [`ReadOptions::allow_prefix()`](crate::base::read::ReadOptions::allow_prefix), and is otherwise zero. Entries'
+    /// offsets have already been corrected by this length.
+    pub fn prefix_length(&self) -> u64 {
+        self.prefix_length
+    }
+
+    /// Returns the warnings collected whilst reading this ZIP file, in the order they were encountered.
+    ///
+    /// Warnings are only collected when reading with [`Strictness::Lenient`](crate::base::read::Strictness::Lenient),
+    /// both whilst parsing the central directory and whilst reading entries (which may be concurrent), so later calls
+    /// may return more warnings.
+    pub fn warnings(&self) -> Vec<ReadWarning> {
+        self.warnings.collected()
+    }
+
+    /// Returns the index of the entry with the provided name, if any.
+    ///
+    /// The name is compared byte-for-byte against both the UTF-8 and raw forms of each entry's name. The first lookup
+    /// builds an index of all names, so subsequent lookups don't scan the entries.
+    pub fn entry_index(&self, name: impl AsRef<[u8]>) -> Option<usize> {
+        self.name_index(NameMatching::Exact).get(name)
+    }
+
+    /// Returns an index of this ZIP file's entries by name, building it on first use.
+    pub fn name_index(&self, matching: NameMatching) -> &NameIndex {
+        let index = match matching {
+            NameMatching::Exact => &self.name_indices.exact,
+            NameMatching::CaseInsensitive => &self.name_indices.case_insensitive,
+            #[cfg(feature = "unicode-normalization")]
+            NameMatching::Normalized => &self.name_indices.normalized,
+        };
+        index.get_or_init(|| NameIndex::new(&self.entries, matching))
+    }
 }
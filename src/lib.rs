@@ -13,6 +13,7 @@
 //! - Various different reading approaches (seek, stream, filesystem, in-memory buffer).
 //! - Support for writing complete data (u8 slices) or stream writing using data descriptors.
 //! - Initial support for ZIP64 reading and writing.
+//! - Support for reading and writing entries encrypted with traditional PKWARE encryption (ZipCrypto) or WinZip AES.
 //! - Aims for reasonable [specification](https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md) compliance.
 //!
 //! ## Installation
@@ -26,12 +27,18 @@
 //! - `full` - Enables all below features.
 //! - `full-wasm` - Enables all below features that are compatible with WASM.
 //! - `tokio` - Enables support for the `tokio` implementation module.
-//! - `tokio-fs` - Enables support for the `tokio::fs` reading module.
+//! - `tokio-fs` - Enables support for the `tokio::fs` reading, extraction, and directory archiving modules.
 //! - `deflate` - Enables support for the Deflate compression method.
 //! - `bzip2` - Enables support for the bzip2 compression method.
 //! - `lzma` - Enables support for the LZMA compression method.
 //! - `zstd` - Enables support for the zstd compression method.
 //! - `xz` - Enables support for the xz compression method.
+//! - `encryption` - Enables writing encrypted entries, seeding their salts and headers from the OS's random number
+//!   generator.
+//! - `aes` - Enables support for WinZip AES encryption (implies `encryption`).
+//! - `mmap` - Enables support for the memory-mapped file reading module.
+//! - `bytes` - Enables support for handing out entries' data as `bytes::Bytes` from the in-memory reader.
+//! - `unicode-normalization` - Enables comparing entry names after Unicode normalization (`NameMatching::Normalized`).
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
@@ -41,6 +48,7 @@ pub mod error;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+pub(crate) mod crypto;
 pub(crate) mod date;
 pub(crate) mod entry;
 pub(crate) mod file;
@@ -53,9 +61,15 @@ pub(crate) mod tests;
 
 pub use crate::spec::attribute::AttributeCompatibility;
 pub use crate::spec::compression::{Compression, DeflateOption};
+pub use crate::spec::encryption::Encryption;
+pub use crate::spec::header::AesStrength;
 
 pub use crate::date::{builder::ZipDateTimeBuilder, ZipDateTime};
 pub use crate::entry::{builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+pub use crate::file::{
+    builder::ZipFileBuilder,
+    index::{NameIndex, NameMatching},
+    ZipFile,
+};
 
 pub use crate::string::{StringEncoding, ZipString};
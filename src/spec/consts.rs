@@ -53,3 +53,17 @@ pub const NON_ZIP64_MAX_NUM_FILES: u16 = 0xFFFF;
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
 pub const DATA_DESCRIPTOR_LENGTH: usize = 12;
 pub const ZIP64_DATA_DESCRIPTOR_LENGTH: usize = 20;
+
+// Split ZIP file constants
+//
+// APPNOTE sections 8.5.3 and 8.5.4
+/// The signature which starts the first segment of a split ZIP file, sharing its value with that of a data descriptor.
+pub const SPANNING_SIGNATURE: u32 = DATA_DESCRIPTOR_SIGNATURE;
+/// The signature which replaces the spanning signature when a split ZIP file only required a single segment ("PK00").
+pub const TEMPORARY_SPANNING_MARKER: u32 = 0x30304b50;
+
+// WinZip AES encryption constants
+//
+// https://www.winzip.com/en/support/aes-encryption/
+/// The compression method recorded in the headers of an AES-encrypted entry.
+pub const AES_COMPRESSION_METHOD: u16 = 99;
@@ -0,0 +1,19 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "aes")]
+use crate::spec::header::AesStrength;
+
+/// An encryption method supported by this crate when writing entries.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encryption {
+    /// Traditional PKWARE encryption.
+    ///
+    /// This is supported by almost all ZIP tooling, but is cryptographically weak and shouldn't be relied upon to
+    /// keep data confidential.
+    ZipCrypto,
+    /// WinZip AES encryption (AE-2) with the provided key strength.
+    #[cfg(feature = "aes")]
+    Aes(AesStrength),
+}
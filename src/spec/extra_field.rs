@@ -2,9 +2,10 @@
 
 use crate::error::{Result as ZipResult, ZipError};
 use crate::spec::header::{
-    ExtraField, HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, UnknownExtraField,
-    Zip64ExtendedInformationExtraField,
+    AesStrength, AesVendorVersion, ExtraField, HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField,
+    UnknownExtraField, WinZipAesExtraField, Zip64ExtendedInformationExtraField,
 };
+use crate::spec::Compression;
 
 use super::consts::NON_ZIP64_MAX_SIZE;
 
@@ -34,6 +35,7 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.as_bytes(),
+            ExtraField::WinZipAes(field) => field.as_bytes(),
             ExtraField::Unknown(field) => field.as_bytes(),
         }
     }
@@ -43,6 +45,7 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.count_bytes(),
+            ExtraField::WinZipAes(field) => field.count_bytes(),
             ExtraField::Unknown(field) => field.count_bytes(),
         }
     }
@@ -153,10 +156,64 @@ impl ExtraFieldAsBytes for InfoZipUnicodePathExtraField {
     }
 }
 
+impl ExtraFieldAsBytes for WinZipAesExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::WINZIP_AES_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut 7u16.to_le_bytes().to_vec());
+        let vendor_version: u16 = match self.vendor_version {
+            AesVendorVersion::Ae1 => 1,
+            AesVendorVersion::Ae2 => 2,
+        };
+        bytes.append(&mut vendor_version.to_le_bytes().to_vec());
+        bytes.extend_from_slice(b"AE");
+        bytes.push(match self.strength {
+            AesStrength::Aes128 => 1,
+            AesStrength::Aes192 => 2,
+            AesStrength::Aes256 => 3,
+        });
+        bytes.append(&mut u16::from(self.compression).to_le_bytes().to_vec());
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        11
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn winzip_aes_field_round_trips() {
+        let field = WinZipAesExtraField {
+            vendor_version: AesVendorVersion::Ae2,
+            strength: AesStrength::Aes256,
+            compression: Compression::Stored,
+        };
+
+        let bytes = field.as_bytes();
+        assert_eq!(field.count_bytes(), bytes.len());
+
+        let parsed = winzip_aes_extra_field_from_bytes(HeaderId::WINZIP_AES_EXTRA_FIELD, &bytes[4..]).unwrap();
+        assert_eq!(parsed.vendor_version, AesVendorVersion::Ae2);
+        assert_eq!(parsed.strength, AesStrength::Aes256);
+        assert_eq!(parsed.compression, Compression::Stored);
+    }
+
+    #[test]
+    fn winzip_aes_field_rejects_unknown_strength() {
+        let data = [2, 0, b'A', b'E', 4, 0, 0];
+
+        assert!(matches!(
+            winzip_aes_extra_field_from_bytes(HeaderId::WINZIP_AES_EXTRA_FIELD, &data),
+            Err(ZipError::InvalidWinZipAesExtraField)
+        ));
+    }
+
     #[test]
     fn zip64_disk_start_number_size_matches_serialized_bytes() {
         let field = Zip64ExtendedInformationExtraField {
@@ -303,6 +360,29 @@ fn info_zip_unicode_path_extra_field_from_bytes(
     }
 }
 
+/// Parse a WinZip AES extra field from bytes.
+/// The content of "data" should exclude the header.
+fn winzip_aes_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> ZipResult<WinZipAesExtraField> {
+    if data.len() != 7 || &data[2..4] != b"AE" {
+        return Err(ZipError::InvalidWinZipAesExtraField);
+    }
+
+    let vendor_version = match u16::from_le_bytes(data[0..2].try_into().unwrap()) {
+        1 => AesVendorVersion::Ae1,
+        2 => AesVendorVersion::Ae2,
+        _ => return Err(ZipError::InvalidWinZipAesExtraField),
+    };
+    let strength = match data[4] {
+        1 => AesStrength::Aes128,
+        2 => AesStrength::Aes192,
+        3 => AesStrength::Aes256,
+        _ => return Err(ZipError::InvalidWinZipAesExtraField),
+    };
+    let compression = Compression::try_from(u16::from_le_bytes(data[5..7].try_into().unwrap()))?;
+
+    Ok(WinZipAesExtraField { vendor_version, strength, compression })
+}
+
 pub(crate) fn extra_field_from_bytes(
     header_id: HeaderId,
     data_size: u16,
@@ -329,6 +409,9 @@ pub(crate) fn extra_field_from_bytes(
         HeaderId::INFO_ZIP_UNICODE_PATH_EXTRA_FIELD => Ok(ExtraField::InfoZipUnicodePath(
             info_zip_unicode_path_extra_field_from_bytes(header_id, data_size, data)?,
         )),
+        HeaderId::WINZIP_AES_EXTRA_FIELD => {
+            Ok(ExtraField::WinZipAes(winzip_aes_extra_field_from_bytes(header_id, data)?))
+        }
         _ => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
     }
 }
@@ -1,6 +1,8 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::spec::Compression;
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#437
 pub struct LocalFileHeader {
     pub version: u16,
@@ -34,6 +36,7 @@ impl HeaderId {
     pub const ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD: HeaderId = HeaderId(0x0001);
     pub const INFO_ZIP_UNICODE_COMMENT_EXTRA_FIELD: HeaderId = HeaderId(0x6375);
     pub const INFO_ZIP_UNICODE_PATH_EXTRA_FIELD: HeaderId = HeaderId(0x7075);
+    pub const WINZIP_AES_EXTRA_FIELD: HeaderId = HeaderId(0x9901);
 }
 
 impl From<u16> for HeaderId {
@@ -56,6 +59,7 @@ pub enum ExtraField {
     Zip64ExtendedInformation(Zip64ExtendedInformationExtraField),
     InfoZipUnicodeComment(InfoZipUnicodeCommentExtraField),
     InfoZipUnicodePath(InfoZipUnicodePathExtraField),
+    WinZipAes(WinZipAesExtraField),
     Unknown(UnknownExtraField),
 }
 
@@ -66,6 +70,7 @@ impl ExtraField {
             ExtraField::Zip64ExtendedInformation(..) => HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD,
             ExtraField::InfoZipUnicodeComment(..) => HeaderId::INFO_ZIP_UNICODE_COMMENT_EXTRA_FIELD,
             ExtraField::InfoZipUnicodePath(..) => HeaderId::INFO_ZIP_UNICODE_PATH_EXTRA_FIELD,
+            ExtraField::WinZipAes(..) => HeaderId::WINZIP_AES_EXTRA_FIELD,
             ExtraField::Unknown(field) => field.header_id,
         }
     }
@@ -108,6 +113,54 @@ pub enum InfoZipUnicodePathExtraField {
     Unknown { version: u8, data: Vec<u8> },
 }
 
+/// Stores the WinZip AES encryption parameters of an entry, alongside the compression method actually used for its
+/// data (as the headers record the AES-specific method 99 instead).
+/// https://www.winzip.com/en/support/aes-encryption/
+#[derive(Clone, Debug)]
+pub struct WinZipAesExtraField {
+    pub vendor_version: AesVendorVersion,
+    pub strength: AesStrength,
+    pub compression: Compression,
+}
+
+/// The vendor version of a WinZip AES-encrypted entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AesVendorVersion {
+    /// AE-1, where the entry's CRC32 is stored and should be checked.
+    Ae1,
+    /// AE-2, where the entry's CRC32 is omitted in favour of the authentication code.
+    Ae2,
+}
+
+/// The key strength of a WinZip AES-encrypted entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AesStrength {
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+#[cfg(feature = "aes")]
+impl AesStrength {
+    /// Returns the length of the salt which precedes the entry's encrypted data.
+    pub(crate) fn salt_length(self) -> usize {
+        match self {
+            AesStrength::Aes128 => 8,
+            AesStrength::Aes192 => 12,
+            AesStrength::Aes256 => 16,
+        }
+    }
+
+    /// Returns the length of the encryption and authentication keys.
+    pub(crate) fn key_length(self) -> usize {
+        match self {
+            AesStrength::Aes128 => 16,
+            AesStrength::Aes192 => 24,
+            AesStrength::Aes256 => 32,
+        }
+    }
+}
+
 /// Represents any unparsed extra field.
 #[derive(Clone, Debug)]
 pub struct UnknownExtraField {
@@ -5,6 +5,7 @@ pub(crate) mod attribute;
 pub(crate) mod compression;
 pub(crate) mod consts;
 pub(crate) mod data_descriptor;
+pub(crate) mod encryption;
 pub(crate) mod extra_field;
 pub(crate) mod header;
 pub(crate) mod parse;
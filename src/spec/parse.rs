@@ -7,7 +7,7 @@ use crate::spec::header::{
     CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader,
     Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
 };
-use crate::spec::version::validate_extract_version;
+use crate::spec::version::{validate_compression_version, validate_supported_extract_version};
 
 use futures_lite::io::{AsyncRead, AsyncReadExt};
 
@@ -62,9 +62,6 @@ fn validate_general_purpose_flags(flags: GeneralPurposeFlag) -> Result<()> {
     if flags.strong_encryption {
         return Err(ZipError::FeatureNotSupported("strong encryption"));
     }
-    if flags.encrypted {
-        return Err(ZipError::FeatureNotSupported("encryption"));
-    }
     if flags.compressed_patched {
         return Err(ZipError::FeatureNotSupported("compressed patched data"));
     }
@@ -221,11 +218,21 @@ impl From<[u8; 16]> for DataDescriptor {
 
 impl LocalFileHeader {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
+        let header = Self::from_reader_without_compression_check(reader).await?;
+        validate_compression_version(header.version, header.compression)?;
+        Ok(header)
+    }
+
+    /// Reads a header without validating its extraction version against its compression method, leaving that to
+    /// the caller via [`validate_compression_version()`].
+    pub(crate) async fn from_reader_without_compression_check<R: AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<LocalFileHeader> {
         let mut buffer: [u8; 26] = [0; 26];
         reader.read_exact(&mut buffer).await?;
         let header = LocalFileHeader::from(buffer);
         validate_general_purpose_flags(header.flags)?;
-        validate_extract_version(header.version, header.compression)?;
+        validate_supported_extract_version(header.version)?;
         Ok(header)
     }
 }
@@ -240,11 +247,21 @@ impl EndOfCentralDirectoryHeader {
 
 impl CentralDirectoryRecord {
     pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<CentralDirectoryRecord> {
+        let header = Self::from_reader_without_compression_check(reader).await?;
+        validate_compression_version(header.v_needed, header.compression)?;
+        Ok(header)
+    }
+
+    /// Reads a record without validating its extraction version against its compression method, leaving that to
+    /// the caller via [`validate_compression_version()`].
+    pub(crate) async fn from_reader_without_compression_check<R: AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<CentralDirectoryRecord> {
         let mut buffer: [u8; 42] = [0; 42];
         reader.read_exact(&mut buffer).await?;
         let header = CentralDirectoryRecord::from(buffer);
         validate_general_purpose_flags(header.flags)?;
-        validate_extract_version(header.v_needed, header.compression)?;
+        validate_supported_extract_version(header.v_needed)?;
         Ok(header)
     }
 }
@@ -3,11 +3,14 @@
 
 use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
+use crate::spec::encryption::Encryption;
+use crate::spec::header::ExtraField;
 use crate::spec::Compression;
 
 pub(crate) const SPEC_VERSION_MADE_BY: u16 = 63;
 pub(crate) const MAX_SUPPORTED_EXTRACT_VERSION: u16 = 63;
 const DEFAULT_VERSION_NEEDED: u16 = 10;
+pub(crate) const ZIP64_VERSION_NEEDED: u16 = 45;
 
 /// Returns the minimum ZIP specification version required by a compression method.
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#443
@@ -48,15 +51,24 @@ fn validate_deflate64_version(version: u16, compression: u16) -> Result<()> {
     Ok(())
 }
 
-pub(crate) fn validate_extract_version(raw_version: u16, compression: u16) -> Result<()> {
-    // The extraction version occupies the low byte. The high byte is reserved,
-    // but some writers populate it as though this were a "version made by" field.
-    let version = raw_version & 0xff;
-    if version > MAX_SUPPORTED_EXTRACT_VERSION {
+/// Validates that the extraction version is one which this crate supports.
+pub(crate) fn validate_supported_extract_version(raw_version: u16) -> Result<()> {
+    if extract_version(raw_version) > MAX_SUPPORTED_EXTRACT_VERSION {
         return Err(ZipError::FeatureNotSupported("zip file version > 6.3"));
     }
 
-    validate_deflate64_version(version, compression)
+    Ok(())
+}
+
+/// Validates the extraction version against the minimum required by the compression method.
+pub(crate) fn validate_compression_version(raw_version: u16, compression: u16) -> Result<()> {
+    validate_deflate64_version(extract_version(raw_version), compression)
+}
+
+fn extract_version(raw_version: u16) -> u16 {
+    // The extraction version occupies the low byte. The high byte is reserved,
+    // but some writers populate it as though this were a "version made by" field.
+    raw_version & 0xff
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#443
@@ -67,6 +79,19 @@ pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
         version = std::cmp::max(version, 20);
     }
 
+    match entry.encryption.as_ref().map(|encryption| encryption.method) {
+        Some(Encryption::ZipCrypto) => version = std::cmp::max(version, 20),
+        #[cfg(feature = "aes")]
+        Some(Encryption::Aes(_)) => version = std::cmp::max(version, 51),
+        // Entries read from another ZIP file only record whether they're encrypted, with AES-encrypted entries being
+        // distinguished by their WinZip AES extra field.
+        None if entry.encrypted => {
+            let aes = entry.extra_fields.iter().any(|field| matches!(field, ExtraField::WinZipAes(_)));
+            version = std::cmp::max(version, if aes { 51 } else { 20 });
+        }
+        None => (),
+    }
+
     version
 }
 
@@ -0,0 +1,68 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Fixtures shared between the unit tests.
+
+use std::path::{Path, PathBuf};
+
+use futures_lite::io::AsyncWriteExt;
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes a ZIP file holding the provided entries, each via [`ZipFileWriter::write_entry_whole()`].
+pub(crate) async fn write_zip(entries: &[(&str, Compression, &[u8])]) -> Vec<u8> {
+    write_entries(ZipFileWriter::new(Vec::new()), entries).await
+}
+
+/// Writes the provided entries via a configured writer (eg. one which allows duplicate names), then closes it.
+pub(crate) async fn write_entries(
+    mut writer: ZipFileWriter<Vec<u8>>,
+    entries: &[(&str, Compression, &[u8])],
+) -> Vec<u8> {
+    for (name, compression, data) in entries {
+        writer.write_entry_whole(ZipEntryBuilder::new((*name).into(), *compression), data).await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+/// Writes an entry via [`ZipFileWriter::write_entry_stream()`], so that it's followed by a data descriptor.
+pub(crate) async fn write_streamed(
+    writer: &mut ZipFileWriter<Vec<u8>>,
+    name: &str,
+    compression: Compression,
+    data: &[u8],
+) {
+    let mut entry_writer = writer.write_entry_stream(ZipEntryBuilder::new(name.into(), compression)).await.unwrap();
+    entry_writer.write_all(data).await.unwrap();
+    entry_writer.close().await.unwrap();
+}
+
+/// Returns the offset of every occurrence of a (little-endian) signature within the data, in order.
+pub(crate) fn positions(data: &[u8], signature: u32) -> Vec<usize> {
+    let signature = signature.to_le_bytes();
+    data.windows(4).enumerate().filter(|(_, window)| *window == signature).map(|(offset, _)| offset).collect()
+}
+
+/// A temporary directory, which is removed (alongside everything within it) once dropped.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates an empty directory, named after the provided name and the current process.
+    pub(crate) fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("async_zip_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
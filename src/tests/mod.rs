@@ -2,6 +2,7 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod combined;
+pub(crate) mod common;
 pub(crate) mod read;
 pub(crate) mod spec;
 pub(crate) mod write;
@@ -540,17 +540,19 @@ async fn test_each_local_zip64_size_must_have_an_override() {
     }
 }
 
+#[cfg(feature = "deflate")]
 #[tokio::test]
-async fn test_central_directory_encryption_is_rejected() {
+async fn test_central_directory_encryption_mismatch_is_rejected() {
     use crate::base::read::mem::ZipFileReader;
     use crate::error::ZipError;
 
     let data = include_bytes!("diff-085-sample.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
 
-    let Err(err) = ZipFileReader::new(data).await else {
-        panic!("expected central-directory encryption to be rejected");
+    let Err(err) = reader.reader_without_entry(0).await else {
+        panic!("expected central-directory encryption mismatch to be rejected");
     };
-    assert!(matches!(err, ZipError::FeatureNotSupported("encryption")));
+    assert!(matches!(err, ZipError::LocalFileHeaderEncryptionMismatch));
 }
 
 #[tokio::test]
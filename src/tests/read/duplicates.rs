@@ -0,0 +1,146 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::Cursor;
+
+use crate::base::read::cd::{CentralDirectoryReader, Entry};
+use crate::base::read::{mem, DuplicateNames, ReadOptions};
+use crate::base::write::{self, ZipFileWriter};
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::tests::common::{positions, write_entries};
+use crate::{Compression, NameMatching, ZipEntryBuilder};
+
+/// Writes a ZIP file holding an entry for each name (which may repeat), containing the name itself.
+async fn write_names(names: &[&str]) -> Vec<u8> {
+    let entries: Vec<_> = names.iter().map(|name| (*name, Compression::Stored, name.as_bytes())).collect();
+    write_entries(ZipFileWriter::new(Vec::new()).allow_duplicate_names(), &entries).await
+}
+
+/// Reads the names of an archive's entries, comparing names case-insensitively when checking for duplicates.
+async fn read_names(data: Vec<u8>, duplicate_names: DuplicateNames) -> Result<Vec<String>, ZipError> {
+    let options =
+        ReadOptions::new().duplicate_names(duplicate_names).duplicate_name_matching(NameMatching::CaseInsensitive);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await?;
+    Ok(reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap().to_string()).collect())
+}
+
+#[tokio::test]
+async fn writer_rejects_exact_duplicate_names_by_default() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"").await.unwrap();
+    let result = writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"").await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "a.txt"));
+    writer.write_entry_whole(ZipEntryBuilder::new("A.TXT".into(), Compression::Stored), b"").await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    assert_eq!(read_names(data, DuplicateNames::Allow).await.unwrap(), ["a.txt", "A.TXT"]);
+}
+
+#[tokio::test]
+async fn writer_rejects_duplicate_names_with_matching() {
+    let duplicate_names = write::DuplicateNames::Reject(NameMatching::CaseInsensitive);
+    let mut writer = ZipFileWriter::new(Vec::new()).duplicate_names(duplicate_names);
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"").await.unwrap();
+    let result = writer.write_entry_whole(ZipEntryBuilder::new("A.TXT".into(), Compression::Stored), b"").await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "A.TXT"));
+
+    let data = writer.close().await.unwrap();
+    assert_eq!(read_names(data, DuplicateNames::Reject).await.unwrap(), ["a.txt"]);
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[tokio::test]
+async fn writer_rejects_normalized_duplicate_names() {
+    let duplicate_names = write::DuplicateNames::Reject(NameMatching::Normalized);
+    let mut writer = ZipFileWriter::new(Vec::new()).duplicate_names(duplicate_names);
+
+    // A precomposed `é` and an `e` followed by a combining acute accent.
+    writer.write_entry_whole(ZipEntryBuilder::new("caf\u{e9}".into(), Compression::Stored), b"").await.unwrap();
+    let result = writer.write_entry_stream(ZipEntryBuilder::new("cafe\u{301}".into(), Compression::Stored)).await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(_))));
+}
+
+#[tokio::test]
+async fn writer_only_records_names_of_written_entries() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    // An entry writer which isn't closed doesn't write its entry.
+    drop(writer.write_entry_stream(ZipEntryBuilder::new("stream".into(), Compression::Stored)).await.unwrap());
+    writer.write_entry_whole(ZipEntryBuilder::new("stream".into(), Compression::Stored), b"").await.unwrap();
+
+    // Copying an entry fails before anything is written if its source is invalid.
+    let source = mem::ZipFileReader::new(write_names(&["copied"]).await).await.unwrap();
+    let result = writer.copy_raw_entry(&source.file().entries()[0], Cursor::new(vec![0; 64])).await;
+    assert!(result.is_err());
+    writer.write_entry_whole(ZipEntryBuilder::new("copied".into(), Compression::Stored), b"").await.unwrap();
+}
+
+#[tokio::test]
+async fn writer_allows_duplicate_names_when_requested() {
+    let data = write_names(&["a.txt", "a.txt"]).await;
+    assert_eq!(read_names(data, DuplicateNames::Allow).await.unwrap(), ["a.txt", "a.txt"]);
+}
+
+#[tokio::test]
+async fn reader_duplicate_name_policies() {
+    let data = write_names(&["a.txt", "b", "A.TXT"]).await;
+
+    assert_eq!(read_names(data.clone(), DuplicateNames::Allow).await.unwrap(), ["a.txt", "b", "A.TXT"]);
+    assert_eq!(read_names(data.clone(), DuplicateNames::KeepFirst).await.unwrap(), ["a.txt", "b"]);
+    assert_eq!(read_names(data.clone(), DuplicateNames::KeepLast).await.unwrap(), ["b", "A.TXT"]);
+
+    let result = read_names(data, DuplicateNames::Reject).await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "A.TXT"));
+}
+
+#[tokio::test]
+async fn reader_matches_names_exactly_by_default() {
+    let data = write_names(&["a.txt", "A.TXT", "a.txt"]).await;
+    let options = ReadOptions::new().duplicate_names(DuplicateNames::KeepFirst);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(names, ["a.txt", "A.TXT"]);
+}
+
+#[tokio::test]
+async fn reader_kept_entries_remain_readable() {
+    let data = write_names(&["a.txt", "A.TXT"]).await;
+    let options = ReadOptions::new()
+        .duplicate_names(DuplicateNames::KeepLast)
+        .duplicate_name_matching(NameMatching::CaseInsensitive);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"A.TXT");
+}
+
+async fn central_directory_names(data: &[u8], duplicate_names: DuplicateNames) -> Result<Vec<String>, ZipError> {
+    let offset = positions(data, CDH_SIGNATURE)[0];
+    let mut cursor = Cursor::new(&data[offset + 4..]);
+    let mut reader = CentralDirectoryReader::new(&mut cursor, offset as u64)
+        .with_duplicate_names(duplicate_names)
+        .with_duplicate_name_matching(NameMatching::CaseInsensitive);
+
+    let mut names = Vec::new();
+    while let Entry::CentralDirectoryEntry(entry) = reader.next().await? {
+        names.push(entry.filename.as_str().unwrap().to_string());
+    }
+    Ok(names)
+}
+
+#[tokio::test]
+async fn central_directory_reader_duplicate_name_policies() {
+    let data = write_names(&["a.txt", "b", "A.TXT"]).await;
+
+    assert_eq!(central_directory_names(&data, DuplicateNames::Allow).await.unwrap(), ["a.txt", "b", "A.TXT"]);
+    assert_eq!(central_directory_names(&data, DuplicateNames::KeepFirst).await.unwrap(), ["a.txt", "b"]);
+
+    let result = central_directory_names(&data, DuplicateNames::Reject).await;
+    assert!(matches!(result, Err(ZipError::DuplicateEntryName(name)) if name == "A.TXT"));
+    let result = central_directory_names(&data, DuplicateNames::KeepLast).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+
+const STORED_CONTENT: &[u8] = b"stored data\n";
+
+fn locally_encrypted_archive() -> Vec<u8> {
+    let mut data = include_bytes!("../cd/diff-085-sample.zip").to_vec();
+    let central_directory = data
+        .windows(4)
+        .position(|window| window == [0x50, 0x4b, 0x01, 0x02])
+        .expect("fixture should contain a central directory record");
+
+    // Keep the fixture usable without optional compression features: neither test
+    // reads the entry body, so Stored is sufficient to exercise header parsing.
+    data[8..10].copy_from_slice(&0u16.to_le_bytes());
+    data[central_directory + 10..central_directory + 12].copy_from_slice(&0u16.to_le_bytes());
+    data[6] |= 1;
+    data[central_directory + 8] &= !1;
+    data
+}
+
+#[tokio::test]
+async fn test_streaming_reader_requires_password_for_local_header_encryption() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let data = locally_encrypted_archive();
+    let reader = ZipFileReader::new(data.as_slice());
+
+    let Err(err) = reader.next_without_entry().await else {
+        panic!("expected an encrypted entry without a password to be rejected");
+    };
+    assert!(matches!(err, ZipError::PasswordRequired));
+}
+
+#[tokio::test]
+async fn test_seekable_reader_rejects_local_header_encryption_mismatch() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = locally_encrypted_archive();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    let Err(err) = reader.reader_without_entry(0).await else {
+        panic!("expected local-header encryption to be rejected");
+    };
+    assert!(matches!(err, ZipError::LocalFileHeaderEncryptionMismatch));
+}
+
+#[tokio::test]
+async fn test_zipcrypto_stored_archive_password() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("zipcrypto-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+    assert!(reader.file().entries()[0].encrypted());
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, STORED_CONTENT);
+}
+
+#[tokio::test]
+async fn test_zipcrypto_stored_entry_password() {
+    use crate::base::read::seek::ZipFileReader;
+    use futures_lite::io::Cursor;
+
+    let data = include_bytes!("zipcrypto-stored.zip").to_vec();
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+
+    let mut entry = reader.reader_with_entry_with_password(0, b"secret").await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, STORED_CONTENT);
+}
+
+#[tokio::test]
+async fn test_zipcrypto_missing_password() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("zipcrypto-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let Err(err) = reader.reader_without_entry(0).await else {
+        panic!("expected an encrypted entry without a password to be rejected");
+    };
+    assert!(matches!(err, ZipError::PasswordRequired));
+}
+
+#[tokio::test]
+async fn test_zipcrypto_incorrect_password() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("zipcrypto-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    let Err(err) = reader.reader_without_entry_with_password(0, b"not the password").await else {
+        panic!("expected an incorrect password to be rejected");
+    };
+    assert!(matches!(err, ZipError::IncorrectPassword));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn test_zipcrypto_deflate_archive() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("zipcrypto-deflate.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = String::new();
+    entry.read_to_string_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, "Some plaintext content that should survive encryption.\n".repeat(20));
+
+    let mut entry = reader.reader_with_entry(1).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, STORED_CONTENT);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn test_zipcrypto_streaming_reader_with_data_descriptor() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let data = include_bytes!("zipcrypto-streamed.zip");
+    let reader = ZipFileReader::new(&data[..]).with_password("secret");
+
+    let mut entry = reader.next_with_entry().await.unwrap().expect("archive should contain an entry");
+    assert!(entry.reader().entry().data_descriptor());
+
+    let mut buffer = String::new();
+    entry.reader_mut().read_to_string_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, "Some plaintext content that should survive encryption.\n".repeat(20));
+
+    let (descriptor, reader) = entry.done().await.unwrap();
+    assert!(descriptor.is_some());
+    assert!(reader.next_without_entry().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_aes_extra_field_surfaces_compression() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::spec::header::{AesStrength, AesVendorVersion, ExtraField};
+    use crate::Compression;
+
+    let data = include_bytes!("aes-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let entry = &reader.file().entries()[1];
+    assert!(entry.encrypted());
+    assert_eq!(entry.compression(), Compression::Stored);
+
+    let Some(ExtraField::WinZipAes(field)) = entry.extra_fields().first() else {
+        panic!("expected a WinZip AES extra field");
+    };
+    assert_eq!(field.vendor_version, AesVendorVersion::Ae2);
+    assert_eq!(field.strength, AesStrength::Aes256);
+}
+
+#[tokio::test]
+async fn test_aes_without_extra_field() {
+    use crate::base::read::mem::ZipFileReader;
+
+    // Rename every WinZip AES extra field (0x9901), leaving the AES compression method without one.
+    let mut data = include_bytes!("aes-stored.zip").to_vec();
+    let offsets: Vec<_> =
+        data.windows(4).enumerate().filter(|(_, w)| *w == [0x01, 0x99, 0x07, 0x00]).map(|(i, _)| i).collect();
+    assert!(!offsets.is_empty());
+    for offset in offsets {
+        data[offset + 1] = 0x98;
+    }
+
+    let Err(err) = ZipFileReader::new(data).await else {
+        panic!("expected an AES entry without its extra field to be rejected");
+    };
+    assert!(matches!(err, ZipError::MissingWinZipAesExtraField));
+}
+
+#[cfg(not(feature = "aes"))]
+#[tokio::test]
+async fn test_aes_requires_feature() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("aes-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    let Err(err) = reader.reader_without_entry(0).await else {
+        panic!("expected an AES-encrypted entry to be rejected without the aes feature");
+    };
+    assert!(matches!(err, ZipError::FeatureNotSupported(_)));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn test_aes_stored_archive() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("aes-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    // AE-1 with AES-128, followed by AE-2 with AES-256.
+    for index in 0..2 {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, STORED_CONTENT);
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn test_aes_incorrect_password() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let data = include_bytes!("aes-stored.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let Err(err) = reader.reader_without_entry(0).await else {
+        panic!("expected an encrypted entry without a password to be rejected");
+    };
+    assert!(matches!(err, ZipError::PasswordRequired));
+
+    let Err(err) = reader.reader_without_entry_with_password(0, b"not the password").await else {
+        panic!("expected an incorrect password to be rejected");
+    };
+    assert!(matches!(err, ZipError::IncorrectPassword));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn test_aes_tampered_data() {
+    use crate::base::read::mem::ZipFileReader;
+
+    // The first entry's data follows its 30-byte header, 10-byte name, 11-byte extra field, 8-byte salt, and 2-byte
+    // password verifier.
+    let mut data = include_bytes!("aes-stored.zip").to_vec();
+    data[61] ^= 1;
+    let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    let err = entry.read_to_end_checked(&mut buffer).await.unwrap_err();
+    assert!(matches!(err, ZipError::AuthenticationCodeMismatch));
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn test_aes_deflate_archive() {
+    use crate::base::read::seek::ZipFileReader;
+    use futures_lite::io::Cursor;
+
+    let data = include_bytes!("aes-deflate.zip").to_vec();
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+
+    // AE-2 with AES-256, followed by AE-1 with AES-192.
+    for index in 0..2 {
+        let mut entry = reader.reader_with_entry_with_password(index, b"secret").await.unwrap();
+        let mut buffer = String::new();
+        entry.read_to_string_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, "Some plaintext content that should survive encryption.\n".repeat(20));
+    }
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn test_aes_streaming_reader_with_data_descriptor() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let data = include_bytes!("aes-streamed.zip");
+    let reader = ZipFileReader::new(&data[..]).with_password("secret");
+
+    let mut entry = reader.next_with_entry().await.unwrap().expect("archive should contain an entry");
+    assert!(entry.reader().entry().data_descriptor());
+
+    let mut buffer = String::new();
+    entry.reader_mut().read_to_string_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, "Some plaintext content that should survive encryption.\n".repeat(20));
+
+    let (descriptor, reader) = entry.done().await.unwrap();
+    assert!(descriptor.is_some());
+    assert!(reader.next_without_entry().await.unwrap().is_none());
+}
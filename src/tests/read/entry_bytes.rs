@@ -0,0 +1,71 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::base::read::mem::ZipFileReader;
+use crate::error::ZipError;
+use crate::tests::common::write_zip;
+use crate::Compression;
+
+const STORED: &[u8] = b"stored data";
+
+const ENTRIES: &[(&str, Compression, &[u8])] = &[
+    ("stored", Compression::Stored, STORED),
+    #[cfg(feature = "deflate")]
+    ("deflated", Compression::Deflate, &[b'a'; 1024]),
+];
+
+#[tokio::test]
+async fn stored_entries_are_borrowed() {
+    let reader = ZipFileReader::new(write_zip(ENTRIES).await).await.unwrap();
+
+    let data = reader.entry_bytes_checked(0).await.unwrap();
+    assert!(matches!(data, Cow::Borrowed(_)));
+    assert_eq!(&*data, STORED);
+    assert!(reader.data().as_ptr_range().contains(&data.as_ptr()));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn compressed_entries_are_decompressed() {
+    let reader = ZipFileReader::new(write_zip(ENTRIES).await).await.unwrap();
+
+    let data = reader.entry_bytes_checked(1).await.unwrap();
+    assert!(matches!(data, Cow::Owned(_)));
+    assert_eq!(&*data, &[b'a'; 1024]);
+}
+
+#[tokio::test]
+async fn crc_is_only_checked_when_requested() {
+    let mut zip = write_zip(ENTRIES).await;
+    let offset = zip.windows(STORED.len()).position(|window| window == STORED).unwrap();
+    zip[offset] ^= 0xFF;
+    let reader = ZipFileReader::new(zip).await.unwrap();
+
+    assert_eq!(reader.entry_bytes(0).await.unwrap()[1..], STORED[1..]);
+    assert!(matches!(reader.entry_bytes_checked(0).await, Err(ZipError::CRC32CheckError)));
+}
+
+#[tokio::test]
+async fn shared_buffers_are_accepted() {
+    let zip: Arc<[u8]> = write_zip(ENTRIES).await.into();
+    let reader = ZipFileReader::new(zip.clone()).await.unwrap();
+
+    assert_eq!(&*reader.entry_bytes(0).await.unwrap(), STORED);
+    assert!(reader.clone().test_all().await.is_ok());
+    assert_eq!(reader.data().as_ptr(), zip.as_ptr());
+}
+
+#[cfg(feature = "bytes")]
+#[tokio::test]
+async fn bytes_are_shared_without_copying() {
+    let zip = bytes::Bytes::from(write_zip(ENTRIES).await);
+    let reader = ZipFileReader::new(zip.clone()).await.unwrap();
+
+    let data = reader.entry_bytes_shared_checked(0).await.unwrap();
+    assert_eq!(data, STORED);
+    assert!(zip.as_ptr_range().contains(&data.as_ptr()));
+    assert!(matches!(reader.entry_bytes_shared(ENTRIES.len()).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{BufReader, Cursor};
+
+use crate::base::read::{mem, seek, TestReport};
+use crate::error::ZipError;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::tests::common::{positions, write_zip};
+use crate::Compression;
+
+const ENTRIES: &[(&str, Compression, &[u8])] =
+    &[("a", Compression::Stored, b"first"), ("b", Compression::Stored, b"other"), ("c", Compression::Stored, b"third")];
+
+fn failures(report: &TestReport) -> Vec<(usize, String)> {
+    report.failures().iter().map(|failure| (failure.index(), failure.error().to_string())).collect()
+}
+
+#[tokio::test]
+async fn intact_archive() {
+    let data = write_zip(ENTRIES).await;
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let report = reader.test_all().await;
+    assert!(report.is_ok());
+    assert_eq!(report.tested(), 3);
+    assert!(reader.test_all_concurrently(2).await.is_ok());
+
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    assert!(reader.test_all().await.is_ok());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn intact_deflate_archive() {
+    let data = write_zip(&[("a", Compression::Deflate, &[7; 256 * 1024]), ("b", Compression::Deflate, b"")]).await;
+
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+    assert!(reader.test_all().await.is_ok());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn trailing_compressed_data_is_reported() {
+    use crate::spec::consts::EOCDR_SIGNATURE;
+
+    let mut data = write_zip(&[("a", Compression::Deflate, &[7; 1024])]).await;
+
+    // Append a byte to the entry's compressed data, which the deflate stream ends before.
+    let cdh = positions(&data, CDH_SIGNATURE)[0];
+    data.insert(cdh, 0);
+    let (lfh, cdh, eocdr) = (0, cdh + 1, positions(&data, EOCDR_SIGNATURE)[0]);
+    for field in [lfh + 18, cdh + 20] {
+        let size = u32::from_le_bytes(data[field..field + 4].try_into().unwrap()) + 1;
+        data[field..field + 4].copy_from_slice(&size.to_le_bytes());
+    }
+    data[eocdr + 16..eocdr + 20].copy_from_slice(&(cdh as u32).to_le_bytes());
+
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+    let report = reader.test_all().await;
+    let [failure] = report.failures() else {
+        panic!("expected a single failure, found {:?}", report.failures());
+    };
+    assert!(matches!(failure.error(), ZipError::CompressedSizeMismatch(_)));
+}
+
+#[tokio::test]
+async fn failures_are_reported_per_entry() {
+    let mut data = write_zip(ENTRIES).await;
+    let lfh = positions(&data, LFH_SIGNATURE);
+    let cdh = positions(&data, CDH_SIGNATURE);
+
+    // Corrupt the first entry's data, and overstate the last entry's uncompressed size.
+    data[lfh[0] + 31] ^= 0xff;
+    data[lfh[2] + 22..lfh[2] + 26].copy_from_slice(&6u32.to_le_bytes());
+    data[cdh[2] + 24..cdh[2] + 28].copy_from_slice(&6u32.to_le_bytes());
+
+    let expected = [
+        (0, ZipError::CRC32CheckError.to_string()),
+        (2, ZipError::UncompressedSizeMismatch { expected: 6, actual: 5 }.to_string()),
+    ];
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let report = reader.test_all_concurrently(3).await;
+    assert_eq!(report.tested(), 3);
+    assert_eq!(failures(&report), expected);
+
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    assert_eq!(failures(&reader.test_all().await), expected);
+}
+
+#[tokio::test]
+async fn structural_failures_are_reported() {
+    let mut data = write_zip(ENTRIES).await;
+    let lfh = positions(&data, LFH_SIGNATURE);
+    data[lfh[1] + 30] = b'x';
+
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+    let report = reader.test_all().await;
+    let [failure] = report.failures() else {
+        panic!("expected a single failure, found {:?}", report.failures());
+    };
+    assert_eq!(failure.index(), 1);
+    assert!(matches!(failure.error(), ZipError::LocalFileHeaderNameMismatch));
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn fs_reader_tests_concurrently() {
+    use crate::tests::common::TempDir;
+    use crate::tokio::read::fs;
+
+    let temp = TempDir::new("integrity");
+    let path = temp.path().join("archive.zip");
+    std::fs::write(&path, write_zip(ENTRIES).await).unwrap();
+
+    let reader = fs::ZipFileReader::new(&path).await.unwrap();
+    let report = tokio::spawn(async move { reader.test_all_concurrently(4).await }).await.unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(report.tested(), 3);
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{BufReader, Cursor};
+
+use crate::base::read::{mem, seek, ReadOptions, Strictness};
+use crate::error::ZipError;
+use crate::spec::consts::{CDDS_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::tests::common::{positions, write_zip};
+use crate::Compression;
+
+const ENTRIES: &[(&str, Compression, &[u8])] =
+    &[("a", Compression::Stored, b"first"), ("b", Compression::Stored, b"second")];
+
+fn lenient() -> ReadOptions {
+    ReadOptions::new().strictness(Strictness::Lenient)
+}
+
+#[tokio::test]
+async fn local_header_size_mismatch_is_tolerated() {
+    let mut data = write_zip(ENTRIES).await;
+    let lfh = positions(&data, LFH_SIGNATURE)[0];
+    data[lfh + 18..lfh + 22].copy_from_slice(&7_u32.to_le_bytes());
+    data[lfh + 22..lfh + 26].copy_from_slice(&7_u32.to_le_bytes());
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let Err(err) = reader.reader_with_entry(0).await else {
+        panic!("expected the local header sizes to be rejected");
+    };
+    assert!(matches!(err, ZipError::LocalFileHeaderSizeMismatch));
+
+    let reader = mem::ZipFileReader::new_with_options(data.clone(), lenient()).await.unwrap();
+    assert!(reader.file().warnings().is_empty());
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"first");
+
+    let warnings = reader.file().warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].entry(), Some(0));
+    assert!(matches!(warnings[0].error(), ZipError::LocalFileHeaderSizeMismatch));
+
+    // Reading the entry again doesn't record the same warning again.
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut Vec::new()).await.unwrap();
+    assert_eq!(reader.file().warnings().len(), 1);
+
+    let mut reader = seek::ZipFileReader::new_with_options(BufReader::new(Cursor::new(data)), lenient()).await.unwrap();
+    assert!(reader.test_all().await.is_ok());
+    assert_eq!(reader.file().warnings().len(), 1);
+}
+
+#[tokio::test]
+async fn understated_central_directory_size_is_tolerated() {
+    let mut data = write_zip(ENTRIES).await;
+    let eocdr = *positions(&data, EOCDR_SIGNATURE).last().unwrap();
+    let directory_size = u32::from_le_bytes(data[eocdr + 12..eocdr + 16].try_into().unwrap());
+    data[eocdr + 12..eocdr + 16].copy_from_slice(&(directory_size - 2).to_le_bytes());
+
+    assert!(mem::ZipFileReader::new(data.clone()).await.is_err());
+
+    let reader = mem::ZipFileReader::new_with_options(data, lenient()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+
+    let warnings = reader.file().warnings();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().all(|warning| warning.entry().is_none()));
+    assert!(matches!(
+        warnings[0].error(),
+        ZipError::InvalidCentralDirectorySize { expected, actual } if *expected == *actual - 2
+    ));
+    assert!(matches!(warnings[1].error(), ZipError::InvalidCentralDirectoryBinding { .. }));
+}
+
+#[tokio::test]
+async fn overstated_entry_count_is_rejected() {
+    let mut data = write_zip(ENTRIES).await;
+    let eocdr = *positions(&data, EOCDR_SIGNATURE).last().unwrap();
+    data[eocdr + 8..eocdr + 10].copy_from_slice(&3_u16.to_le_bytes());
+    data[eocdr + 10..eocdr + 12].copy_from_slice(&3_u16.to_le_bytes());
+
+    // The central directory is still bounded, so the missing record isn't searched for beyond the end records.
+    let Err(err) = mem::ZipFileReader::new_with_options(data, lenient()).await else {
+        panic!("expected the entry count to be rejected");
+    };
+    assert!(matches!(err, ZipError::InvalidCentralDirectoryEntryCount { entries: 3 }));
+}
+
+#[tokio::test]
+async fn malformed_digital_signature_is_tolerated() {
+    let mut data = write_zip(ENTRIES).await;
+    let eocdr = *positions(&data, EOCDR_SIGNATURE).last().unwrap();
+    let mut signature_record = CDDS_SIGNATURE.to_le_bytes().to_vec();
+    signature_record.extend_from_slice(&2_u16.to_le_bytes());
+    signature_record.extend_from_slice(b"x");
+    let signature_record_len = signature_record.len();
+    data.splice(eocdr..eocdr, signature_record);
+
+    let eocdr = eocdr + signature_record_len;
+    let directory_size = u32::from_le_bytes(data[eocdr + 12..eocdr + 16].try_into().unwrap());
+    data[eocdr + 12..eocdr + 16].copy_from_slice(&(directory_size + signature_record_len as u32).to_le_bytes());
+
+    assert!(mem::ZipFileReader::new(data.clone()).await.is_err());
+
+    let reader = mem::ZipFileReader::new_with_options(data, lenient()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+
+    let warnings = reader.file().warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0].error(), ZipError::InvalidCentralDirectorySize { .. }));
+    assert_eq!(warnings[0].to_string(), warnings[0].error().to_string());
+}
+
+#[tokio::test]
+async fn intact_archive_has_no_warnings() {
+    let reader = mem::ZipFileReader::new_with_options(write_zip(ENTRIES).await, lenient()).await.unwrap();
+    assert!(reader.test_all().await.is_ok());
+    assert!(reader.file().warnings().is_empty());
+}
+
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn warnings_refer_to_entries_kept_from_duplicates() {
+    use crate::base::read::DuplicateNames;
+    use crate::base::write::ZipFileWriter;
+    use crate::spec::consts::CDH_SIGNATURE;
+    use crate::tests::common::write_entries;
+
+    let entries = [("a", Compression::Stored, b"first".as_slice()), ("a", Compression::Stored, b"second".as_slice())];
+    let mut data = write_entries(ZipFileWriter::new(Vec::new()).allow_duplicate_names(), &entries).await;
+
+    // Mark the second entry as Deflate64 compressed, with a version needed to extract which predates Deflate64.
+    let cdh = positions(&data, CDH_SIGNATURE)[1];
+    data[cdh + 6..cdh + 8].copy_from_slice(&20_u16.to_le_bytes());
+    data[cdh + 10..cdh + 12].copy_from_slice(&9_u16.to_le_bytes());
+
+    let options = lenient().duplicate_names(DuplicateNames::KeepLast);
+    let reader = mem::ZipFileReader::new_with_options(data.clone(), options).await.unwrap();
+    let warnings = reader.file().warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].entry(), Some(0));
+    assert!(matches!(warnings[0].error(), ZipError::InvalidCompressionVersion { .. }));
+
+    // Once the entry is discarded, its warning no longer refers to the entry which took its index.
+    let options = lenient().duplicate_names(DuplicateNames::KeepFirst);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+    let warnings = reader.file().warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].entry(), None);
+}
@@ -0,0 +1,154 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncReadExt, BufReader, Cursor};
+
+use crate::base::read::{mem, seek, stream, ReadLimits, ReadOptions};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::tests::common::{positions, write_streamed, write_zip};
+use crate::{Compression, ZipEntryBuilder};
+
+async fn read_entry(reader: &mem::ZipFileReader, index: usize) -> Result<Vec<u8>, ZipError> {
+    let mut entry = reader.reader_without_entry(index).await?;
+    let mut buffer = Vec::new();
+    entry.read_to_end(&mut buffer).await?;
+    Ok(buffer)
+}
+
+#[tokio::test]
+async fn entry_size_limit() {
+    let data = write_zip(&[("small", Compression::Stored, &[1; 500]), ("large", Compression::Stored, &[1; 501])]).await;
+    let reader = mem::ZipFileReader::new(data).await.unwrap().with_limits(ReadLimits::new().max_entry_size(500));
+
+    assert_eq!(read_entry(&reader, 0).await.unwrap().len(), 500);
+    assert!(matches!(read_entry(&reader, 1).await, Err(ZipError::EntrySizeLimitExceeded(500))));
+}
+
+#[tokio::test]
+async fn total_size_limit_is_shared_between_clones() {
+    let data = write_zip(&[("a", Compression::Stored, &[1; 600]), ("b", Compression::Stored, &[1; 600])]).await;
+    let reader = mem::ZipFileReader::new(data).await.unwrap().with_limits(ReadLimits::new().max_total_size(1000));
+    let cloned = reader.clone();
+
+    assert!(read_entry(&reader, 0).await.is_ok());
+    assert!(matches!(read_entry(&cloned, 1).await, Err(ZipError::TotalSizeLimitExceeded(1000))));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn compression_ratio_limit() {
+    let zeros = vec![0; 4 * 1024 * 1024];
+    let data =
+        write_zip(&[("small", Compression::Deflate, &zeros[..1024]), ("bomb", Compression::Deflate, &zeros)]).await;
+    let reader = mem::ZipFileReader::new(data).await.unwrap().with_limits(ReadLimits::new().max_compression_ratio(100));
+
+    // Small entries are exempt from the check, however well they compress.
+    assert_eq!(read_entry(&reader, 0).await.unwrap().len(), 1024);
+    assert!(matches!(read_entry(&reader, 1).await, Err(ZipError::CompressionRatioLimitExceeded(100))));
+}
+
+#[tokio::test]
+async fn declared_uncompressed_size_is_enforced() {
+    let mut data = write_zip(&[("file", Compression::Stored, &[1; 100])]).await;
+
+    // Understate the uncompressed size in both the local file header and the central directory.
+    let lfh = positions(&data, LFH_SIGNATURE)[0];
+    data[lfh + 22..lfh + 26].copy_from_slice(&50u32.to_le_bytes());
+    let cdh = positions(&data, CDH_SIGNATURE)[0];
+    data[cdh + 24..cdh + 28].copy_from_slice(&50u32.to_le_bytes());
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(matches!(read_entry(&reader, 0).await, Err(ZipError::UncompressedSizeExceeded(50))));
+
+    let reader = stream::ZipFileReader::new(data.as_slice());
+    let mut entry = reader.next_without_entry().await.unwrap().unwrap();
+    let err = entry.reader_mut().read_to_end(&mut Vec::new()).await.unwrap_err();
+    assert!(matches!(ZipError::from(err), ZipError::UncompressedSizeExceeded(50)));
+}
+
+#[tokio::test]
+async fn seek_reader_limits() {
+    let data = write_zip(&[("file", Compression::Stored, &[1; 100])]).await;
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data)))
+        .await
+        .unwrap()
+        .with_limits(ReadLimits::new().max_entry_size(10));
+
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let err = entry.read_to_end(&mut Vec::new()).await.unwrap_err();
+    assert!(matches!(ZipError::from(err), ZipError::EntrySizeLimitExceeded(10)));
+}
+
+#[tokio::test]
+async fn stream_reader_limits_apply_to_data_descriptor_entries() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    write_streamed(&mut writer, "file", Compression::Stored, &[1; 100]).await;
+    let data = writer.close().await.unwrap();
+
+    let reader = stream::ZipFileReader::new(data.as_slice()).with_limits(ReadLimits::new().max_total_size(99));
+    let mut entry = reader.next_without_entry().await.unwrap().unwrap();
+    let err = entry.reader_mut().read_to_end(&mut Vec::new()).await.unwrap_err();
+    assert!(matches!(ZipError::from(err), ZipError::TotalSizeLimitExceeded(99)));
+}
+
+#[tokio::test]
+async fn directory_entry_count_limit() {
+    let data =
+        write_zip(&[("a", Compression::Stored, b""), ("b", Compression::Stored, b""), ("c", Compression::Stored, b"")])
+            .await;
+
+    assert!(mem::ZipFileReader::new_with_options(data.clone(), ReadOptions::new().max_entries(3)).await.is_ok());
+    let result = mem::ZipFileReader::new_with_options(data, ReadOptions::new().max_entries(2)).await;
+    assert!(matches!(result, Err(ZipError::EntryCountLimitExceeded { entries: 3, limit: 2 })));
+}
+
+#[tokio::test]
+async fn directory_filename_limit() {
+    let data = write_zip(&[("aaaa", Compression::Stored, b""), ("bbbb", Compression::Stored, b"")]).await;
+
+    let limits = ReadOptions::new().max_total_filename_size(7);
+    let result = seek::ZipFileReader::new_with_options(BufReader::new(Cursor::new(data.clone())), limits).await;
+    assert!(matches!(result, Err(ZipError::TotalFileNameLimitExceeded(7))));
+
+    #[cfg(feature = "tokio")]
+    {
+        let source = tokio::io::BufReader::new(std::io::Cursor::new(data));
+        let result = seek::ZipFileReader::with_tokio_and_options(source, limits).await;
+        assert!(matches!(result, Err(ZipError::TotalFileNameLimitExceeded(7))));
+    }
+}
+
+#[tokio::test]
+async fn directory_extra_field_limit() {
+    let field = UnknownExtraField { header_id: HeaderId(0xcafe), data_size: 16, content: vec![0; 16] };
+    let builder =
+        ZipEntryBuilder::new("file".into(), Compression::Stored).extra_fields(vec![ExtraField::Unknown(field)]);
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, b"").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let limits = ReadOptions::new().max_extra_field_size(19);
+    let result = mem::ZipFileReader::new_with_options(data, limits).await;
+    assert!(matches!(result, Err(ZipError::ExtraFieldLimitExceeded { length: 20, limit: 19 })));
+}
+
+#[tokio::test]
+async fn directory_comment_limits() {
+    let limits = ReadOptions::new().max_comment_size(4);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("archive".to_string());
+    let data = writer.close().await.unwrap();
+    let result = mem::ZipFileReader::new_with_options(data, limits).await;
+    assert!(matches!(result, Err(ZipError::CommentLimitExceeded { length: 7, limit: 4 })));
+
+    let builder = ZipEntryBuilder::new("file".into(), Compression::Stored).comment("entry".into());
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, b"").await.unwrap();
+    let data = writer.close().await.unwrap();
+    let result = mem::ZipFileReader::new_with_options(data, limits).await;
+    assert!(matches!(result, Err(ZipError::CommentLimitExceeded { length: 5, limit: 4 })));
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use memmap2::Mmap;
+
+use crate::base::read::mmap::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::tests::common::TempDir;
+use crate::{Compression, ZipEntryBuilder};
+
+/// A ZIP file written to a temporary directory, which is removed once dropped.
+struct TempZip(TempDir);
+
+impl TempZip {
+    async fn new(name: &str) -> Self {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        writer
+            .write_entry_whole(ZipEntryBuilder::new("stored".into(), Compression::Stored), b"stored data")
+            .await
+            .unwrap();
+        #[cfg(feature = "deflate")]
+        writer
+            .write_entry_whole(ZipEntryBuilder::new("deflated".into(), Compression::Deflate), &[b'a'; 1024])
+            .await
+            .unwrap();
+
+        let temp = TempDir::new(&format!("mmap_{name}"));
+        std::fs::write(temp.path().join("archive.zip"), writer.close().await.unwrap()).unwrap();
+        Self(temp)
+    }
+
+    async fn open(&self) -> ZipFileReader {
+        let file = std::fs::File::open(self.0.path().join("archive.zip")).unwrap();
+        // SAFETY: The file isn't modified whilst mapped.
+        let mmap = unsafe { Mmap::map(&file).unwrap() };
+        ZipFileReader::from_mmap(mmap).await.unwrap()
+    }
+}
+
+#[tokio::test]
+async fn entries_are_read_from_clones() {
+    let zip = TempZip::new("clones").await;
+    let reader = zip.open().await;
+
+    let handles: Vec<_> = (0..reader.file().entries().len())
+        .map(|index| {
+            let reader = reader.clone();
+            tokio::spawn(async move {
+                let mut data = Vec::new();
+                reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut data).await.unwrap();
+                data
+            })
+        })
+        .collect();
+
+    assert_eq!(handles.len(), if cfg!(feature = "deflate") { 2 } else { 1 });
+    for (index, handle) in handles.into_iter().enumerate() {
+        let expected = if index == 0 { b"stored data".to_vec() } else { vec![b'a'; 1024] };
+        assert_eq!(handle.await.unwrap(), expected);
+    }
+    assert!(reader.test_all().await.is_ok());
+}
+
+#[tokio::test]
+async fn stored_data_is_borrowed_from_the_mapping() {
+    let zip = TempZip::new("stored").await;
+    let reader = zip.open().await;
+
+    let data = reader.stored_data(0).await.unwrap();
+    assert_eq!(data, b"stored data");
+    assert!(reader.data().as_ptr_range().contains(&data.as_ptr()));
+
+    #[cfg(feature = "deflate")]
+    assert!(matches!(reader.stored_data(1).await, Err(ZipError::EntryNotStored)));
+    assert!(matches!(reader.stored_data(2).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
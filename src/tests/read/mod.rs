@@ -3,8 +3,23 @@
 
 pub(crate) mod cd;
 pub(crate) mod compression;
+pub(crate) mod duplicates;
 pub(crate) mod encryption;
+pub(crate) mod entry_bytes;
+pub(crate) mod integrity;
+pub(crate) mod lenient;
+pub(crate) mod limits;
 pub(crate) mod locator;
+#[cfg(feature = "mmap")]
+pub(crate) mod mmap;
+pub(crate) mod nested;
+pub(crate) mod prefix;
+pub(crate) mod range;
+pub(crate) mod raw;
+pub(crate) mod recover;
+pub(crate) mod split;
+pub(crate) mod stored;
 pub(crate) mod stream;
+pub(crate) mod verify;
 pub(crate) mod version;
 pub(crate) mod zip64;
@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::Cursor;
+
+use crate::base::read::nested::NestedOptions;
+use crate::base::read::{mem, seek};
+#[cfg(feature = "deflate")]
+use crate::error::ZipError;
+use crate::tests::common::write_zip;
+use crate::Compression;
+
+async fn write_outer(compression: Compression) -> Vec<u8> {
+    let inner = write_zip(&[("META-INF/MANIFEST.MF", Compression::Stored, b"Manifest-Version: 1.0")]).await;
+    write_zip(&[("index.html", Compression::Stored, b"<html></html>"), ("WEB-INF/lib/inner.jar", compression, &inner)])
+        .await
+}
+
+async fn read_manifest<R>(nested: &mut seek::ZipFileReader<R>) -> String
+where
+    R: futures_lite::io::AsyncBufRead + futures_lite::io::AsyncSeek + Unpin,
+{
+    let mut data = String::new();
+    nested
+        .reader_with_entry_by_name("META-INF/MANIFEST.MF")
+        .await
+        .unwrap()
+        .read_to_string_checked(&mut data)
+        .await
+        .unwrap();
+    data
+}
+
+#[tokio::test]
+async fn stored_archives_are_read_in_place() {
+    let outer = write_outer(Compression::Stored).await;
+    let reader = mem::ZipFileReader::new(outer.clone()).await.unwrap();
+
+    let mut nested = reader.open_nested(1, NestedOptions::new().max_buffered_size(0)).await.unwrap();
+    assert!(!nested.inner_mut().is_buffered());
+    assert_eq!(read_manifest(&mut nested).await, "Manifest-Version: 1.0");
+
+    let mut reader = seek::ZipFileReader::new(Cursor::new(outer)).await.unwrap();
+    let mut nested = reader.open_nested(1, NestedOptions::new()).await.unwrap();
+    assert_eq!(read_manifest(&mut nested).await, "Manifest-Version: 1.0");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn compressed_archives_are_buffered() {
+    let reader = mem::ZipFileReader::new(write_outer(Compression::Deflate).await).await.unwrap();
+
+    let mut nested = reader.open_nested(1, NestedOptions::new()).await.unwrap();
+    assert!(nested.inner_mut().is_buffered());
+    assert_eq!(read_manifest(&mut nested).await, "Manifest-Version: 1.0");
+    assert!(nested.test_all().await.is_ok());
+
+    let size = reader.file().entries()[1].uncompressed_size();
+    let result = reader.open_nested(1, NestedOptions::new().max_buffered_size(size - 1)).await;
+    assert!(matches!(result, Err(ZipError::NestedArchiveLimitExceeded { size: actual, .. }) if actual == size));
+}
+
+#[cfg(all(feature = "deflate", feature = "tokio-fs"))]
+#[tokio::test]
+async fn compressed_archives_are_buffered_in_temp_files() {
+    use crate::tests::common::TempDir;
+
+    let temp = TempDir::new("nested");
+    let dir = temp.path();
+    let reader = mem::ZipFileReader::new(write_outer(Compression::Deflate).await).await.unwrap();
+
+    let mut nested = reader.open_nested(1, NestedOptions::new().temp_dir(dir)).await.unwrap();
+    assert!(nested.inner_mut().is_buffered());
+    assert_eq!(std::fs::read_dir(dir).unwrap().count(), 1);
+    assert_eq!(read_manifest(&mut nested).await, "Manifest-Version: 1.0");
+
+    drop(nested);
+    assert_eq!(std::fs::read_dir(dir).unwrap().count(), 0);
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{BufReader, Cursor};
+
+use crate::base::read::{mem, seek, Finding, ReadOptions};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+const STUB: &[u8] = b"#!/bin/sh\necho 'installing...'\nexit 0\n";
+
+async fn write_prefixed_zip(zip64: bool) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    if zip64 {
+        writer = writer.force_zip64();
+    }
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), b"first").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b".into(), Compression::Stored), b"second").await.unwrap();
+
+    let mut data = STUB.to_vec();
+    data.extend_from_slice(&writer.close().await.unwrap());
+    data
+}
+
+async fn check_prefixed(zip64: bool) {
+    let data = write_prefixed_zip(zip64).await;
+    assert!(mem::ZipFileReader::new(data.clone()).await.is_err());
+
+    let options = ReadOptions::new().allow_prefix(true);
+    let reader = mem::ZipFileReader::new_with_options(data.clone(), options).await.unwrap();
+    assert_eq!(reader.file().zip64(), zip64);
+    assert_eq!(reader.file().prefix_length(), STUB.len() as u64);
+    assert_eq!(reader.file().entries()[0].header_offset(), STUB.len() as u64);
+
+    for (index, expected) in [b"first".as_slice(), b"second"].into_iter().enumerate() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+
+    let mut reader = seek::ZipFileReader::new_with_options(BufReader::new(Cursor::new(data)), options).await.unwrap();
+    assert!(reader.test_all().await.is_ok());
+}
+
+#[tokio::test]
+async fn prefixed_archive() {
+    check_prefixed(false).await;
+}
+
+#[tokio::test]
+async fn prefixed_zip64_archive() {
+    check_prefixed(true).await;
+}
+
+#[tokio::test]
+async fn unprefixed_archive_has_no_prefix_length() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), b"first").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let options = ReadOptions::new().allow_prefix(true);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+    assert_eq!(reader.file().prefix_length(), 0);
+    assert_eq!(reader.file().entries()[0].header_offset(), 0);
+}
+
+#[tokio::test]
+async fn prefix_is_rejected_by_default() {
+    let data = write_prefixed_zip(false).await;
+    let Err(err) = mem::ZipFileReader::new(data).await else {
+        panic!("expected a prefixed archive to be rejected by default");
+    };
+    assert!(matches!(err, ZipError::UnexpectedHeaderError(_, _)));
+}
+
+#[tokio::test]
+async fn prefix_is_reported_by_verify() {
+    let data = write_prefixed_zip(false).await;
+    let reader = mem::ZipFileReader::new_with_options(data, ReadOptions::new().allow_prefix(true)).await.unwrap();
+
+    let report = reader.verify().await.unwrap();
+    let [Finding::UnaccountedBytes { start: 0, end }] = report.findings() else {
+        panic!("expected only the prefix to be reported, found {:?}", report.findings());
+    };
+    assert_eq!(*end, STUB.len() as u64);
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::base::read::range::{RangeReader, ZipFileReader};
+use crate::error::ZipError;
+use crate::tests::common::write_zip;
+use crate::Compression;
+
+/// A range source over an in-memory ZIP file, recording each range requested.
+struct MemorySource {
+    data: Vec<u8>,
+    requests: Mutex<Vec<(u64, u64)>>,
+}
+
+impl MemorySource {
+    fn new(data: Vec<u8>) -> Self {
+        Self { data, requests: Mutex::new(Vec::new()) }
+    }
+
+    fn take_requests(&self) -> Vec<(u64, u64)> {
+        std::mem::take(&mut self.requests.lock().unwrap())
+    }
+}
+
+impl RangeReader for MemorySource {
+    async fn length(&self) -> std::io::Result<u64> {
+        Ok(self.data.len() as u64)
+    }
+
+    async fn read_range(&self, range: Range<u64>) -> std::io::Result<Vec<u8>> {
+        self.requests.lock().unwrap().push((range.start, range.end));
+        Ok(self.data[range.start as usize..range.end as usize].to_vec())
+    }
+}
+
+fn data(index: usize) -> Vec<u8> {
+    format!("data for entry {index}").repeat(index + 1).into_bytes()
+}
+
+async fn write_numbered(entries: usize) -> Vec<u8> {
+    let entries: Vec<_> = (0..entries).map(|index| (format!("entries/{index:05}.txt"), data(index))).collect();
+    write_zip(
+        &entries.iter().map(|(name, data)| (name.as_str(), Compression::Stored, data.as_slice())).collect::<Vec<_>>(),
+    )
+    .await
+}
+
+async fn read_entry(reader: &ZipFileReader<MemorySource>, index: usize) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn entries_are_fetched_on_demand() {
+    let zip = write_numbered(3).await;
+    let length = zip.len() as u64;
+    let reader = ZipFileReader::new(MemorySource::new(zip)).await.unwrap();
+
+    // The whole file fits within the tail, which includes the central directory.
+    assert_eq!(reader.inner().take_requests(), [(0, length)]);
+    assert_eq!(reader.file().entries().len(), 3);
+
+    assert_eq!(read_entry(&reader, 1).await, data(1));
+    let entry = &reader.file().entries()[1];
+    let next = reader.file().entries()[2].header_offset();
+    assert_eq!(reader.inner().take_requests(), [(entry.header_offset(), next)]);
+}
+
+#[tokio::test]
+async fn entries_are_fetched_in_chunks() {
+    let zip = write_numbered(3).await;
+    let reader = ZipFileReader::new(MemorySource::new(zip)).await.unwrap().with_chunk_size(16);
+    reader.inner().take_requests();
+
+    assert_eq!(read_entry(&reader, 2).await, data(2));
+    let requests = reader.inner().take_requests();
+    assert_eq!(requests[0].0, reader.file().entries()[2].header_offset());
+    assert!(requests.len() > data(2).len() / 16);
+    assert!(requests.iter().all(|(start, end)| end - start <= 16));
+    assert!(requests.windows(2).all(|pair| pair[0].1 <= pair[1].0));
+}
+
+#[tokio::test]
+async fn central_directory_is_fetched_in_one_request() {
+    // Enough entries that the central directory extends beyond the tail.
+    let zip = write_numbered(2_000).await;
+    let length = zip.len() as u64;
+    let reader = ZipFileReader::new(MemorySource::new(zip)).await.unwrap();
+
+    let requests = reader.inner().take_requests();
+    let [tail, directory] = &requests[..] else {
+        panic!("expected the tail and central directory to be fetched, but found {requests:?}");
+    };
+    assert_eq!(tail.1, length);
+    assert_eq!(directory.1, tail.0);
+    assert_eq!(reader.file().entries().len(), 2_000);
+
+    assert_eq!(read_entry(&reader, 1_999).await, data(1_999));
+    assert_eq!(reader.inner().take_requests().len(), 1);
+}
+
+#[tokio::test]
+async fn prefetched_entries_are_coalesced() {
+    let zip = write_numbered(5).await;
+    let mut reader = ZipFileReader::new(MemorySource::new(zip)).await.unwrap();
+    reader.inner().take_requests();
+
+    let entries = reader.file().entries();
+    let (start, end) = (entries[1].header_offset(), entries[4].header_offset());
+    reader.prefetch([3, 1, 2]).await.unwrap();
+    assert_eq!(reader.inner().take_requests(), [(start, end)]);
+
+    for index in 1..4 {
+        assert_eq!(read_entry(&reader, index).await, data(index));
+    }
+    assert!(reader.test_all().await.is_ok());
+    assert_eq!(reader.inner().take_requests().len(), 2);
+}
+
+#[tokio::test]
+async fn short_ranges_are_rejected() {
+    struct ShortSource(Vec<u8>);
+
+    impl RangeReader for ShortSource {
+        async fn length(&self) -> std::io::Result<u64> {
+            Ok(self.0.len() as u64)
+        }
+
+        async fn read_range(&self, range: Range<u64>) -> std::io::Result<Vec<u8>> {
+            Ok(self.0[range.start as usize..range.end as usize - 1].to_vec())
+        }
+    }
+
+    let zip = write_numbered(1).await;
+    let length = zip.len() as u64;
+    let result = ZipFileReader::new(ShortSource(zip)).await;
+    assert!(matches!(
+        result,
+        Err(ZipError::RangeLengthMismatch { expected, actual }) if expected == length && actual == length - 1
+    ));
+}
+
+#[tokio::test]
+async fn entry_readers_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let reader = ZipFileReader::new(MemorySource::new(write_numbered(1).await)).await.unwrap();
+    assert_send(&reader.reader_with_entry(0));
+    assert_send(&reader.reader_with_entry(0).await.unwrap());
+    assert_send(&reader.test_all());
+}
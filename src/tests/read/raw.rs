@@ -0,0 +1,134 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+use crate::base::read::{mem, seek, stream};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::tests::common::{write_streamed, write_zip};
+use crate::{Compression, ZipEntryBuilder};
+
+const ENTRIES: &[(&str, Compression, &[u8])] = &[
+    ("stored", Compression::Stored, b"stored data"),
+    #[cfg(feature = "deflate")]
+    ("deflated", Compression::Deflate, &[b'a'; 1024]),
+];
+
+async fn read_all(mut reader: impl futures_lite::io::AsyncRead + Unpin) -> Vec<u8> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).await.unwrap();
+    data
+}
+
+#[tokio::test]
+async fn raw_data_spans_the_compressed_size() {
+    let reader = mem::ZipFileReader::new(write_zip(ENTRIES).await).await.unwrap();
+
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        let data = read_all(reader.reader_raw(index).await.unwrap()).await;
+        assert_eq!(data.len() as u64, entry.compressed_size());
+    }
+    assert_eq!(read_all(reader.reader_raw(0).await.unwrap()).await, b"stored data");
+    assert!(matches!(reader.reader_raw(2).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn raw_data_is_left_compressed() {
+    use async_compression::futures::bufread::DeflateDecoder;
+    use futures_lite::io::BufReader;
+
+    let reader = mem::ZipFileReader::new(write_zip(ENTRIES).await).await.unwrap();
+    let raw = read_all(reader.reader_raw(1).await.unwrap()).await;
+    assert!(raw.len() < 1024);
+
+    let decoded = read_all(DeflateDecoder::new(BufReader::new(Cursor::new(raw)))).await;
+    assert_eq!(decoded, [b'a'; 1024]);
+}
+
+#[tokio::test]
+async fn readers_agree_on_raw_data() {
+    let zip = write_zip(ENTRIES).await;
+    let mem_reader = mem::ZipFileReader::new(zip.clone()).await.unwrap();
+    let mut seek_reader = seek::ZipFileReader::new(Cursor::new(zip.clone())).await.unwrap();
+    let mut stream_reader = stream::ZipFileReader::new(Cursor::new(zip));
+
+    for index in 0..mem_reader.file().entries().len() {
+        let expected = read_all(mem_reader.reader_raw(index).await.unwrap()).await;
+        assert_eq!(read_all(seek_reader.reader_raw(index).await.unwrap()).await, expected);
+
+        let mut entry = stream_reader.next_raw().await.unwrap().unwrap();
+        assert_eq!(entry.entry().filename().as_bytes(), mem_reader.file().entries()[index].filename().as_bytes());
+        assert_eq!(read_all(entry.reader_mut()).await, expected);
+        let (descriptor, reader) = entry.done().await.unwrap();
+        assert!(descriptor.is_none());
+        stream_reader = reader;
+    }
+    assert!(stream_reader.next_raw().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn raw_stream_entries_can_be_skipped() {
+    let zip = write_zip(ENTRIES).await;
+    let mut stream_reader = stream::ZipFileReader::new(Cursor::new(zip));
+
+    let mut entry = stream_reader.next_raw().await.unwrap().unwrap();
+    entry.reader_mut().read_exact(&mut [0; 3]).await.unwrap();
+    assert!(matches!(entry.done().await, Err(ZipError::EOFNotReached)));
+
+    stream_reader = stream::ZipFileReader::new(Cursor::new(write_zip(ENTRIES).await));
+    let entry = stream_reader.next_raw().await.unwrap().unwrap();
+    stream_reader = entry.skip().await.unwrap().1;
+    let expected = if cfg!(feature = "deflate") { 1 } else { 0 };
+    let mut count = 0;
+    while let Some(entry) = stream_reader.next_raw().await.unwrap() {
+        stream_reader = entry.skip().await.unwrap().1;
+        count += 1;
+    }
+    assert_eq!(count, expected);
+}
+
+#[tokio::test]
+async fn raw_stream_entries_with_data_descriptors_are_delimited() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (name, compression, data) in ENTRIES {
+        write_streamed(&mut writer, name, *compression, data).await;
+    }
+    writer.write_entry_whole(ZipEntryBuilder::new("last".into(), Compression::Stored), b"last").await.unwrap();
+    let zip = writer.close().await.unwrap();
+
+    let mem_reader = mem::ZipFileReader::new(zip.clone()).await.unwrap();
+    let mut stream_reader = stream::ZipFileReader::new(Cursor::new(zip));
+    for (index, stored) in mem_reader.file().entries().iter().enumerate() {
+        let mut entry = stream_reader.next_raw().await.unwrap().unwrap();
+        assert_eq!(read_all(entry.reader_mut()).await, read_all(mem_reader.reader_raw(index).await.unwrap()).await);
+
+        let (descriptor, reader) = entry.done().await.unwrap();
+        let streamed = index < ENTRIES.len();
+        assert_eq!(descriptor.is_some(), streamed);
+        if let Some(descriptor) = descriptor {
+            assert_eq!(descriptor.crc, stored.crc32());
+            assert_eq!(descriptor.compressed_size, stored.compressed_size());
+            assert_eq!(descriptor.uncompressed_size, stored.uncompressed_size());
+        }
+        stream_reader = reader;
+    }
+    assert!(stream_reader.next_raw().await.unwrap().is_none());
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn fs_reader_reads_raw_data() {
+    use crate::tests::common::TempDir;
+    use crate::tokio::read::fs;
+
+    let temp = TempDir::new("raw");
+    let path = temp.path().join("archive.zip");
+    std::fs::write(&path, write_zip(ENTRIES).await).unwrap();
+
+    let reader = fs::ZipFileReader::new(&path).await.unwrap();
+    let data = read_all(reader.reader_raw(0).await.unwrap()).await;
+
+    assert_eq!(data, b"stored data");
+}
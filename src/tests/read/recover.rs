@@ -0,0 +1,162 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{BufReader, Cursor};
+
+use crate::base::read::recover::{recover, recover_with_limits, Recovery};
+use crate::base::read::{mem, seek, ReadLimits};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::tests::common::{positions, write_entries, write_streamed, write_zip};
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes a ZIP file with entries of known size ("a" and "c") either side of one using a data descriptor ("b").
+async fn write_with_descriptor(compression: Compression) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), compression), b"first entry").await.unwrap();
+    write_streamed(&mut writer, "b", compression, b"second entry").await;
+    writer.write_entry_whole(ZipEntryBuilder::new("c".into(), compression), b"third entry").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+fn salvaged(recovery: &Recovery) -> Vec<&str> {
+    recovery.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect()
+}
+
+fn lost(recovery: &Recovery) -> Vec<(&str, String)> {
+    recovery.lost().iter().map(|lost| (lost.filename().as_str().unwrap(), lost.error().to_string())).collect()
+}
+
+/// Writes the salvaged entries to a new ZIP file, returning the contents of each entry it contains.
+async fn repair(data: Vec<u8>, recovery: &Recovery) -> Vec<(String, Vec<u8>)> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    recovery.write(Cursor::new(data), &mut writer).await.unwrap();
+
+    let reader = mem::ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let mut contents = Vec::new();
+    for index in 0..reader.file().entries().len() {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        contents.push((entry.entry().filename().as_str().unwrap().to_string(), buffer));
+    }
+    contents
+}
+
+async fn check_truncated(compression: Compression) {
+    let mut data = write_with_descriptor(compression).await;
+    data.truncate(positions(&data, LFH_SIGNATURE)[2] + 40);
+    assert!(matches!(mem::ZipFileReader::new(data.clone()).await, Err(ZipError::UnableToLocateEOCDR)));
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a", "b"]);
+    assert_eq!(recovery.lost().len(), 1);
+    assert_eq!(recovery.lost()[0].filename().as_str().unwrap(), "c");
+    assert_eq!(recovery.lost()[0].offset(), positions(&data, LFH_SIGNATURE)[2] as u64);
+
+    let contents = repair(data, &recovery).await;
+    assert_eq!(contents, [("a".to_string(), b"first entry".to_vec()), ("b".to_string(), b"second entry".to_vec())]);
+}
+
+#[tokio::test]
+async fn truncated_archive() {
+    check_truncated(Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn truncated_deflate_archive() {
+    check_truncated(Compression::Deflate).await;
+}
+
+#[tokio::test]
+async fn intact_archive() {
+    let data = write_with_descriptor(Compression::Stored).await;
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a", "b", "c"]);
+    assert!(recovery.lost().is_empty());
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    for (recovered, original) in recovery.file().entries().iter().zip(reader.file().entries()) {
+        assert_eq!(recovered.header_offset(), original.header_offset());
+        assert_eq!(recovered.crc32(), original.crc32());
+        assert_eq!(recovered.compressed_size(), original.compressed_size());
+        assert_eq!(recovered.uncompressed_size(), original.uncompressed_size());
+    }
+}
+
+#[tokio::test]
+async fn prefixed_archive_without_central_directory() {
+    let mut data = b"#!/bin/sh\nexit 0\n".to_vec();
+    let zip = write_with_descriptor(Compression::Stored).await;
+    data.extend_from_slice(&zip[..positions(&zip, CDH_SIGNATURE)[0]]);
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a", "b", "c"]);
+    assert!(recovery.lost().is_empty());
+
+    let mut reader = seek::ZipFileReader::from_raw_parts(BufReader::new(Cursor::new(data)), recovery.into_file());
+    let mut entry = reader.reader_with_entry(1).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"second entry");
+}
+
+#[tokio::test]
+async fn corrupted_entry_is_lost() {
+    let mut data = write_with_descriptor(Compression::Stored).await;
+    let offset = data.windows(5).position(|window| window == b"first").unwrap();
+    data[offset] ^= 0xff;
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["b", "c"]);
+    assert_eq!(lost(&recovery), [("a", ZipError::CRC32CheckError.to_string())]);
+
+    let contents = repair(data, &recovery).await;
+    assert_eq!(contents, [("b".to_string(), b"second entry".to_vec()), ("c".to_string(), b"third entry".to_vec())]);
+}
+
+#[tokio::test]
+async fn stray_signatures_are_skipped() {
+    let mut data = b"not a header: ".to_vec();
+    data.extend_from_slice(&LFH_SIGNATURE.to_le_bytes());
+    let data = write_zip(&[("a", Compression::Stored, &data)]).await;
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a"]);
+    assert!(recovery.lost().is_empty());
+}
+
+#[tokio::test]
+async fn duplicate_names_are_lost() {
+    let entries = [("a", Compression::Stored, b"first".as_slice()), ("a", Compression::Stored, b"second".as_slice())];
+    let zip = write_entries(ZipFileWriter::new(Vec::new()).allow_duplicate_names(), &entries).await;
+    let data = zip[..positions(&zip, CDH_SIGNATURE)[0]].to_vec();
+
+    let recovery = recover(Cursor::new(data.as_slice())).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a"]);
+    assert_eq!(lost(&recovery), [("a", ZipError::DuplicateEntryName("a".to_string()).to_string())]);
+    assert_eq!(recovery.lost()[0].offset(), positions(&data, LFH_SIGNATURE)[1] as u64);
+
+    let contents = repair(data, &recovery).await;
+    assert_eq!(contents, [("a".to_string(), b"first".to_vec())]);
+}
+
+#[tokio::test]
+async fn limits_apply_to_data_descriptor_entries() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    write_streamed(&mut writer, "a", Compression::Stored, &[1; 100]).await;
+    write_streamed(&mut writer, "b", Compression::Stored, &[1; 10]).await;
+    let zip = writer.close().await.unwrap();
+    let data = &zip[..positions(&zip, CDH_SIGNATURE)[0]];
+
+    let recovery = recover_with_limits(Cursor::new(data), ReadLimits::new().max_entry_size(50)).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["b"]);
+    assert_eq!(lost(&recovery), [("a", ZipError::EntrySizeLimitExceeded(50).to_string())]);
+
+    let recovery = recover_with_limits(Cursor::new(data), ReadLimits::new().max_total_size(105)).await.unwrap();
+    assert_eq!(salvaged(&recovery), ["a"]);
+    assert_eq!(lost(&recovery), [("b", ZipError::TotalSizeLimitExceeded(105).to_string())]);
+}
@@ -0,0 +1,218 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncReadExt, BufReader, Cursor};
+
+use crate::base::read::seek;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{
+    CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_SIGNATURE, NON_ZIP64_MAX_SIZE, ZIP64_EOCDL_SIGNATURE,
+    ZIP64_EOCDR_SIGNATURE,
+};
+use crate::tests::common::{positions, write_entries};
+use crate::Compression;
+
+const SEGMENT_SIZE: u64 = 64;
+
+async fn write_zip(zip64: bool) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    if zip64 {
+        writer = writer.force_zip64();
+    }
+    let entries = entries();
+    write_entries(
+        writer,
+        &entries.iter().map(|(name, data)| (name.as_str(), Compression::Stored, data.as_slice())).collect::<Vec<_>>(),
+    )
+    .await
+}
+
+fn entries() -> Vec<(String, Vec<u8>)> {
+    (0..3).map(|index| (format!("file-{index}"), vec![b'a' + index; 100 + index as usize])).collect()
+}
+
+fn u16_at(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap())
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Splits a ZIP file into segments of at most `SEGMENT_SIZE` bytes, as `zip -s` would, keeping the end of central
+/// directory records within the final segment.
+fn split(zip: &[u8]) -> Vec<Vec<u8>> {
+    let mut data = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes().to_vec();
+    data.extend_from_slice(zip);
+
+    let eocdr = positions(&data, EOCDR_SIGNATURE).last().copied().unwrap();
+    let zip64_eocdr = positions(&data, ZIP64_EOCDR_SIGNATURE).last().copied();
+    let records_start = zip64_eocdr.unwrap_or(eocdr) as u64;
+    let boundaries: Vec<u64> =
+        (0..).map(|disk| disk * SEGMENT_SIZE).take_while(|start| *start < records_start).collect();
+    let last_disk = (boundaries.len() - 1) as u32;
+    let locate = |offset: u64| {
+        let disk = boundaries.partition_point(|start| *start <= offset) - 1;
+        (disk as u32, offset - boundaries[disk])
+    };
+
+    // Rewrite each central directory record's local file header offset relative to its disk.
+    let directory_start = u32_at(&data, eocdr + 16) as u64 + 4;
+    let directory_start = zip64_eocdr.map(|record| u64_at(&data, record + 48) + 4).unwrap_or(directory_start);
+    let mut record = directory_start as usize;
+    while u32_at(&data, record) == CDH_SIGNATURE {
+        let filename_length = u16_at(&data, record + 28) as usize;
+        let extra_field_length = u16_at(&data, record + 30) as usize;
+        let comment_length = u16_at(&data, record + 32) as usize;
+
+        let offset_field = if u32_at(&data, record + 42) == NON_ZIP64_MAX_SIZE {
+            let extra_field = record + 46 + filename_length;
+            assert_eq!(u16_at(&data, extra_field), 0x0001);
+            let sizes = [24, 20].iter().filter(|field| u32_at(&data, record + **field) == NON_ZIP64_MAX_SIZE).count();
+            Some(extra_field + 4 + 8 * sizes)
+        } else {
+            None
+        };
+        let offset = offset_field.map(|field| u64_at(&data, field)).unwrap_or(u32_at(&data, record + 42) as u64);
+        let (disk, relative) = locate(offset + 4);
+        data[record + 34..record + 36].copy_from_slice(&(disk as u16).to_le_bytes());
+        match offset_field {
+            Some(field) => data[field..field + 8].copy_from_slice(&relative.to_le_bytes()),
+            None => data[record + 42..record + 46].copy_from_slice(&(relative as u32).to_le_bytes()),
+        }
+
+        record += 46 + filename_length + extra_field_length + comment_length;
+    }
+
+    // Rewrite the end of central directory records to describe the disks.
+    let (directory_disk, directory_offset) = locate(directory_start);
+    data[eocdr + 4..eocdr + 6].copy_from_slice(&(last_disk as u16).to_le_bytes());
+    data[eocdr + 6..eocdr + 8].copy_from_slice(&(directory_disk as u16).to_le_bytes());
+    data[eocdr + 8..eocdr + 10].copy_from_slice(&0_u16.to_le_bytes());
+    if let Some(zip64_eocdr) = zip64_eocdr {
+        data[zip64_eocdr + 16..zip64_eocdr + 20].copy_from_slice(&last_disk.to_le_bytes());
+        data[zip64_eocdr + 20..zip64_eocdr + 24].copy_from_slice(&directory_disk.to_le_bytes());
+        data[zip64_eocdr + 24..zip64_eocdr + 32].copy_from_slice(&0_u64.to_le_bytes());
+        data[zip64_eocdr + 48..zip64_eocdr + 56].copy_from_slice(&directory_offset.to_le_bytes());
+
+        let locator = positions(&data, ZIP64_EOCDL_SIGNATURE).last().copied().unwrap();
+        let (disk, relative) = locate(zip64_eocdr as u64);
+        data[locator + 4..locator + 8].copy_from_slice(&disk.to_le_bytes());
+        data[locator + 8..locator + 16].copy_from_slice(&relative.to_le_bytes());
+        data[locator + 16..locator + 20].copy_from_slice(&(last_disk + 1).to_le_bytes());
+    }
+    if u32_at(&data, eocdr + 16) != NON_ZIP64_MAX_SIZE {
+        data[eocdr + 16..eocdr + 20].copy_from_slice(&(directory_offset as u32).to_le_bytes());
+    }
+
+    let mut segments = Vec::new();
+    for (disk, start) in boundaries.iter().enumerate() {
+        let end = boundaries.get(disk + 1).copied().unwrap_or(data.len() as u64);
+        segments.push(data[*start as usize..end as usize].to_vec());
+    }
+    segments
+}
+
+fn cursors(segments: Vec<Vec<u8>>) -> Vec<Cursor<Vec<u8>>> {
+    segments.into_iter().map(Cursor::new).collect()
+}
+
+async fn check_split(zip64: bool) {
+    let segments = split(&write_zip(zip64).await);
+    assert!(segments.len() > 3);
+
+    let mut reader = seek::ZipFileReader::new_split(cursors(segments)).await.unwrap();
+    assert_eq!(reader.file().zip64(), zip64);
+    assert_eq!(reader.file().entries().len(), 3);
+
+    for (index, (name, expected)) in entries().into_iter().enumerate() {
+        let entry = &reader.file().entries()[index];
+        assert_eq!(entry.filename().as_str().unwrap(), name);
+
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+
+    // Read the entries out of order, so that segments are revisited.
+    let mut buffer = Vec::new();
+    reader.reader_without_entry(0).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+    assert_eq!(buffer, entries()[0].1);
+
+    assert!(reader.test_all().await.is_ok());
+    assert!(reader.verify().await.unwrap().is_consistent());
+}
+
+#[tokio::test]
+async fn split_archive_is_read_across_segments() {
+    check_split(false).await;
+}
+
+#[tokio::test]
+async fn split_zip64_archive_is_read_across_segments() {
+    check_split(true).await;
+}
+
+#[tokio::test]
+async fn unsplit_archive_is_read_as_one_segment() {
+    let data = write_zip(false).await;
+    let mut reader = seek::ZipFileReader::new_split(cursors(vec![data])).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 3);
+    assert!(reader.test_all().await.is_ok());
+}
+
+#[tokio::test]
+async fn missing_segment_is_rejected() {
+    let mut segments = split(&write_zip(false).await);
+    let disks = segments.len() as u64;
+    segments.remove(1);
+
+    let Err(err) = seek::ZipFileReader::new_split(cursors(segments)).await else {
+        panic!("expected a missing segment to be rejected");
+    };
+    assert!(matches!(
+        err,
+        ZipError::SplitSegmentCountMismatch { expected, actual } if expected == disks && actual as u64 == disks - 1
+    ));
+}
+
+#[tokio::test]
+async fn concatenated_split_archive_is_unsupported() {
+    let data = split(&write_zip(false).await).concat();
+    let Err(err) = seek::ZipFileReader::new(BufReader::new(Cursor::new(data))).await else {
+        panic!("expected a split archive to be rejected without its segments");
+    };
+    assert!(matches!(err, ZipError::FeatureNotSupported(_)));
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn split_archive_is_opened_from_the_file_system() {
+    use crate::tests::common::TempDir;
+    use crate::tokio::read::split;
+
+    let temp = TempDir::new("split");
+    let directory = temp.path();
+
+    let segments = self::split(&write_zip(false).await);
+    let last = segments.len() - 1;
+    for (disk, segment) in segments.iter().enumerate() {
+        let extension = if disk == last { "zip".to_string() } else { format!("z{:02}", disk + 1) };
+        std::fs::write(directory.join("archive").with_extension(extension), segment).unwrap();
+    }
+
+    let path = directory.join("archive.zip");
+    let paths = split::segment_paths(&path).await.unwrap();
+    let mut reader = split::open(&path).await.unwrap();
+    let report = reader.test_all().await;
+
+    assert_eq!(paths.len(), segments.len());
+    assert_eq!(paths.last(), Some(&path));
+    assert!(report.is_ok());
+    assert_eq!(report.tested(), 3);
+}
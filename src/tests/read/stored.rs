@@ -0,0 +1,77 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use std::io::SeekFrom;
+
+use futures_lite::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, Cursor};
+
+use crate::base::read::{mem, seek};
+use crate::error::ZipError;
+use crate::tests::common::write_zip;
+use crate::Compression;
+
+#[tokio::test]
+async fn nested_archives_are_read_from_entries() {
+    let inner =
+        write_zip(&[("first.txt", Compression::Stored, b"first"), ("second.txt", Compression::Stored, b"second")])
+            .await;
+    let outer =
+        write_zip(&[("readme.txt", Compression::Stored, b"readme"), ("inner.zip", Compression::Stored, &inner)]).await;
+    let reader = mem::ZipFileReader::new(outer.clone()).await.unwrap();
+
+    let entry = reader.reader_stored(1).await.unwrap();
+    assert_eq!(entry.len(), inner.len() as u64);
+    let mut nested = seek::ZipFileReader::new(entry).await.unwrap();
+    assert_eq!(nested.file().entries().len(), 2);
+
+    let mut data = String::new();
+    nested.reader_with_entry(1).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+    assert_eq!(data, "second");
+    assert!(nested.test_all().await.is_ok());
+
+    // The same applies when the outer reader borrows its source.
+    let mut reader = seek::ZipFileReader::new(Cursor::new(outer)).await.unwrap();
+    let mut nested = seek::ZipFileReader::new(reader.reader_stored(1).await.unwrap()).await.unwrap();
+    let mut data = String::new();
+    nested.reader_with_entry(0).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+    assert_eq!(data, "first");
+}
+
+#[tokio::test]
+async fn seeks_are_bounded_to_the_entry() {
+    let zip =
+        write_zip(&[("first.txt", Compression::Stored, b"0123456789"), ("second.txt", Compression::Stored, b"abcdef")])
+            .await;
+    let reader = mem::ZipFileReader::new(zip).await.unwrap();
+    let mut entry = reader.reader_stored(0).await.unwrap();
+
+    assert_eq!(entry.seek(SeekFrom::End(-4)).await.unwrap(), 6);
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data).await.unwrap();
+    assert_eq!(data, b"6789");
+
+    assert_eq!(entry.seek(SeekFrom::Current(-7)).await.unwrap(), 3);
+    assert_eq!(entry.fill_buf().await.unwrap(), b"3456789");
+    entry.consume(5);
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data).await.unwrap();
+    assert_eq!(data, b"89");
+
+    entry.seek(SeekFrom::Start(100)).await.unwrap();
+    assert_eq!(entry.read(&mut [0; 4]).await.unwrap(), 0);
+    assert!(entry.seek(SeekFrom::Current(-101)).await.is_err());
+}
+
+#[tokio::test]
+async fn only_stored_entries_are_seekable() {
+    let zip = write_zip(&[("first.txt", Compression::Stored, b"first")]).await;
+    let reader = mem::ZipFileReader::new(zip).await.unwrap();
+    assert!(matches!(reader.reader_stored(1).await, Err(ZipError::EntryIndexOutOfBounds)));
+
+    #[cfg(feature = "deflate")]
+    {
+        let zip = write_zip(&[("deflated.txt", Compression::Deflate, b"deflated")]).await;
+        let reader = mem::ZipFileReader::new(zip).await.unwrap();
+        assert!(matches!(reader.reader_stored(0).await, Err(ZipError::EntryNotStored)));
+    }
+}
@@ -21,3 +21,82 @@ async fn invalid_deflate64_stream_version_fails_before_body_read() {
 
     assert!(matches!(err, ZipError::InvalidCompressionVersion { version: 1, required: 21, compression: 9 }));
 }
+
+#[tokio::test]
+async fn stored_entries_with_data_descriptors() {
+    use crate::base::read::stream::ZipFileReader;
+
+    // Written by Python's zipfile to an unseekable output; the second entry's data contains a data descriptor
+    // signature.
+    let data = include_bytes!("stored-data-descriptor.zip");
+    let expected: [&[u8]; 2] = [b"stored data\n", b"contains a signature: PK\x07\x08 mid-stream\n"];
+
+    let mut zip = ZipFileReader::new(data.as_slice());
+    for expected in expected {
+        let mut entry = zip.next_with_entry().await.unwrap().expect("archive should contain another entry");
+        assert!(entry.reader().entry().data_descriptor());
+
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, expected);
+
+        let (descriptor, next) = entry.done().await.unwrap();
+        let descriptor = descriptor.expect("entry should be followed by a data descriptor");
+        assert_eq!(descriptor.crc, crc32fast::hash(expected));
+        assert_eq!(descriptor.compressed_size, expected.len() as u64);
+        zip = next;
+    }
+    assert!(zip.next_without_entry().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn stored_entries_with_data_descriptors_round_trip() {
+    use crate::base::read::stream::ZipFileReader;
+    use crate::base::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+    use futures_lite::io::AsyncWriteExt;
+
+    let contents = [b"first".repeat(4096), Vec::new(), b"PK\x07\x08".repeat(64)];
+
+    for force_no_zip64 in [false, true] {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        if force_no_zip64 {
+            writer = writer.force_no_zip64();
+        }
+        for (index, content) in contents.iter().enumerate() {
+            let entry = ZipEntryBuilder::new(format!("{index}").into(), Compression::Stored);
+            let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+            entry_writer.write_all(content).await.unwrap();
+            entry_writer.close().await.unwrap();
+        }
+        let data = writer.close().await.unwrap();
+
+        let mut zip = ZipFileReader::new(data.as_slice());
+        for content in &contents {
+            let mut entry = zip.next_with_entry().await.unwrap().expect("archive should contain another entry");
+            let mut buffer = Vec::new();
+            futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+            assert_eq!(&buffer, content);
+
+            let (descriptor, next) = entry.skip().await.unwrap();
+            assert_eq!(descriptor.unwrap().uncompressed_size, content.len() as u64);
+            zip = next;
+        }
+        assert!(zip.next_without_entry().await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn stored_entry_without_data_descriptor_fails() {
+    use crate::base::read::stream::ZipFileReader;
+    use crate::error::ZipError;
+
+    // Truncate the archive partway through the first entry's data descriptor.
+    let data = include_bytes!("stored-data-descriptor.zip");
+    let data = &data[..60];
+
+    let mut entry = ZipFileReader::new(data).next_with_entry().await.unwrap().unwrap();
+    let mut buffer = Vec::new();
+    let err = entry.reader_mut().read_to_end_checked(&mut buffer).await.unwrap_err();
+    assert!(matches!(err, ZipError::DataDescriptorNotFound));
+}
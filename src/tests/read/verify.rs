@@ -0,0 +1,151 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{BufReader, Cursor};
+
+use crate::base::read::{mem, seek, DuplicateNames, Finding, HeaderField, ReadOptions, VerificationReport};
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::tests::common::{positions, write_entries, write_streamed};
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes a ZIP file with an entry of known size ("a") followed by one using a data descriptor ("b").
+async fn write_with_descriptor() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), b"first").await.unwrap();
+    write_streamed(&mut writer, "b", Compression::Stored, b"second").await;
+    writer.close().await.unwrap()
+}
+
+/// Rewrites the end of central directory record's entry counts and central directory size.
+fn set_directory(data: &mut [u8], entries: u16, size: u32) {
+    let eocdr = positions(data, EOCDR_SIGNATURE)[0];
+    data[eocdr + 8..eocdr + 10].copy_from_slice(&entries.to_le_bytes());
+    data[eocdr + 10..eocdr + 12].copy_from_slice(&entries.to_le_bytes());
+    data[eocdr + 12..eocdr + 16].copy_from_slice(&size.to_le_bytes());
+}
+
+async fn verify(data: Vec<u8>) -> VerificationReport {
+    mem::ZipFileReader::new(data).await.unwrap().verify().await.unwrap()
+}
+
+fn mismatches(report: &VerificationReport) -> Vec<(usize, HeaderField)> {
+    report
+        .findings()
+        .iter()
+        .map(|finding| match finding {
+            Finding::Mismatch { entry, field } => (*entry, *field),
+            finding => panic!("unexpected finding: {finding:?}"),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn consistent_archive() {
+    let data = write_with_descriptor().await;
+    assert!(verify(data.clone()).await.is_consistent());
+
+    let mut reader = seek::ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap();
+    assert!(reader.verify().await.unwrap().is_consistent());
+}
+
+#[tokio::test]
+async fn local_header_field_mismatches() {
+    let mut data = write_with_descriptor().await;
+    let lfh = positions(&data, LFH_SIGNATURE)[0];
+    data[lfh + 7] ^= 0x08; // Toggle the UTF-8 flag.
+    data[lfh + 10] ^= 0x01; // Alter the modification time.
+    data[lfh + 14] ^= 0x01; // Alter the CRC32.
+    data[lfh + 30] = b'c'; // Rename "a" to "c".
+
+    let report = verify(data).await;
+    assert_eq!(
+        mismatches(&report),
+        [
+            (0, HeaderField::Flags),
+            (0, HeaderField::LastModification),
+            (0, HeaderField::FileName),
+            (0, HeaderField::Crc32)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn local_header_size_mismatch() {
+    let mut data = write_with_descriptor().await;
+    let lfh = positions(&data, LFH_SIGNATURE)[0];
+    data[lfh + 14..lfh + 18].copy_from_slice(&0u32.to_le_bytes());
+    data[lfh + 22..lfh + 26].copy_from_slice(&4u32.to_le_bytes());
+
+    let report = verify(data).await;
+    assert_eq!(mismatches(&report), [(0, HeaderField::Crc32), (0, HeaderField::UncompressedSize)]);
+}
+
+#[tokio::test]
+async fn data_descriptor_mismatch() {
+    let mut data = write_with_descriptor().await;
+    let descriptor = positions(&data, DATA_DESCRIPTOR_SIGNATURE)[0];
+    data[descriptor + 4] ^= 0x01;
+
+    let report = verify(data).await;
+    assert_eq!(mismatches(&report), [(1, HeaderField::DataDescriptor)]);
+}
+
+#[tokio::test]
+async fn hidden_local_entry() {
+    let mut data = write_with_descriptor().await;
+
+    // Drop the second entry's central directory record, leaving its local record hidden from seeking readers.
+    let cdh = positions(&data, CDH_SIGNATURE);
+    let eocdr = positions(&data, EOCDR_SIGNATURE)[0];
+    data.drain(cdh[1]..eocdr);
+    set_directory(&mut data, 1, (cdh[1] - cdh[0]) as u32);
+
+    let second = positions(&data, LFH_SIGNATURE)[1] as u64;
+    let report = verify(data).await;
+    let [Finding::UnaccountedBytes { start, end }] = report.findings() else {
+        panic!("expected unaccounted bytes, found {:?}", report.findings());
+    };
+    assert_eq!((*start, *end), (second, cdh[0] as u64));
+}
+
+#[tokio::test]
+async fn overlapping_entries() {
+    let mut data = write_with_descriptor().await;
+
+    // Replace the second central directory record with a copy of the first, so both refer to the same local record.
+    let cdh = positions(&data, CDH_SIGNATURE);
+    let eocdr = positions(&data, EOCDR_SIGNATURE)[0];
+    let first = data[cdh[0]..cdh[1]].to_vec();
+    data.splice(cdh[1]..eocdr, first);
+    set_directory(&mut data, 2, (2 * (cdh[1] - cdh[0])) as u32);
+
+    let second = positions(&data, LFH_SIGNATURE)[1] as u64;
+    let report = verify(data).await;
+    let [Finding::Overlap { entry: 1, other: 0 }, Finding::UnaccountedBytes { start, .. }] = report.findings() else {
+        panic!("expected overlapping entries, found {:?}", report.findings());
+    };
+    assert_eq!(*start, second);
+}
+
+#[tokio::test]
+async fn local_header_name_mismatch_sample() {
+    let report = verify(include_bytes!("cd/diff-004-sample.zip").to_vec()).await;
+    assert_eq!(mismatches(&report), [(0, HeaderField::FileName)]);
+}
+
+#[tokio::test]
+async fn entries_are_audited_as_read() {
+    let entries = [("a", Compression::Stored, b"first".as_slice()), ("a", Compression::Stored, b"second".as_slice())];
+    let data = write_entries(ZipFileWriter::new(Vec::new()).allow_duplicate_names(), &entries).await;
+    let second = positions(&data, LFH_SIGNATURE)[1] as u64;
+
+    // The discarded entry's local record is no longer accounted for, whilst the kept entry is audited as entry 0.
+    let options = ReadOptions::new().duplicate_names(DuplicateNames::KeepLast);
+    let reader = mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+    let report = reader.verify().await.unwrap();
+    let [Finding::UnaccountedBytes { start: 0, end }] = report.findings() else {
+        panic!("expected unaccounted bytes, found {:?}", report.findings());
+    };
+    assert_eq!(*end, second);
+}
@@ -111,3 +111,29 @@ async fn invalid_seekable_local_header_version_is_rejected_before_entry_read() {
 
     assert!(matches!(err, ZipError::InvalidCompressionVersion { version: 1, required: 21, compression: 9 }));
 }
+
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn invalid_versions_are_tolerated_when_lenient() {
+    use futures_lite::io::{BufReader, Cursor};
+
+    use crate::base::read::seek::ZipFileReader;
+    use crate::base::read::{ReadOptions, Strictness};
+    use crate::error::ZipError;
+
+    let options = ReadOptions::new().strictness(Strictness::Lenient);
+    let data = deflate64_zip(1, 1);
+    let mut zip = ZipFileReader::new_with_options(BufReader::new(Cursor::new(data)), options).await.unwrap();
+    assert_eq!(zip.file().warnings().len(), 1);
+    zip.reader_without_entry(0).await.unwrap();
+
+    let warnings = zip.file().warnings();
+    assert_eq!(warnings.len(), 2);
+    for warning in warnings {
+        assert_eq!(warning.entry(), Some(0));
+        assert!(matches!(
+            warning.error(),
+            ZipError::InvalidCompressionVersion { version: 1, required: 21, compression: 9 }
+        ));
+    }
+}
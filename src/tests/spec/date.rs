@@ -71,3 +71,16 @@ fn date_conversion_test() {
     assert_eq!(minute, built.minute());
     assert_eq!(second, built.second());
 }
+
+#[test]
+fn system_time_conversion_test() {
+    use std::time::{Duration, SystemTime};
+
+    let date_time = ZipDateTimeBuilder::new().year(2024).month(2).day(29).hour(13).minute(37).second(42).build();
+    let system_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_709_213_862);
+
+    assert_eq!(date_time.as_system_time(), Some(system_time));
+    assert_eq!(ZipDateTime::from_system_time(system_time), Some(date_time));
+    assert_eq!(ZipDateTime::from_system_time(SystemTime::UNIX_EPOCH), None);
+    assert_eq!(ZipDateTime { date: 0, time: 0 }.as_system_time(), None);
+}
@@ -0,0 +1,149 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncWriteExt, Cursor};
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes an archive holding a single entry, with the provided comment.
+async fn existing_archive(comment: Option<&str>) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("first".into(), Compression::Stored), b"first data").await.unwrap();
+    if let Some(comment) = comment {
+        writer.comment(comment.to_string());
+    }
+    writer.close().await.unwrap()
+}
+
+async fn assert_entries(data: Vec<u8>, expected: &[(&str, &[u8])]) {
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), expected.len());
+    for (index, (name, contents)) in expected.iter().enumerate() {
+        assert_eq!(reader.file().entries()[index].filename().as_str().unwrap(), *name);
+
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, *contents);
+    }
+
+    let mut zip = zip::read::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+    for (name, contents) in expected {
+        let mut file = zip.by_name(name).unwrap();
+        let mut buffer = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut buffer).unwrap();
+        assert_eq!(buffer, *contents);
+    }
+}
+
+#[tokio::test]
+async fn append_entries() {
+    let existing = existing_archive(None).await;
+
+    let mut writer = ZipFileWriter::append(Cursor::new(existing)).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("whole".into(), Compression::Stored), b"whole data").await.unwrap();
+
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("stream".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"stream data").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let mut entry_writer =
+        writer.write_entry_seekable(ZipEntryBuilder::new("seekable".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"seekable data").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let data = writer.close().await.unwrap().into_inner();
+    assert_entries(
+        data,
+        &[
+            ("first", b"first data"),
+            ("whole", b"whole data"),
+            ("stream", b"stream data"),
+            ("seekable", b"seekable data"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn append_preserves_comment() {
+    let existing = existing_archive(Some("archive comment")).await;
+
+    let mut writer = ZipFileWriter::append(Cursor::new(existing)).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("second".into(), Compression::Stored), b"second data").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), "archive comment");
+    assert_entries(data, &[("first", b"first data"), ("second", b"second data")]).await;
+}
+
+#[tokio::test]
+async fn append_without_entries_is_unchanged() {
+    let existing = existing_archive(Some("archive comment")).await;
+
+    let writer = ZipFileWriter::append(Cursor::new(existing.clone())).await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    assert_eq!(data, existing);
+}
+
+#[tokio::test]
+async fn append_preserves_non_utf8_comment() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("first".into(), Compression::Stored), b"first data").await.unwrap();
+    let mut existing = writer.close().await.unwrap();
+    let comment = b"caf\xe9 \xff";
+    let length = existing.len();
+    existing[length - 2..].copy_from_slice(&(comment.len() as u16).to_le_bytes());
+    existing.extend_from_slice(comment);
+
+    let writer = ZipFileWriter::append(Cursor::new(existing.clone())).await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().comment().as_bytes(), comment);
+    assert_eq!(data, existing);
+}
+
+#[tokio::test]
+async fn append_with_shorter_directory_is_truncated() {
+    let existing = existing_archive(Some("a rather long archive comment")).await;
+
+    let mut writer = ZipFileWriter::append(Cursor::new(existing.clone())).await.unwrap();
+    writer.comment("short".to_string());
+    let (cursor, len) = writer.close_with_len().await.unwrap();
+    assert_eq!(len, cursor.position());
+    assert!(len < existing.len() as u64);
+
+    // Without truncating, the original end of central directory record is left in place.
+    let mut data = cursor.into_inner();
+    assert_eq!(data.len(), existing.len());
+    data.truncate(len as usize);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), "short");
+    assert_entries(data, &[("first", b"first data")]).await;
+}
+
+#[tokio::test]
+async fn append_keeps_zip64() {
+    let mut writer = ZipFileWriter::new(Vec::new()).force_zip64();
+    writer.write_entry_whole(ZipEntryBuilder::new("first".into(), Compression::Stored), b"first data").await.unwrap();
+    let existing = writer.close().await.unwrap();
+
+    let mut writer = ZipFileWriter::append(Cursor::new(existing)).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("second".into(), Compression::Stored), b"second data").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(reader.file().zip64);
+    assert_entries(data, &[("first", b"first data"), ("second", b"second data")]).await;
+}
+
+#[tokio::test]
+async fn append_rejects_invalid_archive() {
+    assert!(ZipFileWriter::append(Cursor::new(b"not a zip file".to_vec())).await.is_err());
+}
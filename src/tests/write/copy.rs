@@ -0,0 +1,175 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncSeekExt, Cursor, SeekFrom};
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{CDH_SIGNATURE, NON_ZIP64_MAX_SIZE};
+use crate::spec::header::ExtraField;
+use crate::tests::common::positions;
+use crate::tests::write::SeekableAsyncSink;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Copies every entry of `source` into a new archive, followed by a freshly written entry.
+async fn copy_all(source: &[u8]) -> Vec<u8> {
+    let reader = ZipFileReader::new(source.to_vec()).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for entry in reader.file().entries() {
+        writer.copy_raw_entry(entry, Cursor::new(reader.data())).await.unwrap();
+    }
+    writer.write_entry_whole(ZipEntryBuilder::new("new".into(), Compression::Stored), b"new data").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+/// Returns the "version made by" and "version needed to extract" fields of each central directory record.
+fn central_versions(data: &[u8]) -> Vec<(u16, u16)> {
+    positions(data, CDH_SIGNATURE)
+        .into_iter()
+        .map(|record| {
+            let field = |offset: usize| u16::from_le_bytes([data[record + offset], data[record + offset + 1]]);
+            (field(4), field(6))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn copy_preserves_entries() {
+    let contents = b"repeated data ".repeat(128);
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("stored".into(), Compression::Stored).unix_permissions(0o755);
+    writer.write_entry_whole(entry, &contents).await.unwrap();
+    #[cfg(feature = "deflate")]
+    writer.write_entry_whole(ZipEntryBuilder::new("deflate".into(), Compression::Deflate), &contents).await.unwrap();
+    let source = writer.close().await.unwrap();
+
+    let data = copy_all(&source).await;
+
+    let source = ZipFileReader::new(source).await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), source.file().entries().len() + 1);
+    for (index, expected) in source.file().entries().iter().enumerate() {
+        let actual = &reader.file().entries()[index];
+        assert_eq!(actual.filename().as_bytes(), expected.filename().as_bytes());
+        assert_eq!(actual.compression(), expected.compression());
+        assert_eq!(actual.crc32(), expected.crc32());
+        assert_eq!(actual.compressed_size(), expected.compressed_size());
+        assert_eq!(actual.uncompressed_size(), expected.uncompressed_size());
+        assert_eq!(actual.external_file_attribute(), expected.external_file_attribute());
+
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, contents);
+    }
+
+    let mut zip = zip::read::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+    let mut file = zip.by_name("stored").unwrap();
+    let mut buffer = Vec::new();
+    std::io::Read::read_to_end(&mut file, &mut buffer).unwrap();
+    assert_eq!(buffer, contents);
+}
+
+#[tokio::test]
+async fn copy_preserves_version_made_by() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("dos".into(), Compression::Stored), b"dos data").await.unwrap();
+    let mut source = writer.close().await.unwrap();
+
+    // Mark the entry as having been made by MS-DOS (host 0) with version 2.0 of the specification.
+    let record = positions(&source, CDH_SIGNATURE)[0];
+    source[record + 4..record + 6].copy_from_slice(&20_u16.to_le_bytes());
+
+    let data = copy_all(&source).await;
+    let versions = central_versions(&data);
+    assert_eq!(versions[0].0, 20);
+    assert_eq!(versions[1].0, crate::spec::version::as_made_by());
+}
+
+#[tokio::test]
+async fn copy_entries_with_data_descriptors() {
+    let data = copy_all(include_bytes!("../read/stored-data-descriptor.zip")).await;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 3);
+    for index in 0..3 {
+        let mut entry = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer.len() as u64, reader.file().entries()[index].uncompressed_size());
+    }
+    assert!(reader.file().entries()[0].data_descriptor());
+}
+
+#[cfg(feature = "encryption")]
+#[tokio::test]
+async fn copy_encrypted_entries() {
+    use crate::Encryption;
+    use futures_lite::io::AsyncWriteExt;
+
+    let encryptions = [
+        Encryption::ZipCrypto,
+        #[cfg(feature = "aes")]
+        Encryption::Aes(crate::AesStrength::Aes256),
+    ];
+
+    for encryption in encryptions {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        let entry = ZipEntryBuilder::new("whole".into(), Compression::Stored).encryption(encryption, "secret");
+        writer.write_entry_whole(entry, b"whole data").await.unwrap();
+        let entry = ZipEntryBuilder::new("stream".into(), Compression::Stored).encryption(encryption, "secret");
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(b"stream data").await.unwrap();
+        entry_writer.close().await.unwrap();
+        let source = writer.close().await.unwrap();
+
+        let data = copy_all(&source).await;
+        assert_eq!(central_versions(&data)[..2], central_versions(&source));
+
+        let reader = ZipFileReader::new(data).await.unwrap().with_password("secret");
+        for (index, expected) in [b"whole data".as_slice(), b"stream data"].into_iter().enumerate() {
+            assert!(reader.file().entries()[index].encrypted());
+            let mut entry = reader.reader_with_entry(index).await.unwrap();
+            let mut buffer = Vec::new();
+            entry.read_to_end_checked(&mut buffer).await.unwrap();
+            assert_eq!(buffer, expected);
+        }
+    }
+}
+
+#[tokio::test]
+async fn copy_rewrites_zip64_local_header_offset() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("file".into(), Compression::Stored), b"data").await.unwrap();
+    let source = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(SeekableAsyncSink::default());
+    writer.writer.seek(SeekFrom::Start(NON_ZIP64_MAX_SIZE as u64 + 1)).await.unwrap();
+    writer.copy_raw_entry(&source.file().entries()[0], Cursor::new(source.data())).await.unwrap();
+
+    assert!(writer.is_zip64);
+    let cd_entry = writer.cd_entries.last().unwrap();
+    assert_eq!(cd_entry.header.v_needed, 45);
+    assert_eq!(cd_entry.header.lh_offset, NON_ZIP64_MAX_SIZE);
+    match cd_entry.entry.extra_fields().last().unwrap() {
+        ExtraField::Zip64ExtendedInformation(zip64) => {
+            assert_eq!(zip64.compressed_size, None);
+            assert_eq!(zip64.relative_header_offset, Some(NON_ZIP64_MAX_SIZE as u64 + 1));
+        }
+        field => panic!("Expected a Zip64 extended field, got {field:?}"),
+    }
+}
+
+#[tokio::test]
+async fn copy_rejects_zip64_when_forced_off() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("file".into(), Compression::Stored), b"data").await.unwrap();
+    let source = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(SeekableAsyncSink::default()).force_no_zip64();
+    writer.writer.seek(SeekFrom::Start(NON_ZIP64_MAX_SIZE as u64 + 1)).await.unwrap();
+    let result = writer.copy_raw_entry(&source.file().entries()[0], Cursor::new(source.data())).await;
+
+    assert!(matches!(result, Err(crate::error::ZipError::Zip64Needed(_))));
+}
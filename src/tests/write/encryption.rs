@@ -0,0 +1,179 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncWriteExt, Cursor};
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, Encryption, ZipEntryBuilder};
+
+const CONTENT: &[u8] = b"Some plaintext content that should survive encryption.\n";
+
+/// Writes a single entry with each of the whole, stream, and seekable writers, returning the resulting archives.
+async fn write_archives(encryption: Encryption, compression: Compression) -> Vec<Vec<u8>> {
+    let builder = || ZipEntryBuilder::new("file".into(), compression).encryption(encryption, "secret");
+
+    let mut whole = ZipFileWriter::new(Vec::new());
+    whole.write_entry_whole(builder(), CONTENT).await.unwrap();
+
+    let mut stream = ZipFileWriter::new(Vec::new());
+    let mut entry_writer = stream.write_entry_stream(builder()).await.unwrap();
+    entry_writer.write_all(CONTENT).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let mut seekable = ZipFileWriter::new(Cursor::new(Vec::new()));
+    let mut entry_writer = seekable.write_entry_seekable(builder()).await.unwrap();
+    entry_writer.write_all(CONTENT).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    vec![whole.close().await.unwrap(), stream.close().await.unwrap(), seekable.close().await.unwrap().into_inner()]
+}
+
+async fn assert_round_trips(encryption: Encryption, compression: Compression) {
+    for data in write_archives(encryption, compression).await {
+        assert!(!data.windows(CONTENT.len()).any(|window| window == CONTENT));
+
+        let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap().with_password("secret");
+        let entry = &reader.file().entries()[0];
+        assert!(entry.encrypted());
+        assert_eq!(entry.compression(), compression);
+
+        let mut entry = reader.reader_with_entry(0).await.unwrap();
+        let mut buffer = Vec::new();
+        entry.read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, CONTENT);
+
+        // The stream reader doesn't know the CRC32 value of entries followed by a data descriptor upfront.
+        let reader = crate::base::read::stream::ZipFileReader::new(data.as_slice()).with_password("secret");
+        let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+        let mut buffer = Vec::new();
+        futures_lite::io::AsyncReadExt::read_to_end(entry.reader_mut(), &mut buffer).await.unwrap();
+        assert_eq!(buffer, CONTENT);
+        let (_, reader) = entry.done().await.unwrap();
+        assert!(reader.next_without_entry().await.unwrap().is_none());
+
+        // ZipCrypto only checks a single byte of the password's key stream, so one in 256 incorrect passwords pass the
+        // check and are instead caught by the CRC32 check (or the decompressor).
+        let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+        match reader.reader_with_entry_with_password(0, b"not the password").await {
+            Err(err) => assert!(matches!(err, ZipError::IncorrectPassword)),
+            Ok(mut entry) => assert!(entry.read_to_end_checked(&mut Vec::new()).await.is_err()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn zipcrypto_stored_round_trip() {
+    assert_round_trips(Encryption::ZipCrypto, Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn zipcrypto_deflate_round_trip() {
+    assert_round_trips(Encryption::ZipCrypto, Compression::Deflate).await;
+}
+
+#[tokio::test]
+async fn zipcrypto_interoperates_with_zip_crate() {
+    for data in write_archives(Encryption::ZipCrypto, Compression::Stored).await {
+        let mut zip = zip::read::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+        let mut file = zip.by_name_decrypt("file", b"secret").unwrap();
+        let mut contents = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut contents).unwrap();
+        assert_eq!(contents, CONTENT);
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_interoperates_with_zip_crate() {
+    use crate::AesStrength;
+
+    for strength in [AesStrength::Aes128, AesStrength::Aes192, AesStrength::Aes256] {
+        for data in write_archives(Encryption::Aes(strength), Compression::Stored).await {
+            let mut zip = zip::read::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+            let mut file = zip.by_name_decrypt("file", b"secret").unwrap();
+            let mut contents = Vec::new();
+            std::io::Read::read_to_end(&mut file, &mut contents).unwrap();
+            assert_eq!(contents, CONTENT);
+        }
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_stored_round_trip() {
+    use crate::AesStrength;
+
+    for strength in [AesStrength::Aes128, AesStrength::Aes192, AesStrength::Aes256] {
+        assert_round_trips(Encryption::Aes(strength), Compression::Stored).await;
+    }
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn aes_deflate_round_trip() {
+    assert_round_trips(Encryption::Aes(crate::AesStrength::Aes256), Compression::Deflate).await;
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_writes_ae2_headers() {
+    use crate::spec::header::{AesStrength, AesVendorVersion, ExtraField};
+
+    let encryption = Encryption::Aes(AesStrength::Aes256);
+    for data in write_archives(encryption, Compression::Stored).await {
+        // The local file header should record method 99 and omit the CRC32 value.
+        assert_eq!(u16::from_le_bytes(data[8..10].try_into().unwrap()), 99);
+        assert_eq!(u32::from_le_bytes(data[14..18].try_into().unwrap()), 0);
+
+        let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+        let entry = &reader.file().entries()[0];
+        assert_eq!(entry.crc32(), 0);
+
+        let field = entry.extra_fields().iter().find_map(|field| match field {
+            ExtraField::WinZipAes(field) => Some(field),
+            _ => None,
+        });
+        let field = field.expect("expected a WinZip AES extra field");
+        assert_eq!(field.vendor_version, AesVendorVersion::Ae2);
+        assert_eq!(field.strength, AesStrength::Aes256);
+        assert_eq!(field.compression, Compression::Stored);
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_salts_differ_between_entries() {
+    use crate::AesStrength;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for name in ["first", "second"] {
+        let builder = ZipEntryBuilder::new(name.into(), Compression::Stored)
+            .encryption(Encryption::Aes(AesStrength::Aes256), "secret");
+        writer.write_entry_whole(builder, CONTENT).await.unwrap();
+    }
+    let data = writer.close().await.unwrap();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let salts: Vec<_> = reader
+        .file()
+        .entries()
+        .iter()
+        .map(|entry| {
+            let start = (entry.header_offset() + entry.header_size()) as usize;
+            &data[start..start + AesStrength::Aes256.salt_length()]
+        })
+        .collect();
+    assert_ne!(salts[0], salts[1]);
+}
+
+#[tokio::test]
+async fn passwords_are_not_exposed() {
+    let builder = ZipEntryBuilder::new("file".into(), Compression::Stored).encryption(Encryption::ZipCrypto, "hunter2");
+    assert!(!format!("{:?}", builder.0).contains("hunter2"));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, CONTENT).await.unwrap();
+    assert!(writer.cd_entries.iter().all(|entry| entry.entry.encryption.is_none()));
+}
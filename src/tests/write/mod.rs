@@ -16,7 +16,12 @@ use crate::spec::header::ExtraField;
 use crate::ZipDateTime;
 use crate::{Compression, ZipEntryBuilder};
 
+mod append;
+mod copy;
+#[cfg(feature = "encryption")]
+mod encryption;
 pub(crate) mod offset;
+mod split;
 #[cfg(target_pointer_width = "64")]
 mod zip64;
 
@@ -531,3 +536,13 @@ fn large_central_directory_size_errors_without_zip64() {
     assert!(matches!(result, Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile))));
     assert!(!is_zip64);
 }
+
+#[cfg(not(feature = "encryption"))]
+#[tokio::test]
+async fn encrypted_writes_require_the_encryption_feature() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder =
+        ZipEntryBuilder::new("file".into(), Compression::Stored).encryption(crate::Encryption::ZipCrypto, "secret");
+    let result = writer.write_entry_whole(builder, b"data").await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+}
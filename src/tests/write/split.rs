@@ -0,0 +1,165 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+use futures_lite::io::{AsyncWriteExt, BufReader, Cursor};
+
+use crate::base::read::split::SegmentedReader;
+use crate::base::read::{mem, seek};
+use crate::base::write::split::{SegmentFactory, MIN_SEGMENT_SIZE};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{
+    EOCDR_SIGNATURE, LFH_LENGTH, SIGNATURE_LENGTH, SPANNING_SIGNATURE, TEMPORARY_SPANNING_MARKER,
+};
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::tests::common::TempDir;
+use crate::{Compression, ZipEntryBuilder};
+
+type SplitReader = seek::ZipFileReader<BufReader<SegmentedReader<Cursor<Vec<u8>>>>>;
+
+/// Collects each finished segment in memory.
+#[derive(Default)]
+struct Segments(Vec<Vec<u8>>);
+
+impl SegmentFactory<Vec<u8>> for Segments {
+    fn create_segment(&mut self, disk: u32) -> std::io::Result<Vec<u8>> {
+        assert_eq!(disk as usize, self.0.len() + usize::from(disk > 0));
+        Ok(Vec::new())
+    }
+
+    fn finish_segment(&mut self, disk: u32, segment: Vec<u8>) -> std::io::Result<()> {
+        assert_eq!(disk as usize, self.0.len());
+        self.0.push(segment);
+        Ok(())
+    }
+}
+
+async fn read_split(segments: &[Vec<u8>]) -> SplitReader {
+    let segments = segments.iter().cloned().map(Cursor::new).collect();
+    seek::ZipFileReader::new_split(segments).await.unwrap()
+}
+
+async fn read_entry(reader: &mut SplitReader, index: usize) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn entries_span_segments() {
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, Segments::default()).await.unwrap();
+
+    let data: Vec<Vec<u8>> = (0..3_u8).map(|index| vec![index; 50_000]).collect();
+    writer.write_entry_whole(ZipEntryBuilder::new("whole".into(), Compression::Stored), &data[0]).await.unwrap();
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("stream".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(&data[1]).await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("last".into(), Compression::Stored), &data[2]).await.unwrap();
+
+    let (last, Segments(mut segments)) = writer.close().await.unwrap().into_inner().await.unwrap();
+    segments.push(last);
+
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[0][..4], SPANNING_SIGNATURE.to_le_bytes());
+    assert!(segments.iter().all(|segment| segment.len() as u64 <= MIN_SEGMENT_SIZE));
+    assert!(segments.last().unwrap().windows(4).any(|window| window == EOCDR_SIGNATURE.to_le_bytes()));
+
+    let mut reader = read_split(&segments).await;
+    assert_eq!(reader.file().entries().len(), 3);
+    for (index, expected) in data.iter().enumerate() {
+        assert_eq!(&read_entry(&mut reader, index).await, expected);
+    }
+    assert!(reader.test_all().await.is_ok());
+
+    let copied = segments.concat();
+    // The stream entry's ZIP64 records point within the final segment, so aren't found when concatenated.
+    assert!(mem::ZipFileReader::new(copied).await.is_err());
+}
+
+#[tokio::test]
+async fn headers_are_not_split() {
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, Segments::default()).await.unwrap();
+
+    // Leave too little room in the first segment for the next entry's local file header.
+    let header_length = (SIGNATURE_LENGTH + LFH_LENGTH + 1) as u64;
+    let first = vec![1; (MIN_SEGMENT_SIZE - SIGNATURE_LENGTH as u64 - header_length - 10) as usize];
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), &first).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b".into(), Compression::Stored), b"second").await.unwrap();
+
+    let (last, Segments(mut segments)) = writer.close().await.unwrap().into_inner().await.unwrap();
+    segments.push(last);
+
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].len() as u64, MIN_SEGMENT_SIZE - 10);
+
+    let mut reader = read_split(&segments).await;
+    assert_eq!(reader.file().entries()[1].header_offset(), segments[0].len() as u64);
+    assert_eq!(read_entry(&mut reader, 0).await, first);
+    assert_eq!(read_entry(&mut reader, 1).await, b"second");
+}
+
+#[tokio::test]
+async fn single_segment_is_readable_as_a_whole() {
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, |_| Ok(Vec::new())).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("a".into(), Compression::Stored), b"data").await.unwrap();
+    let (data, _) = writer.close().await.unwrap().into_inner().await.unwrap();
+    assert_eq!(data[..4], TEMPORARY_SPANNING_MARKER.to_le_bytes());
+
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].header_offset(), SIGNATURE_LENGTH as u64);
+    assert!(reader.test_all().await.is_ok());
+    assert!(reader.verify().await.unwrap().is_consistent());
+}
+
+#[tokio::test]
+async fn segment_size_is_validated() {
+    let result = ZipFileWriter::new_split(MIN_SEGMENT_SIZE - 1, |_| Ok(Vec::new())).await;
+    assert!(matches!(result, Err(ZipError::InvalidSplitSegmentSize(size)) if size == MIN_SEGMENT_SIZE - 1));
+}
+
+#[tokio::test]
+async fn oversized_header_is_rejected() {
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, |_| Ok(Vec::new())).await.unwrap();
+    let extra_field = UnknownExtraField { header_id: HeaderId(0xCAFE), data_size: 1024, content: vec![0; 1024] };
+    let entry = ZipEntryBuilder::new("a".repeat(u16::MAX as usize).into(), Compression::Stored)
+        .extra_fields(vec![ExtraField::Unknown(extra_field)]);
+
+    let result = writer.write_entry_whole(entry, b"data").await;
+    assert!(matches!(result, Err(ZipError::SplitRecordTooLarge(_))));
+}
+
+/// Runs an Info-ZIP tool within the provided directory, which must be installed.
+fn run_info_zip(program: &str, args: &[&str], directory: &std::path::Path) -> std::process::Output {
+    let output = std::process::Command::new(program).args(args).current_dir(directory).output();
+    output.unwrap_or_else(|err| panic!("failed to run `{program}`, which must be installed: {err}"))
+}
+
+#[tokio::test]
+async fn split_archives_are_read_by_info_zip() {
+    let temp = TempDir::new("split_write");
+    let directory = temp.path();
+
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, |_| Ok(Vec::new())).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("single".into(), Compression::Stored), b"data").await.unwrap();
+    let (single, _) = writer.close().await.unwrap().into_inner().await.unwrap();
+    std::fs::write(directory.join("single.zip"), &single).unwrap();
+
+    let mut writer = ZipFileWriter::new_split(MIN_SEGMENT_SIZE, Segments::default()).await.unwrap();
+    for index in 0..3_u8 {
+        let entry = ZipEntryBuilder::new(format!("file-{index}").into(), Compression::Stored);
+        writer.write_entry_whole(entry, &vec![index; 50_000]).await.unwrap();
+    }
+    let (last, Segments(segments)) = writer.close().await.unwrap().into_inner().await.unwrap();
+    for (disk, segment) in segments.iter().enumerate() {
+        std::fs::write(directory.join(format!("split.z{:02}", disk + 1)), segment).unwrap();
+    }
+    std::fs::write(directory.join("split.zip"), &last).unwrap();
+
+    let single = run_info_zip("unzip", &["-t", "single.zip"], directory);
+    assert!(single.status.success(), "{}", String::from_utf8_lossy(&single.stdout));
+    let joined = run_info_zip("zip", &["-s", "0", "split.zip", "--out", "joined.zip"], directory);
+    assert!(joined.status.success(), "{}", String::from_utf8_lossy(&joined.stdout));
+    let tested = run_info_zip("unzip", &["-t", "joined.zip"], directory);
+    assert!(tested.status.success(), "{}", String::from_utf8_lossy(&tested.stdout));
+}
@@ -0,0 +1,347 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Addition of whole directory trees on the file system to a ZIP file.
+//!
+//! Entries are added in a depth-first order, with each directory's children sorted by name, so that the same tree
+//! always results in the same archive. Each directory is added as an explicit entry before its children, and the tree
+//! is walked as entries are added (with files streamed from disk), rather than being loaded into memory up front.
+//!
+//! On Unix, each entry's permissions (and file type) are captured as its Unix mode. The modification time of each
+//! entry is stored in UTC; see [`ZipDateTime::from_system_time()`].
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::write::ZipFileWriter;
+//! # use async_zip::tokio::archive::{add_directory, AddDirectoryOptions, Symlinks};
+//! # use async_zip::Compression;
+//! # use async_zip::error::Result;
+//! #
+//! async fn run() -> Result<()> {
+//!     let mut writer = ZipFileWriter::with_tokio(tokio::fs::File::create("./foo.zip").await?);
+//!     let options = AddDirectoryOptions::new()
+//!         .symlinks(Symlinks::Follow)
+//!         .exclude(|path| path.ends_with(".git"))
+//!         .compression(|_| Compression::Stored);
+//!
+//!     add_directory(&mut writer, "./foo", &options).await?;
+//!     writer.close().await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::base::write::ZipFileWriter;
+use crate::error::{Result, ZipError};
+use crate::{Compression, ZipDateTime, ZipEntryBuilder};
+
+use std::collections::VecDeque;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures_lite::io::{AsyncSeek, AsyncWrite};
+use tokio::fs::File;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+/// How symbolic links within the directory tree are added.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Symlinks {
+    /// Add each link as an entry holding its target, with the Unix mode of a symbolic link.
+    #[default]
+    Store,
+    /// Add the file or directory which each link points to in place of the link.
+    ///
+    /// Links to a directory which contains them (and so would form a cycle) are skipped.
+    Follow,
+    /// Leave links out of the archive.
+    Skip,
+}
+
+type PathFilter = dyn Fn(&Path) -> bool + Send + Sync;
+type CompressionChooser = dyn Fn(&Path) -> Compression + Send + Sync;
+
+/// Options which control how a directory tree is added to a ZIP file.
+///
+/// All paths provided to predicates are relative to the root of the tree being added.
+#[derive(Clone, Default)]
+pub struct AddDirectoryOptions {
+    symlinks: Symlinks,
+    include: Option<Arc<PathFilter>>,
+    exclude: Option<Arc<PathFilter>>,
+    compression: Option<Arc<CompressionChooser>>,
+}
+
+impl AddDirectoryOptions {
+    /// Constructs the default options; storing symbolic links, adding all files, without compression.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how symbolic links within the directory tree are added.
+    pub fn symlinks(mut self, symlinks: Symlinks) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Sets a predicate which decides whether each file (or symbolic link) should be added.
+    ///
+    /// Directories are always descended into, so that files within them can be included, but are only added if
+    /// something within them is.
+    pub fn include<F>(mut self, include: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.include = Some(Arc::new(include));
+        self
+    }
+
+    /// Sets a predicate which decides whether each file or directory should be left out.
+    ///
+    /// Excluded directories aren't descended into.
+    pub fn exclude<F>(mut self, exclude: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.exclude = Some(Arc::new(exclude));
+        self
+    }
+
+    /// Sets a function which chooses the compression method of each file.
+    pub fn compression<F>(mut self, compression: F) -> Self
+    where
+        F: Fn(&Path) -> Compression + Send + Sync + 'static,
+    {
+        self.compression = Some(Arc::new(compression));
+        self
+    }
+}
+
+/// Adds a directory tree to a ZIP file, streaming each file via [`ZipFileWriter::write_entry_stream()`].
+pub async fn add_directory<W, P>(writer: &mut ZipFileWriter<W>, root: P, options: &AddDirectoryOptions) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    P: AsRef<Path>,
+{
+    add_entries::<W, Stream>(writer, root.as_ref(), options).await
+}
+
+/// Adds a directory tree to a ZIP file, streaming each file via [`ZipFileWriter::write_entry_seekable()`].
+pub async fn add_directory_seekable<W, P>(
+    writer: &mut ZipFileWriter<W>,
+    root: P,
+    options: &AddDirectoryOptions,
+) -> Result<()>
+where
+    W: AsyncWrite + AsyncSeek + Unpin,
+    P: AsRef<Path>,
+{
+    add_entries::<W, Seekable>(writer, root.as_ref(), options).await
+}
+
+/// Walks a directory tree, adding each file via `F` and each directory or symbolic link as a whole entry.
+async fn add_entries<W, F>(writer: &mut ZipFileWriter<W>, root: &Path, options: &AddDirectoryOptions) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    F: WriteFile<W>,
+{
+    let mut walk = Walk::new(root, options).await?;
+    while let Some(item) = walk.next().await? {
+        match item.kind {
+            Kind::File => F::write_file(writer, item.builder, File::open(&item.path).await?).await?,
+            Kind::Directory => writer.write_entry_whole(item.builder, &[]).await?,
+            Kind::Symlink(target) => writer.write_entry_whole(item.builder, target.as_bytes()).await?,
+        }
+    }
+    Ok(())
+}
+
+/// How a file's data is written to its entry.
+trait WriteFile<W> {
+    async fn write_file(writer: &mut ZipFileWriter<W>, builder: ZipEntryBuilder, file: File) -> Result<()>;
+}
+
+/// Writes files via [`ZipFileWriter::write_entry_stream()`].
+struct Stream;
+
+/// Writes files via [`ZipFileWriter::write_entry_seekable()`].
+struct Seekable;
+
+impl<W> WriteFile<W> for Stream
+where
+    W: AsyncWrite + Unpin,
+{
+    async fn write_file(writer: &mut ZipFileWriter<W>, builder: ZipEntryBuilder, file: File) -> Result<()> {
+        let mut entry_writer = writer.write_entry_stream(builder).await?;
+        futures_lite::io::copy(&mut file.compat(), &mut entry_writer).await?;
+        entry_writer.close().await
+    }
+}
+
+impl<W> WriteFile<W> for Seekable
+where
+    W: AsyncWrite + AsyncSeek + Unpin,
+{
+    async fn write_file(writer: &mut ZipFileWriter<W>, builder: ZipEntryBuilder, file: File) -> Result<()> {
+        let mut entry_writer = writer.write_entry_seekable(builder).await?;
+        futures_lite::io::copy(&mut file.compat(), &mut entry_writer).await?;
+        entry_writer.close().await
+    }
+}
+
+enum Kind {
+    File,
+    Directory,
+    Symlink(String),
+}
+
+struct Item {
+    path: PathBuf,
+    kind: Kind,
+    builder: ZipEntryBuilder,
+}
+
+/// A depth-first walk of a directory tree, producing the entries to add in order.
+struct Walk<'a> {
+    options: &'a AddDirectoryOptions,
+    /// The canonical paths of the directories being walked (alongside the depths of their children), from the root
+    /// down, so that links which form a cycle aren't followed.
+    ancestors: Vec<(usize, PathBuf)>,
+    /// The paths left to visit (in reverse order), alongside their paths relative to the root and their depths.
+    pending: Vec<(PathBuf, PathBuf, usize)>,
+    /// The directories (alongside their depths) which haven't been produced yet, as nothing within them has been
+    /// included so far. This is only used when an include predicate is set.
+    directories: Vec<(usize, Item)>,
+    ready: VecDeque<Item>,
+}
+
+impl<'a> Walk<'a> {
+    async fn new(root: &Path, options: &'a AddDirectoryOptions) -> Result<Self> {
+        let ancestors = vec![(0, tokio::fs::canonicalize(root).await?)];
+        let mut walk =
+            Self { options, ancestors, pending: Vec::new(), directories: Vec::new(), ready: VecDeque::new() };
+        walk.descend(root, Path::new(""), 0).await?;
+        Ok(walk)
+    }
+
+    /// Queues the children of a directory to be visited next.
+    async fn descend(&mut self, path: &Path, relative: &Path, depth: usize) -> Result<()> {
+        let children = children(path, relative).await?;
+        self.pending.extend(children.into_iter().rev().map(|(path, relative)| (path, relative, depth)));
+        Ok(())
+    }
+
+    /// Returns the next entry to add, or `None` once the whole tree has been walked.
+    async fn next(&mut self) -> Result<Option<Item>> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Ok(Some(item));
+            }
+            let Some((path, relative, depth)) = self.pending.pop() else {
+                return Ok(None);
+            };
+
+            // Any directories which aren't ancestors of this path had nothing within them included.
+            while self.directories.last().is_some_and(|(directory, _)| *directory >= depth) {
+                self.directories.pop();
+            }
+            while self.ancestors.last().is_some_and(|(children, _)| *children > depth) {
+                self.ancestors.pop();
+            }
+            if self.options.exclude.as_ref().is_some_and(|exclude| exclude(&relative)) {
+                continue;
+            }
+
+            let mut metadata = tokio::fs::symlink_metadata(&path).await?;
+            let mut kind = None;
+            if metadata.is_symlink() {
+                match self.options.symlinks {
+                    Symlinks::Skip => continue,
+                    Symlinks::Store => {
+                        let target = tokio::fs::read_link(&path).await?;
+                        let target = target.to_str().ok_or(ZipError::StringNotUtf8)?.replace('\\', "/");
+                        kind = Some(Kind::Symlink(target));
+                    }
+                    Symlinks::Follow => metadata = tokio::fs::metadata(&path).await?,
+                }
+            }
+
+            let kind = match kind {
+                Some(kind) => kind,
+                None if metadata.is_dir() => {
+                    let canonical = tokio::fs::canonicalize(&path).await?;
+                    if self.ancestors.iter().any(|(_, ancestor)| *ancestor == canonical) {
+                        continue;
+                    }
+
+                    self.ancestors.push((depth + 1, canonical));
+                    self.descend(&path, &relative, depth + 1).await?;
+                    let builder = entry_builder(&relative, &Kind::Directory, &metadata, self.options)?;
+                    let item = Item { path, kind: Kind::Directory, builder };
+                    match self.options.include {
+                        Some(_) => self.directories.push((depth, item)),
+                        None => self.ready.push_back(item),
+                    }
+                    continue;
+                }
+                None => Kind::File,
+            };
+
+            if !self.options.include.as_ref().is_none_or(|include| include(&relative)) {
+                continue;
+            }
+
+            let builder = entry_builder(&relative, &kind, &metadata, self.options)?;
+            self.ready.extend(self.directories.drain(..).map(|(_, directory)| directory));
+            self.ready.push_back(Item { path, kind, builder });
+        }
+    }
+}
+
+/// Returns the children of a directory sorted by name, alongside their paths relative to the root.
+async fn children(path: &Path, relative: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut children = Vec::new();
+    let mut entries = tokio::fs::read_dir(path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        children.push((entry.path(), relative.join(entry.file_name())));
+    }
+
+    children.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(children)
+}
+
+fn entry_builder(
+    relative: &Path,
+    kind: &Kind,
+    metadata: &Metadata,
+    options: &AddDirectoryOptions,
+) -> Result<ZipEntryBuilder> {
+    let mut name = Vec::new();
+    for component in relative.components() {
+        name.push(component.as_os_str().to_str().ok_or(ZipError::StringNotUtf8)?);
+    }
+    let mut name = name.join("/");
+
+    let compression = match kind {
+        Kind::File => options.compression.as_ref().map_or(Compression::Stored, |compression| compression(relative)),
+        Kind::Directory => {
+            name.push('/');
+            Compression::Stored
+        }
+        Kind::Symlink(_) => Compression::Stored,
+    };
+
+    let mut builder = ZipEntryBuilder::new(name.into(), compression);
+    if let Some(date) = metadata.modified().ok().and_then(ZipDateTime::from_system_time) {
+        builder = builder.last_modification_date(date);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        // This includes the file type, so stored symbolic links are marked as such.
+        builder = builder.unix_permissions(metadata.permissions().mode() as u16);
+    }
+
+    Ok(builder)
+}
@@ -0,0 +1,383 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! Extraction of whole ZIP files to a directory on the file system.
+//!
+//! Entry names are untrusted input, so each is resolved to a path within the target directory before anything is
+//! written:
+//! - Absolute paths, Windows drive prefixes, and `..` components are rejected with [`ZipError::UnsafeEntryPath`].
+//! - `.` components, empty components, and backslash separators are normalised away.
+//! - Paths are created one component at a time without following symbolic links, so a link which already exists
+//!   within the target directory can't be used to escape it.
+//! - Names which aren't valid UTF-8 are decoded lossily (ie. with invalid sequences replaced by `U+FFFD`).
+//!
+//! Entries which represent symbolic links aren't extracted, failing with [`ZipError::SymlinkEntry`] unless
+//! [`ExtractOptions::skip_symlinks()`] is set. Permissions are limited to their read, write, and execute bits (ie.
+//! set-user-ID, set-group-ID, and sticky bits are dropped). As the MS-DOS modification time stored for an entry has no
+//! associated time zone, it's interpreted as UTC.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::tokio::extract::{extract, ExtractOptions, Overwrite};
+//! # use async_zip::tokio::read::fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!     let options = ExtractOptions::new()
+//!         .overwrite(Overwrite::Replace)
+//!         .filter(|entry| !entry.filename().as_bytes().starts_with(b"__MACOSX/"))
+//!         .concurrency(8);
+//!
+//!     extract(&reader, "./output", &options).await
+//! }
+//! ```
+
+use crate::base::read::io::entry::{WithEntry, ZipEntryReader};
+use crate::base::read::seek;
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::tokio::read::fs;
+
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use futures_lite::io::{AsyncBufRead, AsyncSeek};
+use tokio::fs::OpenOptions;
+use tokio_util::compat::TokioAsyncWriteCompatExt;
+
+/// The file type bits of a Unix mode which identify a symbolic link.
+const UNIX_SYMLINK_MODE: u16 = 0o120000;
+const UNIX_FILE_TYPE_MASK: u16 = 0o170000;
+const UNIX_PERMISSIONS_MASK: u16 = 0o777;
+
+/// What to do when an entry would be extracted to a path which already exists.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Overwrite {
+    /// Fail with [`ZipError::ExtractTargetExists`].
+    #[default]
+    Error,
+    /// Leave the existing file in place and move on to the next entry.
+    Skip,
+    /// Remove the existing file (or symbolic link) and extract the entry in its place.
+    Replace,
+}
+
+type EntryFilter = dyn Fn(&ZipEntry) -> bool + Send + Sync;
+
+/// Options which control how a ZIP file is extracted.
+#[derive(Clone)]
+pub struct ExtractOptions {
+    overwrite: Overwrite,
+    filter: Option<Arc<EntryFilter>>,
+    concurrency: usize,
+    skip_symlinks: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self { overwrite: Overwrite::default(), filter: None, concurrency: 1, skip_symlinks: false }
+    }
+}
+
+impl ExtractOptions {
+    /// Constructs the default options; failing on existing files, extracting all entries, one at a time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what to do when an entry would be extracted to a path which already exists.
+    pub fn overwrite(mut self, overwrite: Overwrite) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Sets a filter which decides whether each entry should be extracted.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&ZipEntry) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Sets the maximum number of entries extracted concurrently.
+    ///
+    /// This only applies to [`extract()`], as the reader used by [`extract_seek()`] can only read one entry at a time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Skips entries which represent symbolic links, rather than failing with [`ZipError::SymlinkEntry`].
+    pub fn skip_symlinks(mut self) -> Self {
+        self.skip_symlinks = true;
+        self
+    }
+
+    fn includes(&self, entry: &ZipEntry) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter(entry))
+    }
+}
+
+/// Extracts all entries from a file system ZIP reader into a directory, creating it if needed.
+///
+/// Up to [`ExtractOptions::concurrency()`] entries are extracted concurrently, each with its own file handle.
+pub async fn extract<P>(reader: &fs::ZipFileReader, directory: P, options: &ExtractOptions) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let directory = directory.as_ref();
+    tokio::fs::create_dir_all(directory).await?;
+
+    let mut directories = Vec::new();
+    crate::utils::for_each_concurrent(
+        reader.file().entries().len(),
+        options.concurrency,
+        |index| extract_entry(reader, index, directory, options),
+        |index, result| {
+            directories.extend(result?.map(|path| (path, index)));
+            Ok::<_, ZipError>(())
+        },
+    )
+    .await?;
+
+    finish_directories(directories.into_iter().map(|(path, index)| (path, &reader.file().entries()[index].entry))).await
+}
+
+/// Extracts the entry at the provided index from a file system ZIP reader, returning the path of the directory it
+/// represents (if any) so that its permissions can be set once every entry has been extracted.
+async fn extract_entry(
+    reader: &fs::ZipFileReader,
+    index: usize,
+    directory: &Path,
+    options: &ExtractOptions,
+) -> Result<Option<PathBuf>> {
+    let stored = &reader.file().entries()[index];
+    if !options.includes(stored) {
+        return Ok(None);
+    }
+
+    match prepare(directory, stored, options).await? {
+        Target::Skip => Ok(None),
+        Target::Directory(path) => Ok(Some(path)),
+        Target::File(path) => {
+            write_file(&path, reader.reader_with_entry(index).await?).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Extracts all entries from a seekable ZIP reader into a directory, creating it if needed.
+pub async fn extract_seek<R, P>(
+    reader: &mut seek::ZipFileReader<R>,
+    directory: P,
+    options: &ExtractOptions,
+) -> Result<()>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+    P: AsRef<Path>,
+{
+    let directory = directory.as_ref();
+    tokio::fs::create_dir_all(directory).await?;
+
+    let mut directories = Vec::new();
+    for index in 0..reader.file().entries().len() {
+        let stored = &reader.file().entries()[index];
+        if !options.includes(stored) {
+            continue;
+        }
+
+        match prepare(directory, stored, options).await? {
+            Target::Skip => (),
+            Target::Directory(path) => directories.push((path, index)),
+            Target::File(path) => write_file(&path, reader.reader_with_entry(index).await?).await?,
+        }
+    }
+
+    let file = reader.file();
+    finish_directories(directories.into_iter().map(|(path, index)| (path, &file.entries()[index].entry))).await
+}
+
+enum Target {
+    Skip,
+    Directory(PathBuf),
+    File(PathBuf),
+}
+
+/// Resolves an entry's name to a path relative to the target directory.
+///
+/// Returns `None` for names which resolve to the target directory itself (eg. `./`).
+fn entry_path(filename: &str) -> Result<Option<PathBuf>> {
+    let normalised = filename.replace('\\', "/");
+    let unsafe_path = || ZipError::UnsafeEntryPath(filename.to_string());
+
+    if normalised.starts_with('/') {
+        return Err(unsafe_path());
+    }
+
+    let mut path = PathBuf::new();
+    for (index, component) in normalised.split('/').enumerate() {
+        match component {
+            "" | "." => continue,
+            ".." => return Err(unsafe_path()),
+            // Reject Windows drive prefixes (eg. `C:`) regardless of the platform being extracted on.
+            _ if index == 0 && component.len() >= 2 && component.as_bytes()[1] == b':' => return Err(unsafe_path()),
+            _ => path.push(component),
+        }
+    }
+
+    if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(unsafe_path());
+    }
+
+    Ok((!path.as_os_str().is_empty()).then_some(path))
+}
+
+/// Creates the parents of an entry's path, and decides where (and whether) it should be extracted.
+async fn prepare(directory: &Path, entry: &ZipEntry, options: &ExtractOptions) -> Result<Target> {
+    let filename = String::from_utf8_lossy(entry.filename().as_bytes());
+    if entry.unix_permissions().is_some_and(is_symlink) {
+        if options.skip_symlinks {
+            return Ok(Target::Skip);
+        }
+        return Err(ZipError::SymlinkEntry(filename.into_owned()));
+    }
+    let Some(relative) = entry_path(&filename)? else {
+        return Ok(Target::Skip);
+    };
+
+    let is_directory = filename.ends_with('/');
+    let mut components = relative.components().peekable();
+    let mut path = directory.to_path_buf();
+    while let Some(component) = components.next() {
+        path.push(component);
+        if components.peek().is_none() && !is_directory {
+            break;
+        }
+        create_directory(&path, &filename).await?;
+    }
+
+    if is_directory {
+        return Ok(Target::Directory(path));
+    }
+
+    match tokio::fs::symlink_metadata(&path).await {
+        Ok(_) => match options.overwrite {
+            Overwrite::Error => Err(ZipError::ExtractTargetExists(path)),
+            Overwrite::Skip => Ok(Target::Skip),
+            Overwrite::Replace => {
+                // This removes (rather than follows) a symbolic link, and fails for directories.
+                tokio::fs::remove_file(&path).await?;
+                Ok(Target::File(path))
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Target::File(path)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Creates a single directory, failing if a symbolic link (or anything other than a directory) is in its place.
+async fn create_directory(path: &Path, filename: &str) -> Result<()> {
+    loop {
+        match tokio::fs::symlink_metadata(path).await {
+            Ok(metadata) if metadata.is_dir() => return Ok(()),
+            Ok(metadata) if metadata.is_symlink() => return Err(ZipError::UnsafeEntryPath(filename.to_string())),
+            Ok(_) => return Err(ZipError::ExtractTargetExists(path.to_path_buf())),
+            Err(err) if err.kind() == ErrorKind::NotFound => (),
+            Err(err) => return Err(err.into()),
+        }
+
+        match tokio::fs::create_dir(path).await {
+            Ok(()) => return Ok(()),
+            // Another entry may have created it concurrently, so check what's now there.
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Writes an entry's data to a new file, verifying its CRC32 value and applying its metadata.
+///
+/// The file is removed if any of this fails, so that corrupt or partial data isn't left behind under the entry's name.
+async fn write_file<R>(path: &Path, reader: ZipEntryReader<'_, R, WithEntry<'_>>) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    // `create_new` never follows a symbolic link which has appeared at the path since it was checked.
+    let file = OpenOptions::new().write(true).create_new(true).open(path).await?;
+    let result = write_contents(path, file, reader).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+    result
+}
+
+async fn write_contents<R>(
+    path: &Path,
+    file: tokio::fs::File,
+    mut reader: ZipEntryReader<'_, R, WithEntry<'_>>,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut writer = file.compat_write();
+    futures_lite::io::copy(&mut reader, &mut writer).await?;
+    if !reader.hash_matches() {
+        return Err(ZipError::CRC32CheckError);
+    }
+
+    let file = writer.into_inner().into_std().await;
+    if let Some(modified) = reader.entry().last_modification_date().as_system_time() {
+        file.set_modified(modified)?;
+    }
+    set_permissions(path, reader.entry()).await
+}
+
+/// Applies the metadata of directory entries, once everything within them has been extracted.
+async fn finish_directories<'a>(directories: impl Iterator<Item = (PathBuf, &'a ZipEntry)>) -> Result<()> {
+    for (path, entry) in directories {
+        set_permissions(&path, entry).await?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn set_permissions(path: &Path, entry: &ZipEntry) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some(mode) = entry.unix_permissions().filter(|&mode| mode & UNIX_PERMISSIONS_MASK != 0) else {
+        return Ok(());
+    };
+    let mode = mode & UNIX_PERMISSIONS_MASK;
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(mode.into())).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn set_permissions(_path: &Path, _entry: &ZipEntry) -> Result<()> {
+    Ok(())
+}
+
+/// Returns whether a Unix mode represents a symbolic link.
+fn is_symlink(mode: u16) -> bool {
+    mode & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_MODE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_paths_stay_within_directory() {
+        assert_eq!(entry_path("a/b.txt").unwrap(), Some(PathBuf::from("a").join("b.txt")));
+        assert_eq!(entry_path("./a//b\\c/").unwrap(), Some(PathBuf::from("a").join("b").join("c")));
+        assert_eq!(entry_path("./").unwrap(), None);
+
+        for unsafe_path in ["/etc/passwd", "\\windows", "../escape", "a/../../escape", "a\\..\\b", "C:\\file", "c:file"]
+        {
+            assert!(matches!(entry_path(unsafe_path), Err(ZipError::UnsafeEntryPath(_))), "{unsafe_path}");
+        }
+    }
+}
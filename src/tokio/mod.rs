@@ -10,6 +10,7 @@
 //! - [`base::read::seek::ZipFileReader::with_tokio()`]
 //! - [`base::read::stream::ZipFileReader::with_tokio()`]
 //! - [`base::write::ZipFileWriter::with_tokio()`]
+//! - [`base::write::ZipFileWriter::append_with_tokio()`]
 //!
 //! As a result of Rust's type inference, we are able to reuse the [`base`] implementation's types with considerable
 //! ease. There only exists one caveat with their use; the types returned by these constructors contain a wrapping
@@ -24,6 +25,10 @@ use tokio;
 #[cfg(doc)]
 use tokio_util;
 
+#[cfg(feature = "tokio-fs")]
+pub mod archive;
+#[cfg(feature = "tokio-fs")]
+pub mod extract;
 pub mod read;
 
 pub mod write {
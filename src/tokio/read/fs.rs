@@ -67,10 +67,17 @@
 //! }
 //! ```
 
-#[cfg(doc)]
 use crate::base::read::seek;
 
+use crate::base::read::integrity::{self, TestReport};
+use crate::base::read::io::decrypt::read_encryption_header;
 use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::base::read::io::stored::StoredEntryReader;
+use crate::base::read::limits::{ReadLimits, SharedLimits};
+use crate::base::read::nested::{self, NestedOptions, NestedSource};
+use crate::base::read::options::ReadOptions;
+use crate::base::read::verify::VerificationReport;
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
@@ -79,6 +86,8 @@ use std::sync::Arc;
 
 use tokio::fs::File;
 use tokio::io::BufReader;
+
+use futures_lite::io::Take;
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
 
 struct Inner {
@@ -90,6 +99,8 @@ struct Inner {
 #[derive(Clone)]
 pub struct ZipFileReader {
     inner: Arc<Inner>,
+    password: Option<Arc<[u8]>>,
+    limits: Option<Arc<SharedLimits>>,
 }
 
 impl ZipFileReader {
@@ -98,7 +109,15 @@ impl ZipFileReader {
     where
         P: AsRef<Path>,
     {
-        let file = crate::base::read::file(File::open(&path).await?.compat()).await?;
+        Self::new_with_options(path, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path, parsing its central directory with the provided options.
+    pub async fn new_with_options<P>(path: P, options: ReadOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = crate::base::read::file(File::open(&path).await?.compat(), &options).await?;
         Ok(ZipFileReader::from_raw_parts(path, file))
     }
 
@@ -109,7 +128,19 @@ impl ZipFileReader {
     where
         P: AsRef<Path>,
     {
-        ZipFileReader { inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }) }
+        ZipFileReader { inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }), password: None, limits: None }
+    }
+
+    /// Sets the password used to decrypt encrypted entries when no per-entry password is provided.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> ZipFileReader {
+        self.password = Some(Arc::from(password.as_ref()));
+        self
+    }
+
+    /// Sets the limits applied to the data read from entries.
+    pub fn with_limits(mut self, limits: ReadLimits) -> ZipFileReader {
+        self.limits = Some(SharedLimits::new(limits));
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -122,21 +153,49 @@ impl ZipFileReader {
         &self.inner.path
     }
 
+    /// Tests the integrity of every entry, as described by [`TestReport`].
+    ///
+    /// Each entry is read via a newly opened [`File`], as with the entry readers.
+    pub async fn test_all(&self) -> TestReport {
+        self.test_all_concurrently(1).await
+    }
+
+    /// Tests the integrity of every entry as per [`ZipFileReader::test_all()`], with up to `concurrency` entries
+    /// being tested at once.
+    ///
+    /// The entries are tested concurrently on the calling task, so their file reads overlap; to also decompress them
+    /// in parallel, clone this reader and test subsets of entries on separate tasks.
+    pub async fn test_all_concurrently(&self, concurrency: usize) -> TestReport {
+        integrity::test_all(self.file().entries().len(), concurrency, |index| async move {
+            integrity::test_entry(self.reader_with_entry(index).await?).await
+        })
+        .await
+    }
+
+    /// Audits every entry's local file header against its central directory record, as described by
+    /// [`VerificationReport`].
+    pub async fn verify(&self) -> Result<VerificationReport> {
+        let reader = BufReader::new(File::open(&self.inner.path).await?).compat();
+        crate::base::read::verify::verify(reader, self.file()).await
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_without_entry(
         &self,
         index: usize,
     ) -> Result<ZipEntryReader<'static, Compat<BufReader<File>>, WithoutEntry>> {
-        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
-
-        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+        let (reader, _) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader)
+    }
 
-        Ok(ZipEntryReader::new_with_owned(
-            fs_file,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_without_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'static, Compat<BufReader<File>>, WithoutEntry>> {
+        let (reader, _) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader)
     }
 
     /// Returns a new entry reader if the provided index is valid.
@@ -144,17 +203,80 @@ impl ZipFileReader {
         &self,
         index: usize,
     ) -> Result<ZipEntryReader<'_, Compat<BufReader<File>>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, self.password.as_deref()).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a new entry reader for the entry with the provided name, if one exists.
+    ///
+    /// See [`ZipFile::entry_index()`] for how names are matched.
+    pub async fn reader_with_entry_by_name(
+        &self,
+        name: impl AsRef<[u8]>,
+    ) -> Result<ZipEntryReader<'_, Compat<BufReader<File>>, WithEntry<'_>>> {
+        let index = self.file().entry_index(name).ok_or(ZipError::EntryNameNotFound)?;
+        self.reader_with_entry(index).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid, decrypting the entry with the provided password.
+    pub async fn reader_with_entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, Compat<BufReader<File>>, WithEntry<'_>>> {
+        let (reader, stored_entry) = self.entry_reader(index, Some(password)).await?;
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a reader over the raw data of the entry at the provided index, if the index is valid.
+    ///
+    /// Exactly the entry's compressed size is read, with the data left as it's stored (ie. neither decompressed nor
+    /// decrypted), and without its CRC32 value being checked.
+    pub async fn reader_raw(&self, index: usize) -> Result<Take<Compat<BufReader<File>>>> {
+        let fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
+        crate::base::read::raw_entry_reader(fs_file, self.file(), index).await
+    }
+
+    /// Returns a seekable reader over the data of the Stored (and unencrypted) entry at the provided index.
+    ///
+    /// See [`StoredEntryReader`] for how this differs from the readers returned by the other methods.
+    pub async fn reader_stored(&self, index: usize) -> Result<StoredEntryReader<Compat<BufReader<File>>>> {
+        let fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
+        crate::base::read::stored_entry_reader(fs_file, self.file(), index).await
+    }
+
+    /// Opens the ZIP file nested within the entry at the provided index.
+    ///
+    /// See the [`nested`] module for how the entry is read.
+    pub async fn open_nested(
+        &self,
+        index: usize,
+        options: NestedOptions,
+    ) -> Result<seek::ZipFileReader<NestedSource<Compat<BufReader<File>>>>> {
+        let fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
+        nested::open(fs_file, self.file(), index, self.password.as_deref(), self.limits.clone(), &options).await
+    }
+
+    /// Opens the file, seeks to the data of the entry at the provided index, and constructs a reader over it.
+    async fn entry_reader(
+        &self,
+        index: usize,
+        password: Option<&[u8]>,
+    ) -> Result<(ZipEntryReader<'static, Compat<BufReader<File>>, WithoutEntry>, &StoredZipEntry)> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut fs_file = BufReader::new(File::open(&self.inner.path).await?).compat();
 
-        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+        stored_entry.seek_to_data_offset(&mut fs_file, self.inner.file.warnings.entry(index)).await?;
+        let decryption = read_encryption_header(&mut fs_file, stored_entry, password).await?;
 
         let reader = ZipEntryReader::new_with_owned(
             fs_file,
             stored_entry.entry.compression(),
             stored_entry.entry.compressed_size(),
-        );
+            decryption,
+        )
+        .with_limits(self.limits.clone(), Some(stored_entry.entry.uncompressed_size()));
 
-        Ok(reader.into_with_entry(stored_entry))
+        Ok((reader, stored_entry))
     }
 }
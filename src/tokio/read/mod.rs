@@ -7,6 +7,8 @@ use tokio_util::compat::Compat;
 
 #[cfg(feature = "tokio-fs")]
 pub mod fs;
+#[cfg(feature = "tokio-fs")]
+pub mod split;
 #[cfg(doc)]
 use crate::base;
 #[cfg(doc)]
@@ -0,0 +1,76 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over the segments of a split ZIP file on the file system.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::tokio::read::split;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     // Opens `./foo.z01`, `./foo.z02`, ..., followed by `./foo.zip`.
+//!     let mut reader = split::open("./foo.zip").await?;
+//!
+//!     let mut data = Vec::new();
+//!     let mut entry = reader.reader_without_entry(0).await?;
+//!     entry.read_to_end(&mut data).await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::base::read::options::ReadOptions;
+use crate::base::read::split::SegmentedReader;
+use crate::error::Result;
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use futures_lite::io::BufReader;
+use tokio::fs::File;
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+/// A seeking ZIP reader over the segments of a split ZIP file.
+pub type ZipFileReader = crate::base::read::seek::ZipFileReader<BufReader<SegmentedReader<Compat<File>>>>;
+
+/// Opens a split ZIP file from the path of its final segment (eg. `archive.zip`).
+///
+/// The preceding segments are found alongside it by replacing the extension with `.z01`, `.z02`, and so on, stopping
+/// at the first which doesn't exist. A ZIP file which isn't split is opened as a single segment.
+pub async fn open<P>(path: P) -> Result<ZipFileReader>
+where
+    P: AsRef<Path>,
+{
+    open_with_options(path, ReadOptions::default()).await
+}
+
+/// Opens a split ZIP file from the path of its final segment, parsing its central directory with the provided options.
+pub async fn open_with_options<P>(path: P, options: ReadOptions) -> Result<ZipFileReader>
+where
+    P: AsRef<Path>,
+{
+    let mut segments = Vec::new();
+    for path in segment_paths(path.as_ref()).await? {
+        segments.push(File::open(path).await?.compat());
+    }
+    ZipFileReader::new_split_with_options(segments, options).await
+}
+
+/// Returns the paths of every segment of a split ZIP file in order, given the path of its final segment.
+pub async fn segment_paths(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for number in 1_u32.. {
+        let segment = path.with_extension(format!("z{number:02}"));
+        match tokio::fs::metadata(&segment).await {
+            Ok(_) => paths.push(segment),
+            Err(err) if err.kind() == ErrorKind::NotFound => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    paths.push(path.to_path_buf());
+    Ok(paths)
+}
@@ -4,6 +4,10 @@
 use crate::error::{Result, ZipError};
 use futures_lite::io::{AsyncRead, AsyncReadExt};
 
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
 // Assert that the next four-byte signature read by a reader which impls AsyncRead matches the expected signature.
 pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expected: u32) -> Result<()> {
     let signature = {
@@ -16,3 +20,52 @@ pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expec
         actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
     }
 }
+
+/// Runs `task` for each index in `0..count`, with up to `concurrency` tasks in progress at once, passing the output of
+/// each to `complete` as it finishes.
+///
+/// The tasks are interleaved on the calling task rather than being spawned, so they overlap whilst waiting on I/O. The
+/// first error returned by `complete` is returned immediately, dropping any tasks still in progress.
+pub(crate) async fn for_each_concurrent<T, E, F, Fut, C>(
+    count: usize,
+    concurrency: usize,
+    mut task: F,
+    mut complete: C,
+) -> std::result::Result<(), E>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = T>,
+    C: FnMut(usize, T) -> std::result::Result<(), E>,
+{
+    let concurrency = concurrency.clamp(1, count.max(1));
+    let mut next = 0;
+    let mut running: Vec<(usize, Pin<Box<Fut>>)> = Vec::with_capacity(concurrency);
+
+    futures_lite::future::poll_fn(|cx| loop {
+        while running.len() < concurrency && next < count {
+            running.push((next, Box::pin(task(next))));
+            next += 1;
+        }
+
+        let mut finished = false;
+        let mut position = 0;
+        while position < running.len() {
+            match running[position].1.as_mut().poll(cx) {
+                Poll::Ready(output) => {
+                    let (index, _) = running.swap_remove(position);
+                    complete(index, output)?;
+                    finished = true;
+                }
+                Poll::Pending => position += 1,
+            }
+        }
+
+        if running.is_empty() && next == count {
+            return Poll::Ready(Ok(()));
+        }
+        if !finished {
+            return Poll::Pending;
+        }
+    })
+    .await
+}
@@ -0,0 +1,190 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(feature = "tokio-fs")]
+
+use std::path::Path;
+
+use async_zip::base::read::mem::ZipFileReader;
+use async_zip::base::write::ZipFileWriter;
+use async_zip::tokio::archive::{add_directory, add_directory_seekable, AddDirectoryOptions, Symlinks};
+use async_zip::Compression;
+
+#[allow(dead_code)]
+mod common;
+
+use common::TempDir;
+
+const SAMPLE_DATA: &str = "tests/test_inputs/sample_data";
+
+async fn archive(root: &Path, options: &AddDirectoryOptions) -> ZipFileReader {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    add_directory(&mut writer, root, options).await.unwrap();
+    ZipFileReader::new(writer.close().await.unwrap()).await.unwrap()
+}
+
+fn names(reader: &ZipFileReader) -> Vec<&str> {
+    reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect()
+}
+
+async fn contents(reader: &ZipFileReader, name: &str) -> Vec<u8> {
+    let index = names(reader).iter().position(|entry| *entry == name).unwrap();
+    let mut entry = reader.reader_with_entry(index).await.unwrap();
+    let mut buffer = Vec::new();
+    entry.read_to_end_checked(&mut buffer).await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn add_directory_tree() {
+    let reader = archive(Path::new(SAMPLE_DATA), &AddDirectoryOptions::new()).await;
+
+    assert_eq!(
+        names(&reader),
+        [
+            "alpha/",
+            "alpha/back_to_front.txt",
+            "alpha/front_to_back.txt",
+            "numeric/",
+            "numeric/forward.txt",
+            "numeric/reverse.txt",
+        ]
+    );
+    for name in ["alpha/back_to_front.txt", "numeric/forward.txt"] {
+        let expected = std::fs::read(Path::new(SAMPLE_DATA).join(name)).unwrap();
+        assert_eq!(contents(&reader, name).await, expected);
+    }
+}
+
+#[tokio::test]
+async fn add_directory_tree_seekable() {
+    let mut writer = ZipFileWriter::new(futures_lite::io::Cursor::new(Vec::new()));
+    add_directory_seekable(&mut writer, SAMPLE_DATA, &AddDirectoryOptions::new()).await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let mut zip = zip::read::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+    let mut file = zip.by_name("numeric/reverse.txt").unwrap();
+    let mut buffer = Vec::new();
+    std::io::Read::read_to_end(&mut file, &mut buffer).unwrap();
+    assert_eq!(buffer, std::fs::read(Path::new(SAMPLE_DATA).join("numeric/reverse.txt")).unwrap());
+}
+
+#[tokio::test]
+async fn filter_and_choose_compression() {
+    let options = AddDirectoryOptions::new()
+        .exclude(|path| path == Path::new("numeric"))
+        .include(|path| path.file_name().is_some_and(|name| name == "front_to_back.txt"))
+        .compression(|path| {
+            assert_eq!(path, Path::new("alpha/front_to_back.txt"));
+            Compression::Stored
+        });
+    let reader = archive(Path::new(SAMPLE_DATA), &options).await;
+
+    assert_eq!(names(&reader), ["alpha/", "alpha/front_to_back.txt"]);
+}
+
+#[tokio::test]
+async fn include_only_adds_directories_with_included_entries() {
+    let temp = TempDir::new("include_directories");
+    let root = temp.0.join("root");
+    for directory in ["a", "b", "c", "d/e"] {
+        std::fs::create_dir_all(root.join(directory)).unwrap();
+    }
+    std::fs::write(root.join("a").join("x.txt"), b"x").unwrap();
+    std::fs::write(root.join("b").join("y.log"), b"y").unwrap();
+    std::fs::write(root.join("d").join("e").join("z.txt"), b"z").unwrap();
+
+    let reader = archive(&root, &AddDirectoryOptions::new()).await;
+    assert_eq!(names(&reader), ["a/", "a/x.txt", "b/", "b/y.log", "c/", "d/", "d/e/", "d/e/z.txt"]);
+
+    let options =
+        AddDirectoryOptions::new().include(|path| path.extension().is_some_and(|extension| extension == "txt"));
+    let reader = archive(&root, &options).await;
+    assert_eq!(names(&reader), ["a/", "a/x.txt", "d/", "d/e/", "d/e/z.txt"]);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn compress_chosen_files() {
+    let options = AddDirectoryOptions::new().compression(|path| {
+        if path.starts_with("alpha") {
+            Compression::Deflate
+        } else {
+            Compression::Stored
+        }
+    });
+    let reader = archive(Path::new(SAMPLE_DATA), &options).await;
+
+    for entry in reader.file().entries() {
+        let name = entry.filename().as_str().unwrap();
+        let expected = if name.starts_with("alpha/") && !entry.dir().unwrap() {
+            Compression::Deflate
+        } else {
+            Compression::Stored
+        };
+        assert_eq!(entry.compression(), expected, "for {name}");
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn capture_permissions_and_modification_time() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, SystemTime};
+
+    let temp = TempDir::new("metadata");
+    let root = temp.0.join("root");
+    std::fs::create_dir_all(root.join("dir")).unwrap();
+    std::fs::set_permissions(root.join("dir"), std::fs::Permissions::from_mode(0o750)).unwrap();
+    let script = root.join("dir").join("script.sh");
+    std::fs::write(&script, b"#!/bin/sh\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_592_224_210);
+    std::fs::File::options().write(true).open(&script).unwrap().set_modified(modified).unwrap();
+
+    let reader = archive(&root, &AddDirectoryOptions::new()).await;
+
+    let entries = reader.file().entries();
+    assert_eq!(entries[0].unix_permissions(), Some(0o040750));
+    assert_eq!(entries[1].unix_permissions(), Some(0o100755));
+    assert_eq!(entries[1].last_modification_date().as_system_time(), Some(modified));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn symlink_handling() {
+    let temp = TempDir::new("symlinks");
+    let root = temp.0.join("root");
+    std::fs::create_dir_all(root.join("dir")).unwrap();
+    std::fs::write(root.join("dir").join("file.txt"), b"data").unwrap();
+    std::os::unix::fs::symlink("dir/file.txt", root.join("file_link")).unwrap();
+    // A link back to the root would otherwise be followed indefinitely.
+    std::os::unix::fs::symlink("..", root.join("dir").join("cycle")).unwrap();
+
+    let reader = archive(&root, &AddDirectoryOptions::new().symlinks(Symlinks::Store)).await;
+    assert_eq!(names(&reader), ["dir/", "dir/cycle", "dir/file.txt", "file_link"]);
+    assert_eq!(contents(&reader, "file_link").await, b"dir/file.txt");
+    let link = &reader.file().entries()[3];
+    assert_eq!(link.unix_permissions().unwrap() & 0o170000, 0o120000);
+
+    let reader = archive(&root, &AddDirectoryOptions::new().symlinks(Symlinks::Follow)).await;
+    assert_eq!(names(&reader), ["dir/", "dir/file.txt", "file_link"]);
+    assert_eq!(contents(&reader, "file_link").await, b"data");
+
+    let reader = archive(&root, &AddDirectoryOptions::new().symlinks(Symlinks::Skip)).await;
+    assert_eq!(names(&reader), ["dir/", "dir/file.txt"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn follow_links_which_sort_before_their_target() {
+    let temp = TempDir::new("link_order");
+    let root = temp.0.join("root");
+    std::fs::create_dir_all(root.join("z_dir")).unwrap();
+    std::fs::write(root.join("z_dir").join("file.txt"), b"data").unwrap();
+    std::os::unix::fs::symlink("z_dir", root.join("a_link")).unwrap();
+
+    let reader = archive(&root, &AddDirectoryOptions::new().symlinks(Symlinks::Follow)).await;
+    assert_eq!(names(&reader), ["a_link/", "a_link/file.txt", "z_dir/", "z_dir/file.txt"]);
+    assert_eq!(contents(&reader, "z_dir/file.txt").await, b"data");
+}
@@ -96,3 +96,25 @@ pub async fn check_decompress_mem(zip_data: Vec<u8>) {
         assert_eq!(output, expected, "for {fname}, expect zip data to match file data");
     }
 }
+
+/// A directory which is removed once dropped.
+#[allow(dead_code)]
+pub struct TempDir(pub std::path::PathBuf);
+
+#[allow(dead_code)]
+impl TempDir {
+    /// Creates an empty directory, named after the test binary and the provided name.
+    pub fn new(name: &str) -> Self {
+        let name = format!("async_zip_{}_{name}_{}", env!("CARGO_CRATE_NAME"), std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (c) 2025 Astral
+// MIT License (https://github.com/astral-sh/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(feature = "tokio-fs")]
+
+use std::path::{Path, PathBuf};
+
+use async_zip::base::write::ZipFileWriter;
+use async_zip::error::ZipError;
+use async_zip::tokio::extract::{extract, extract_seek, ExtractOptions, Overwrite};
+use async_zip::tokio::read::fs::ZipFileReader;
+use async_zip::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+#[allow(dead_code)]
+mod common;
+
+use common::TempDir;
+
+const STORE_ZIP_FILE: &str = "tests/test_inputs/sample_data.store.zip";
+const FILE_LIST: &[&str] = &[
+    "sample_data/alpha/back_to_front.txt",
+    "sample_data/alpha/front_to_back.txt",
+    "sample_data/numeric/forward.txt",
+    "sample_data/numeric/reverse.txt",
+];
+
+/// Writes an archive holding the provided entries, returning the path it was written to.
+async fn write_archive(directory: &Path, entries: Vec<(ZipEntryBuilder, &[u8])>) -> PathBuf {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (entry, data) in entries {
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let path = directory.join("archive.zip");
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+    path
+}
+
+fn stored(name: &str) -> ZipEntryBuilder {
+    ZipEntryBuilder::new(name.to_string().into(), Compression::Stored)
+}
+
+fn assert_sample_data(directory: &Path) {
+    for name in FILE_LIST {
+        let expected = std::fs::read(Path::new("tests/test_inputs").join(name)).unwrap();
+        assert_eq!(std::fs::read(directory.join(name)).unwrap(), expected, "for {name}");
+    }
+}
+
+#[tokio::test]
+async fn extract_fs_concurrently() {
+    let temp = TempDir::new("fs_concurrently");
+    let reader = ZipFileReader::new(STORE_ZIP_FILE).await.unwrap();
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new().concurrency(4)).await.unwrap();
+
+    assert_sample_data(&temp.0.join("out"));
+}
+
+#[tokio::test]
+async fn extract_seek_sequentially() {
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    let temp = TempDir::new("seek_sequentially");
+    let file = tokio::io::BufReader::new(tokio::fs::File::open(STORE_ZIP_FILE).await.unwrap());
+    let mut reader = async_zip::base::read::seek::ZipFileReader::new(file.compat()).await.unwrap();
+
+    extract_seek(&mut reader, &temp.0, &ExtractOptions::new()).await.unwrap();
+
+    assert_sample_data(&temp.0);
+}
+
+#[tokio::test]
+async fn reject_parent_components() {
+    let temp = TempDir::new("parent_components");
+    let archive = write_archive(&temp.0, vec![(stored("dir/../../escaped.txt"), b"data")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+
+    assert!(matches!(result, Err(ZipError::UnsafeEntryPath(_))));
+    assert!(!temp.0.join("escaped.txt").exists());
+}
+
+#[tokio::test]
+async fn reject_absolute_paths() {
+    let temp = TempDir::new("absolute_paths");
+    let archive = write_archive(&temp.0, vec![(stored("/tmp/absolute.txt"), b"data")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+
+    assert!(matches!(result, Err(ZipError::UnsafeEntryPath(_))));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn reject_symlink_escapes() {
+    let temp = TempDir::new("symlink_escapes");
+    let outside = temp.0.join("outside");
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::create_dir_all(temp.0.join("out")).unwrap();
+    std::os::unix::fs::symlink(&outside, temp.0.join("out").join("link")).unwrap();
+
+    let archive = write_archive(&temp.0, vec![(stored("link/nested/file.txt"), b"data")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+
+    assert!(matches!(result, Err(ZipError::UnsafeEntryPath(_))));
+    assert!(!outside.join("nested").exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn replace_symlink_rather_than_following_it() {
+    let temp = TempDir::new("replace_symlink");
+    let outside = temp.0.join("outside.txt");
+    std::fs::write(&outside, b"original").unwrap();
+    std::fs::create_dir_all(temp.0.join("out")).unwrap();
+    std::os::unix::fs::symlink(&outside, temp.0.join("out").join("file.txt")).unwrap();
+
+    let archive = write_archive(&temp.0, vec![(stored("file.txt"), b"data")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new().overwrite(Overwrite::Replace)).await.unwrap();
+
+    assert_eq!(std::fs::read(&outside).unwrap(), b"original");
+    assert_eq!(std::fs::read(temp.0.join("out").join("file.txt")).unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn overwrite_policies() {
+    let temp = TempDir::new("overwrite_policies");
+    let archive = write_archive(&temp.0, vec![(stored("file.txt"), b"new")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    let target = temp.0.join("out").join("file.txt");
+    std::fs::create_dir_all(temp.0.join("out")).unwrap();
+    std::fs::write(&target, b"existing").unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+    assert!(matches!(result, Err(ZipError::ExtractTargetExists(path)) if path == target));
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new().overwrite(Overwrite::Skip)).await.unwrap();
+    assert_eq!(std::fs::read(&target).unwrap(), b"existing");
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new().overwrite(Overwrite::Replace)).await.unwrap();
+    assert_eq!(std::fs::read(&target).unwrap(), b"new");
+}
+
+#[tokio::test]
+async fn filter_entries() {
+    let temp = TempDir::new("filter_entries");
+    let archive =
+        write_archive(&temp.0, vec![(stored("keep.txt"), b"keep"), (stored("__MACOSX/skip.txt"), b"skip")]).await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let options = ExtractOptions::new().filter(|entry| !entry.filename().as_bytes().starts_with(b"__MACOSX/"));
+    extract(&reader, temp.0.join("out"), &options).await.unwrap();
+
+    assert!(temp.0.join("out").join("keep.txt").exists());
+    assert!(!temp.0.join("out").join("__MACOSX").exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn apply_permissions_and_modification_time() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = TempDir::new("metadata");
+    let date = ZipDateTimeBuilder::new().year(2020).month(6).day(15).hour(12).minute(30).second(10).build();
+    let archive = write_archive(
+        &temp.0,
+        vec![
+            (stored("dir/").unix_permissions(0o040700), b""),
+            (stored("dir/script.sh").unix_permissions(0o100755).last_modification_date(date), b"#!/bin/sh\n"),
+        ],
+    )
+    .await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await.unwrap();
+
+    let directory = std::fs::metadata(temp.0.join("out").join("dir")).unwrap();
+    assert_eq!(directory.permissions().mode() & 0o777, 0o700);
+    let script = std::fs::metadata(temp.0.join("out").join("dir").join("script.sh")).unwrap();
+    assert_eq!(script.permissions().mode() & 0o777, 0o755);
+    let modified = script.modified().unwrap().duration_since(std::time::UNIX_EPOCH).unwrap();
+    assert_eq!(modified.as_secs(), 1_592_224_210);
+}
+
+#[tokio::test]
+async fn reject_or_skip_symlinks() {
+    let temp = TempDir::new("symlinks");
+    let archive = write_archive(
+        &temp.0,
+        vec![(stored("link").unix_permissions(0o120777), b"/etc/passwd"), (stored("file.txt"), b"data")],
+    )
+    .await;
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+    assert!(matches!(result, Err(ZipError::SymlinkEntry(name)) if name == "link"));
+    assert!(!temp.0.join("out").join("link").exists());
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new().skip_symlinks()).await.unwrap();
+    assert!(std::fs::symlink_metadata(temp.0.join("out").join("link")).is_err());
+    assert_eq!(std::fs::read(temp.0.join("out").join("file.txt")).unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn extract_non_utf8_names_lossily() {
+    let temp = TempDir::new("non_utf8_names");
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let name = async_zip::ZipString::new(b"caf\xe9.txt".to_vec(), async_zip::StringEncoding::Raw);
+    writer.write_entry_whole(ZipEntryBuilder::new(name, Compression::Stored), b"data").await.unwrap();
+    let archive = temp.0.join("archive.zip");
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await.unwrap();
+
+    assert_eq!(std::fs::read(temp.0.join("out").join("caf\u{FFFD}.txt")).unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn reject_crc_mismatch() {
+    let temp = TempDir::new("crc_mismatch");
+    let archive = write_archive(&temp.0, vec![(stored("file.txt"), b"original data")]).await;
+    let mut data = std::fs::read(&archive).unwrap();
+    let offset = data.windows(13).position(|window| window == b"original data").unwrap();
+    data[offset] = b'O';
+    std::fs::write(&archive, data).unwrap();
+    let reader = ZipFileReader::new(archive).await.unwrap();
+
+    let result = extract(&reader, temp.0.join("out"), &ExtractOptions::new()).await;
+
+    assert!(matches!(result, Err(ZipError::CRC32CheckError)));
+    assert!(!temp.0.join("out/file.txt").exists());
+}